@@ -0,0 +1,63 @@
+//! Throughput of paging vault accounts with a cold client per iteration versus a warm, tuned client
+//!
+//! Needs `FIREBLOCKS_API_KEY` and `FIREBLOCKS_SECRET` (sandbox), otherwise nothing is measured
+//!
+//! ```sh
+//! cargo bench --bench paging
+//! ```
+use criterion::{criterion_group, criterion_main, Criterion};
+use fireblocks_sdk::{Client, ClientBuilder, PagedClient};
+use futures::{StreamExt, TryStreamExt};
+use std::sync::Arc;
+use std::time::Duration;
+
+const PAGES: usize = 5;
+const BATCH: u16 = 50;
+
+fn builder(api_key: &str, secret: &[u8]) -> ClientBuilder {
+  ClientBuilder::new(api_key, secret).use_sandbox().with_timeout(Duration::from_secs(30))
+}
+
+fn tuned(api_key: &str, secret: &[u8]) -> color_eyre::Result<Client> {
+  Ok(
+    builder(api_key, secret)
+      .with_pool_max_idle_per_host(16)
+      .with_pool_idle_timeout(Duration::from_secs(90))
+      .with_tcp_keepalive(Duration::from_secs(30))
+      .with_http2_keep_alive(Duration::from_secs(20), Duration::from_secs(10))
+      .with_http2_adaptive_window(true)
+      .build()?,
+  )
+}
+
+async fn page(client: Client) -> color_eyre::Result<usize> {
+  let pages: Vec<_> = PagedClient::new(Arc::new(client)).vaults(BATCH).take(PAGES).try_collect().await?;
+  Ok(pages.len())
+}
+
+fn paging(c: &mut Criterion) {
+  let (Ok(api_key), Ok(secret)) = (std::env::var("FIREBLOCKS_API_KEY"), std::env::var("FIREBLOCKS_SECRET")) else {
+    eprintln!("FIREBLOCKS_API_KEY and FIREBLOCKS_SECRET are not set, skipping");
+    return;
+  };
+  let secret = secret.into_bytes();
+  let rt = tokio::runtime::Runtime::new().expect("runtime");
+  let warm = tuned(&api_key, &secret).expect("client");
+
+  let mut group = c.benchmark_group("paging");
+  group.sample_size(10);
+  group.bench_function("cold", |b| {
+    b.to_async(&rt).iter(|| async {
+      // a new client per iteration pays the TCP and TLS handshakes every time
+      let client = builder(&api_key, &secret).build().expect("client");
+      page(client).await.expect("paging failed")
+    });
+  });
+  group.bench_function("warm", |b| {
+    b.to_async(&rt).iter(|| async { page(warm.clone()).await.expect("paging failed") });
+  });
+  group.finish();
+}
+
+criterion_group!(benches, paging);
+criterion_main!(benches);
@@ -12,6 +12,6 @@ async fn main() -> color_eyre::Result<()> {
   let params = PagingVaultRequestBuilder::new().limit(10).build()?;
   let (vault_accounts, request_id) = client.vaults(params).await?;
   println!("Got requestId: {request_id}");
-  println!("vault accounts: {:#?}", vault_accounts.accounts);
+  println!("vault accounts: {:#?}", vault_accounts.items);
   Ok(())
 }
@@ -0,0 +1,326 @@
+//! Export and import external wallets (the whitelisted counterparty address book) as CSV
+//!
+//! The CSV has the columns `wallet,asset,address,tag`, one row per asset address of a wallet, and a
+//! wallet without assets has a row with only its name. An export of one workspace can be imported into
+//! another, and importing the same file twice creates nothing the second time.
+//!
+//! ```
+//! use fireblocks_sdk::address_book::{AddressBook, AddressBookRow};
+//! use fireblocks_sdk::Client;
+//!
+//! async fn migrate(from: Client, to: Client) -> color_eyre::Result<()> {
+//!   let csv = AddressBookRow::to_csv(&AddressBook::new(from).export().await?);
+//!   let rows = AddressBookRow::from_csv(csv.as_bytes())?;
+//!   let report = AddressBook::new(to).import(rows).await;
+//!   println!("{}", report.to_csv());
+//!   Ok(())
+//! }
+//! ```
+use crate::error::ParamError;
+use crate::types::WalletContainer;
+use crate::{csv, Client, FireblocksError};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddressBookRow {
+  /// Wallet name, unique in the address book
+  pub wallet: String,
+  /// Empty for a wallet without assets
+  pub asset: String,
+  pub address: String,
+  pub tag: Option<String>,
+}
+
+impl AddressBookRow {
+  /// Parse rows from CSV with the columns `wallet,asset,address,tag`, the tag column being optional
+  ///
+  /// A header line is skipped if present.
+  pub fn from_csv(reader: impl BufRead) -> Result<Vec<Self>, ParamError> {
+    let mut rows = Vec::new();
+    for (n, line) in reader.lines().enumerate() {
+      let line = line.map_err(|e| ParamError::InvalidParams { msg: format!("csv line {}: {e}", n + 1) })?;
+      if line.trim().is_empty() {
+        continue;
+      }
+      let fields = csv::parse_line(&line);
+      if n == 0 && fields.first().is_some_and(|f| f.eq_ignore_ascii_case("wallet")) {
+        continue;
+      }
+      let (wallet, asset, address, tag) = match fields.as_slice() {
+        [wallet, asset, address] => (wallet, asset, address, ""),
+        [wallet, asset, address, tag] => (wallet, asset, address, tag.as_str()),
+        _ => return Err(ParamError::InvalidParams { msg: format!("csv line {}: expected 3 or 4 columns", n + 1) }),
+      };
+      if wallet.is_empty() || asset.is_empty() != address.is_empty() {
+        return Err(ParamError::InvalidParams {
+          msg: format!("csv line {}: a wallet name and either both asset and address or neither", n + 1),
+        });
+      }
+      rows.push(Self {
+        wallet: wallet.clone(),
+        asset: asset.clone(),
+        address: address.clone(),
+        tag: Some(String::from(tag)).filter(|t| !t.is_empty()),
+      });
+    }
+    Ok(rows)
+  }
+
+  pub fn to_csv(rows: &[Self]) -> String {
+    let mut out = csv::line(["wallet", "asset", "address", "tag"]);
+    out.push('\n');
+    for r in rows {
+      out.push_str(&csv::line([&r.wallet, &r.asset, &r.address, r.tag.as_deref().unwrap_or_default()]));
+      out.push('\n');
+    }
+    out
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportOutcome {
+  /// The wallet or its asset address was created
+  Created {
+    wallet_id: String,
+  },
+  /// The address book already had the row, e.g. from a previous import
+  AlreadyExists {
+    wallet_id: String,
+  },
+  Failed {
+    error: String,
+  },
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportResult {
+  pub row: AddressBookRow,
+  pub outcome: ImportOutcome,
+}
+
+#[derive(Debug, Default)]
+pub struct ImportReport {
+  pub results: Vec<ImportResult>,
+}
+
+impl ImportReport {
+  pub fn failed(&self) -> impl Iterator<Item = &ImportResult> {
+    self.results.iter().filter(|r| matches!(r.outcome, ImportOutcome::Failed { .. }))
+  }
+
+  /// Render the report as CSV: `wallet,asset,address,tag,status,wallet_id,error`
+  pub fn to_csv(&self) -> String {
+    let mut out = csv::line(["wallet", "asset", "address", "tag", "status", "wallet_id", "error"]);
+    out.push('\n');
+    for r in &self.results {
+      let (status, id, error) = match &r.outcome {
+        ImportOutcome::Created { wallet_id } => ("CREATED", wallet_id.as_str(), ""),
+        ImportOutcome::AlreadyExists { wallet_id } => ("ALREADY_EXISTS", wallet_id.as_str(), ""),
+        ImportOutcome::Failed { error } => ("FAILED", "", error.as_str()),
+      };
+      let tag = r.row.tag.as_deref().unwrap_or_default();
+      out.push_str(&csv::line([&r.row.wallet, &r.row.asset, &r.row.address, tag, status, id, error]));
+      out.push('\n');
+    }
+    out
+  }
+}
+
+/// The external wallets of a workspace
+pub struct AddressBook {
+  client: Client,
+  interval: Duration,
+}
+
+impl AddressBook {
+  pub const fn new(client: Client) -> Self {
+    Self { client, interval: Duration::from_millis(250) }
+  }
+
+  /// Pause between API calls creating wallets and addresses, to stay under rate limits
+  #[allow(clippy::return_self_not_must_use)]
+  pub const fn with_interval(mut self, interval: Duration) -> Self {
+    self.interval = interval;
+    self
+  }
+
+  /// Every external wallet and asset address, ordered by wallet name
+  pub async fn export(&self) -> Result<Vec<AddressBookRow>, FireblocksError> {
+    let (mut wallets, _) = self.client.external_wallets().await?;
+    wallets.sort_by(|a, b| a.name.cmp(&b.name));
+    let mut rows = Vec::new();
+    for wallet in wallets {
+      if wallet.assets.is_empty() {
+        rows.push(AddressBookRow {
+          wallet: wallet.name.clone(),
+          asset: String::new(),
+          address: String::new(),
+          tag: None,
+        });
+      }
+      rows.extend(wallet.assets.into_iter().map(|asset| AddressBookRow {
+        wallet: wallet.name.clone(),
+        asset: asset.id,
+        address: asset.address,
+        tag: asset.tag.filter(|t| !t.is_empty()),
+      }));
+    }
+    Ok(rows)
+  }
+
+  /// Create the wallets and asset addresses of `rows` missing from the address book, returning a result
+  /// for each row in input order
+  ///
+  /// Wallets are matched by name and addresses by wallet and asset. A wallet holding another address
+  /// for the asset of a row fails that row rather than replacing the address.
+  pub async fn import(&self, rows: impl IntoIterator<Item = AddressBookRow>) -> ImportReport {
+    let mut report = ImportReport::default();
+    let rows = rows.into_iter().collect::<Vec<_>>();
+    let mut wallets = match self.client.external_wallets().await {
+      Ok((wallets, _)) => wallets.into_iter().map(|w| (w.name.clone(), w)).collect::<HashMap<_, _>>(),
+      Err(e) => {
+        let error = e.to_string();
+        let failed = |row| ImportResult { row, outcome: ImportOutcome::Failed { error: error.clone() } };
+        report.results.extend(rows.into_iter().map(failed));
+        return report;
+      },
+    };
+    let mut first = true;
+    for row in rows {
+      let outcome = self.import_row(&mut wallets, &row, &mut first).await;
+      let outcome = outcome.unwrap_or_else(|e| ImportOutcome::Failed { error: e.to_string() });
+      report.results.push(ImportResult { row, outcome });
+    }
+    report
+  }
+
+  async fn import_row(
+    &self,
+    wallets: &mut HashMap<String, WalletContainer>,
+    row: &AddressBookRow,
+    first: &mut bool,
+  ) -> Result<ImportOutcome, FireblocksError> {
+    let mut created = false;
+    let wallet = match wallets.entry(row.wallet.clone()) {
+      Entry::Occupied(wallet) => wallet.into_mut(),
+      Entry::Vacant(entry) => {
+        self.pace(first).await;
+        let (wallet, _) = self.client.external_wallet_create(&row.wallet).await?;
+        created = true;
+        entry.insert(wallet)
+      },
+    };
+    let wallet_id = wallet.id.clone();
+    if row.asset.is_empty() {
+      return Ok(if created {
+        ImportOutcome::Created { wallet_id }
+      } else {
+        ImportOutcome::AlreadyExists { wallet_id }
+      });
+    }
+    if let Some(existing) = wallet.assets.iter().find(|a| a.id.eq_ignore_ascii_case(&row.asset)) {
+      if existing.address == row.address && existing.tag.as_deref().filter(|t| !t.is_empty()) == row.tag.as_deref() {
+        return Ok(ImportOutcome::AlreadyExists { wallet_id });
+      }
+      let msg = format!("{} already has {} address {}", row.wallet, existing.id, existing.address);
+      return Err(ParamError::InvalidParams { msg }.into());
+    }
+    self.pace(first).await;
+    self.client.external_wallet_asset_with_tag(&wallet_id, &row.asset, &row.address, row.tag.as_deref()).await?;
+    wallet.assets.push(crate::types::ExternalWalletAsset {
+      id: row.asset.clone(),
+      status: String::new(),
+      address: row.address.clone(),
+      tag: row.tag.clone(),
+      activation_time: None,
+    });
+    Ok(ImportOutcome::Created { wallet_id })
+  }
+
+  async fn pace(&self, first: &mut bool) {
+    if !std::mem::take(first) {
+      tokio::time::sleep(self.interval).await;
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::mock::{self, MockTransport};
+  use crate::transport::{HttpRequest, HttpResponse};
+  use http::{Method, StatusCode};
+  use std::sync::Mutex;
+
+  const CSV: &str = "wallet,asset,address,tag
+Kraken,XRP,rKraken,12345
+Kraken,BTC,bc1qkraken,
+\"Acme, Inc\",ETH,0xacme
+Empty,,
+";
+
+  /// An address book holding wallet `Kraken` with its XRP address
+  fn workspace(posts: &Mutex<usize>, request: &HttpRequest) -> HttpResponse {
+    let body = if request.method == Method::GET {
+      String::from(
+        r#"[{"id": "w1", "name": "Kraken", "assets": [{"id": "XRP", "status": "APPROVED", "address": "rKraken", "tag": "12345"}]}]"#,
+      )
+    } else {
+      let posts = {
+        let mut posts = posts.lock().expect("posts");
+        *posts += 1;
+        *posts
+      };
+      match request.url.path().trim_start_matches("/v1/").split('/').collect::<Vec<_>>().as_slice() {
+        [_, _, asset] => format!(r#"{{"id": "{asset}"}}"#),
+        _ => format!(r#"{{"id": "w{}", "name": "new", "assets": []}}"#, posts + 1),
+      }
+    };
+    mock::json(StatusCode::OK, body)
+  }
+
+  #[test]
+  fn parse_csv() -> color_eyre::Result<()> {
+    let rows = AddressBookRow::from_csv(CSV.as_bytes())?;
+    assert_eq!(4, rows.len());
+    assert_eq!(Some("12345"), rows[0].tag.as_deref());
+    assert_eq!(None, rows[1].tag);
+    assert_eq!("Acme, Inc", rows[2].wallet);
+    assert!(rows[3].asset.is_empty());
+    assert_eq!(AddressBookRow::from_csv(AddressBookRow::to_csv(&rows).as_bytes())?, rows);
+    assert!(AddressBookRow::from_csv(&b"a,b"[..]).is_err());
+    assert!(AddressBookRow::from_csv(&b"a,ETH,"[..]).is_err());
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn import() -> color_eyre::Result<()> {
+    let posts = Mutex::new(0);
+    let transport = MockTransport::new(move |request| workspace(&posts, request));
+    let client = mock::client(transport.clone())?;
+    let book = AddressBook::new(client).with_interval(Duration::ZERO);
+
+    let mut rows = AddressBookRow::from_csv(CSV.as_bytes())?;
+    rows.push(AddressBookRow { address: String::from("rOther"), ..rows[0].clone() });
+    let report = book.import(rows).await;
+    let outcomes = report.results.iter().map(|r| &r.outcome).collect::<Vec<_>>();
+    assert_eq!(outcomes[0], &ImportOutcome::AlreadyExists { wallet_id: String::from("w1") });
+    assert_eq!(outcomes[1], &ImportOutcome::Created { wallet_id: String::from("w1") });
+    assert!(matches!(outcomes[2], ImportOutcome::Created { .. }));
+    assert!(matches!(outcomes[3], ImportOutcome::Created { .. }));
+    assert_eq!(report.failed().count(), 1);
+    let posts = transport.requests().into_iter().filter(|r| r.method == Method::POST);
+    assert_eq!(
+      posts.map(|r| r.url.path().trim_start_matches("/v1/").to_string()).collect::<Vec<_>>(),
+      ["external_wallets/w1/BTC", "external_wallets", "external_wallets/w3/ETH", "external_wallets"]
+    );
+    assert!(report.to_csv().lines().nth(5).is_some_and(|l| l.contains("FAILED")));
+
+    let exported = AddressBookRow::to_csv(&book.export().await?);
+    assert_eq!(exported, "wallet,asset,address,tag\nKraken,XRP,rKraken,12345\n");
+    Ok(())
+  }
+}
@@ -0,0 +1,48 @@
+use crate::types::{AuditLog, AuditLogs, AuditTimePeriod};
+use crate::Result;
+use crate::{Client, ItemStream, Page};
+use std::borrow::Borrow;
+
+impl Client {
+  /// Paged audit logs of the workspace, for at most the last week. Fireblocks cannot filter them by user, filter
+  /// the items by [`AuditLog::user_id`] instead
+  ///
+  /// See
+  /// * [getAuditLogs](https://developers.fireblocks.com/reference/getauditlogs)
+  /// * [`crate::types::AuditLogListBuilder`]
+  #[tracing::instrument(level = "debug", skip(self, params))]
+  pub async fn audit_logs<I, K, V>(&self, params: I) -> Result<Page<AuditLog>>
+  where
+    I: IntoIterator,
+    I::Item: Borrow<(K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+  {
+    let u = self.build_url_params("management/audit_logs", Some(params))?.0;
+    self.get_page::<AuditLogs>(u).await
+  }
+
+  /// Like [`Client::audit_logs`] but deserializes each record as the body arrives. The cursor of the
+  /// page is not available
+  #[tracing::instrument(level = "debug", skip(self, params))]
+  pub async fn audit_logs_stream<I, K, V>(&self, params: I) -> Result<ItemStream<AuditLog>>
+  where
+    I: IntoIterator,
+    I::Item: Borrow<(K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+  {
+    let u = self.build_url_params("management/audit_logs", Some(params))?.0;
+    self.get_items(u, Some("data")).await
+  }
+
+  /// Unpaged audit logs, capped by Fireblocks. Prefer [`Client::audit_logs`]
+  ///
+  /// See
+  /// * [getAudits](https://developers.fireblocks.com/reference/getaudits)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn audits(&self, period: AuditTimePeriod) -> Result<Page<AuditLog>> {
+    let u = self.build_url_params("audits", Some(&[("timePeriod", period.as_str())]))?.0;
+    self.get_page::<AuditLogs>(u).await
+  }
+}
@@ -0,0 +1,26 @@
+use crate::cache::CacheClass;
+use crate::types::{Blockchain, Blockchains};
+use crate::Result;
+use crate::{Client, Page};
+
+impl Client {
+  /// Blockchains supported by the workspace, cached when [`crate::ClientBuilder::with_cache`] is set
+  ///
+  /// See
+  /// * [listBlockchains](https://developers.fireblocks.com/reference/listblockchains)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn blockchains(&self) -> Result<Page<Blockchain>> {
+    let u = self.build_url("blockchains")?.0;
+    self.cached(CacheClass::Blockchains, "", self.get_page::<Blockchains>(u)).await
+  }
+
+  /// Blockchain by id or legacy id
+  ///
+  /// See
+  /// * [getBlockchain](https://developers.fireblocks.com/reference/getblockchain)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn blockchain(&self, id: &str) -> Result<Blockchain> {
+    let u = self.build_url(format!("blockchains/{id}"))?.0;
+    self.cached(CacheClass::Blockchains, id, self.get(u)).await
+  }
+}
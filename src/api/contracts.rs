@@ -23,7 +23,7 @@ impl Client {
     T: AsRef<str> + Display + Debug,
   {
     let u = self.build_url(format!("contracts/{id}/{asset}"))?.0;
-    let w = WalletCreateAsset { address: String::from(address), tag: "fireblocks-sdk-rs".to_string() };
+    let w = WalletCreateAsset { address: String::from(address), tag: Some("fireblocks-sdk-rs".to_string()) };
     self.post(u, Some(&w)).await
   }
 
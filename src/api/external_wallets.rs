@@ -23,7 +23,26 @@ impl Client {
     T: AsRef<str> + Display + Debug,
   {
     let u = self.build_url(format!("external_wallets/{id}/{asset}"))?.0;
-    let w = WalletCreateAsset { address: String::from(address), tag: "fireblocks-sdk-rs".to_string() };
+    let w = WalletCreateAsset { address: String::from(address), tag: Some("fireblocks-sdk-rs".to_string()) };
+    self.post(u, Some(&w)).await
+  }
+
+  /// Add an asset address to an external wallet, with the destination `tag` (memo) of assets needing one
+  ///
+  /// * [addAssetToExternalWallet](https://docs.fireblocks.com/api/swagger-ui/#/External%20wallets/addAssetToExternalWallet)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn external_wallet_asset_with_tag<T>(
+    &self,
+    id: &str,
+    asset: T,
+    address: &str,
+    tag: Option<&str>,
+  ) -> Result<WalletCreateAssetResponse>
+  where
+    T: AsRef<str> + Display + Debug,
+  {
+    let u = self.build_url(format!("external_wallets/{id}/{asset}"))?.0;
+    let w = WalletCreateAsset { address: String::from(address), tag: tag.map(String::from) };
     self.post(u, Some(&w)).await
   }
 
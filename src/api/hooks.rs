@@ -1,8 +1,10 @@
 use crate::api::Success;
 use crate::types::hooks::HookResponse;
-use crate::Client;
+use crate::types::{CreateWebhook, Notification, Notifications, UpdateWebhook, Webhook, Webhooks};
 use crate::Result;
+use crate::{Client, Page};
 use serde_derive::{Deserialize, Serialize};
+use std::borrow::Borrow;
 
 impl Client {
   /// Resends all failed webhook notifications.
@@ -31,4 +33,76 @@ impl Client {
     let u = self.build_url(format!("webhooks/resend/{tx_id}"))?.0;
     self.post::<Success, HookTransaction>(u, Some(request).as_ref()).await
   }
+
+  /// See
+  /// * [createWebhook](https://developers.fireblocks.com/reference/createwebhook)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn webhook_create(&self, hook: &CreateWebhook) -> Result<Webhook> {
+    let u = self.build_url("webhooks")?.0;
+    self.post(u, Some(hook)).await
+  }
+
+  /// See
+  /// * [getWebhooks](https://developers.fireblocks.com/reference/getwebhooks)
+  /// * [`crate::types::NotificationListBuilder`] for paging
+  #[tracing::instrument(level = "debug", skip(self, params))]
+  pub async fn webhooks<I, K, V>(&self, params: I) -> Result<Page<Webhook>>
+  where
+    I: IntoIterator,
+    I::Item: Borrow<(K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+  {
+    let u = self.build_url_params("webhooks", Some(params))?.0;
+    self.get_page::<Webhooks>(u).await
+  }
+
+  /// See
+  /// * [getWebhook](https://developers.fireblocks.com/reference/getwebhook)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn webhook(&self, webhook_id: &str) -> Result<Webhook> {
+    let u = self.build_url(format!("webhooks/{webhook_id}"))?.0;
+    self.get(u).await
+  }
+
+  /// See
+  /// * [updateWebhook](https://developers.fireblocks.com/reference/updatewebhook)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn webhook_update(&self, webhook_id: &str, update: &UpdateWebhook) -> Result<Webhook> {
+    let u = self.build_url(format!("webhooks/{webhook_id}"))?.0;
+    self.patch(u, Some(update)).await
+  }
+
+  /// See
+  /// * [deleteWebhook](https://developers.fireblocks.com/reference/deletewebhook)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn webhook_delete(&self, webhook_id: &str) -> Result<Webhook> {
+    let u = self.build_url(format!("webhooks/{webhook_id}"))?.0;
+    self.delete(u).await
+  }
+
+  /// List the notifications sent to a webhook, with their delivery attempts
+  ///
+  /// See
+  /// * [getNotifications](https://developers.fireblocks.com/reference/getnotifications)
+  /// * [`crate::types::NotificationListBuilder`]
+  #[tracing::instrument(level = "debug", skip(self, params))]
+  pub async fn webhook_notifications<I, K, V>(&self, webhook_id: &str, params: I) -> Result<Page<Notification>>
+  where
+    I: IntoIterator,
+    I::Item: Borrow<(K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+  {
+    let u = self.build_url_params(format!("webhooks/{webhook_id}/notifications"), Some(params))?.0;
+    self.get_page::<Notifications>(u).await
+  }
+
+  /// See
+  /// * [getNotification](https://developers.fireblocks.com/reference/getnotification)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn webhook_notification(&self, webhook_id: &str, notification_id: &str) -> Result<Notification> {
+    let u = self.build_url(format!("webhooks/{webhook_id}/notifications/{notification_id}"))?.0;
+    self.get(u).await
+  }
 }
@@ -0,0 +1,129 @@
+use crate::types::{Job, JobProgress, JobTask};
+use crate::Result;
+use crate::{Client, FireblocksError};
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use futures::{Stream, StreamExt};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// Progress of a job, polled every interval. Yields when the counts or the state change, and ends after the
+/// update of the terminated job, or when the job is not found
+pub struct JobProgressStream {
+  inner: BoxStream<'static, std::result::Result<JobProgress, FireblocksError>>,
+}
+
+impl Stream for JobProgressStream {
+  type Item = std::result::Result<JobProgress, FireblocksError>;
+
+  fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+    self.inner.poll_next_unpin(cx)
+  }
+}
+
+impl Client {
+  /// List the jobs created in a time period
+  ///
+  /// See
+  /// * [getJobs](https://developers.fireblocks.com/reference/getjobs)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn jobs(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<Job>> {
+    let params = [("fromTime", from.timestamp_millis().to_string()), ("toTime", to.timestamp_millis().to_string())];
+    let u = self.build_url_params("batch/jobs", Some(&params))?.0;
+    self.get(u).await
+  }
+
+  /// See
+  /// * [getJob](https://developers.fireblocks.com/reference/getjob)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn job(&self, job_id: &str) -> Result<Job> {
+    let u = self.build_url(format!("batch/{job_id}"))?.0;
+    self.get(u).await
+  }
+
+  /// See
+  /// * [getJobTasks](https://developers.fireblocks.com/reference/getjobtasks)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn job_tasks(&self, job_id: &str) -> Result<Vec<JobTask>> {
+    let u = self.build_url(format!("batch/{job_id}/tasks"))?.0;
+    self.get(u).await
+  }
+
+  /// Fetch a job and its tasks concurrently. The request id is the one of the job request
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn job_with_tasks(&self, job_id: &str) -> Result<(Job, Vec<JobTask>)> {
+    let ((job, id), (tasks, _)) = futures::try_join!(self.job(job_id), self.job_tasks(job_id))?;
+    Ok(((job, tasks), id))
+  }
+
+  /// Follow the progress of a job, e.g. bulk vault account creation, see [`JobProgressStream`]
+  ///
+  /// ```
+  /// use fireblocks_sdk::Client;
+  /// use futures::StreamExt;
+  /// use std::time::Duration;
+  ///
+  /// async fn report(c: &Client, job_id: &str) -> color_eyre::Result<()> {
+  ///   let mut progress = c.job_progress(job_id, Duration::from_secs(5));
+  ///   while let Some(p) = progress.next().await {
+  ///     let p = p?;
+  ///     println!("{}/{} tasks done, {} failed", p.done, p.total, p.failed);
+  ///   }
+  ///   Ok(())
+  /// }
+  /// ```
+  pub fn job_progress(&self, job_id: &str, interval: Duration) -> JobProgressStream {
+    let state = (self.clone(), String::from(job_id), tokio::time::interval(interval), None::<JobProgress>, false);
+    let inner = futures::stream::unfold(state, |(client, job_id, mut ticker, last, ended)| async move {
+      if ended {
+        return None;
+      }
+      loop {
+        ticker.tick().await;
+        match client.job_with_tasks(&job_id).await {
+          Ok(((job, tasks), _)) => {
+            let progress = JobProgress::new(&job, &tasks);
+            if last.as_ref() == Some(&progress) {
+              continue;
+            }
+            let ended = progress.is_final();
+            return Some((Ok(progress.clone()), (client, job_id, ticker, Some(progress), ended)));
+          },
+          Err(e) => {
+            let ended = matches!(e, FireblocksError::NotFound { .. });
+            return Some((Err(e), (client, job_id, ticker, last, ended)));
+          },
+        }
+      }
+    });
+    JobProgressStream { inner: inner.boxed() }
+  }
+
+  /// See
+  /// * [pauseJob](https://developers.fireblocks.com/reference/pausejob)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn job_pause(&self, job_id: &str) -> Result<()> {
+    self.job_action(job_id, "pause").await
+  }
+
+  /// See
+  /// * [continueJob](https://developers.fireblocks.com/reference/continuejob)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn job_continue(&self, job_id: &str) -> Result<()> {
+    self.job_action(job_id, "continue").await
+  }
+
+  /// See
+  /// * [cancelJob](https://developers.fireblocks.com/reference/canceljob)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn job_cancel(&self, job_id: &str) -> Result<()> {
+    self.job_action(job_id, "cancel").await
+  }
+
+  async fn job_action(&self, job_id: &str, action: &str) -> Result<()> {
+    let u = self.build_url(format!("batch/{job_id}/{action}"))?.0;
+    let (_, id) = self.post::<serde_json::Value, ()>(u, None).await?;
+    Ok(((), id))
+  }
+}
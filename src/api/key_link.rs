@@ -0,0 +1,97 @@
+use crate::types::{CreateSigningKey, CreateValidationKey, SigningKey, SigningKeys, ValidationKey, ValidationKeys};
+use crate::Result;
+use crate::{Client, Page};
+use serde_derive::Serialize;
+use std::borrow::Borrow;
+
+impl Client {
+  /// Register an external signing key
+  ///
+  /// See
+  /// * [createSigningKey](https://developers.fireblocks.com/reference/createsigningkey)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn key_link_signing_key_create(&self, key: &CreateSigningKey) -> Result<SigningKey> {
+    let u = self.build_url("key_link/signing_keys")?.0;
+    self.post(u, Some(key)).await
+  }
+
+  /// List external signing keys
+  ///
+  /// See
+  /// * [getSigningKeysList](https://developers.fireblocks.com/reference/getsigningkeyslist)
+  /// * [`crate::types::SigningKeyListBuilder`]
+  #[tracing::instrument(level = "debug", skip(self, params))]
+  pub async fn key_link_signing_keys<I, K, V>(&self, params: I) -> Result<Page<SigningKey>>
+  where
+    I: IntoIterator,
+    I::Item: Borrow<(K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+  {
+    let u = self.build_url_params("key_link/signing_keys", Some(params))?.0;
+    self.get_page::<SigningKeys>(u).await
+  }
+
+  /// See
+  /// * [getSigningKey](https://developers.fireblocks.com/reference/getsigningkey)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn key_link_signing_key(&self, key_id: &str) -> Result<SigningKey> {
+    let u = self.build_url(format!("key_link/signing_keys/{key_id}"))?.0;
+    self.get(u).await
+  }
+
+  /// Change the agent user of a signing key
+  ///
+  /// See
+  /// * [updateSigningKey](https://developers.fireblocks.com/reference/updatesigningkey)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn key_link_signing_key_agent(&self, key_id: &str, agent_user_id: &str) -> Result<SigningKey> {
+    #[derive(Debug, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Agent<'a> {
+      agent_user_id: &'a str,
+    }
+    let u = self.build_url(format!("key_link/signing_keys/{key_id}"))?.0;
+    self.patch(u, Some(&Agent { agent_user_id })).await
+  }
+
+  /// Register a validation key
+  ///
+  /// See
+  /// * [createValidationKey](https://developers.fireblocks.com/reference/createvalidationkey)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn key_link_validation_key_create(&self, key: &CreateValidationKey) -> Result<ValidationKey> {
+    let u = self.build_url("key_link/validation_keys")?.0;
+    self.post(u, Some(key)).await
+  }
+
+  /// See
+  /// * [getValidationKeysList](https://developers.fireblocks.com/reference/getvalidationkeyslist)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn key_link_validation_keys(&self) -> Result<Page<ValidationKey>> {
+    let u = self.build_url("key_link/validation_keys")?.0;
+    self.get_page::<ValidationKeys>(u).await
+  }
+
+  /// See
+  /// * [getValidationKey](https://developers.fireblocks.com/reference/getvalidationkey)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn key_link_validation_key(&self, key_id: &str) -> Result<ValidationKey> {
+    let u = self.build_url(format!("key_link/validation_keys/{key_id}"))?.0;
+    self.get(u).await
+  }
+
+  /// Disable a validation key. This cannot be undone
+  ///
+  /// See
+  /// * [disableValidationKey](https://developers.fireblocks.com/reference/disablevalidationkey)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn key_link_validation_key_disable(&self, key_id: &str) -> Result<ValidationKey> {
+    #[derive(Debug, Serialize)]
+    struct Enabled {
+      enabled: bool,
+    }
+    let u = self.build_url(format!("key_link/validation_keys/{key_id}"))?.0;
+    self.patch(u, Some(&Enabled { enabled: false })).await
+  }
+}
@@ -1,13 +1,27 @@
 use serde_derive::{Deserialize, Serialize};
 
+mod audit;
+mod blockchains;
 mod contracts;
 mod external_wallets;
 mod hooks;
 mod internal_wallets;
+mod jobs;
+mod key_link;
+mod ncw;
+mod nft;
+mod payouts;
+mod policy;
+mod smart_transfers;
 mod staking;
+mod tokenization;
 mod transactions;
+mod users;
 mod vaults;
 mod wallet_connect;
+mod workspace;
+
+pub use jobs::JobProgressStream;
 
 #[derive(Debug, Serialize)]
 struct WalletCreate {
@@ -18,7 +32,8 @@ struct WalletCreate {
 #[allow(dead_code)]
 pub struct WalletCreateAsset {
   pub address: String,
-  pub tag: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub tag: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Default)]
@@ -0,0 +1,229 @@
+use crate::api::Success;
+use crate::types::{
+  NcwAccount, NcwAccounts, NcwAddress, NcwAddresses, NcwAsset, NcwAssetBalance, NcwAssets, NcwBackup, NcwDevice,
+  NcwDeviceSetupStatus, NcwRpcResponse, NcwWallet, NcwWallets,
+};
+use crate::Result;
+use crate::{Client, Page};
+use serde_derive::Serialize;
+use std::borrow::Borrow;
+
+impl Client {
+  /// Create an embedded wallet
+  ///
+  /// See
+  /// * [createEmbeddedWallet](https://developers.fireblocks.com/reference/createembeddedwallet)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn ncw_wallet_create(&self) -> Result<NcwWallet> {
+    let u = self.build_url("ncw/wallets")?.0;
+    self.post::<NcwWallet, ()>(u, None).await
+  }
+
+  /// See
+  /// * [getEmbeddedWallets](https://developers.fireblocks.com/reference/getembeddedwallets)
+  /// * [`crate::types::NcwListBuilder`]
+  #[tracing::instrument(level = "debug", skip(self, params))]
+  pub async fn ncw_wallets<I, K, V>(&self, params: I) -> Result<Page<NcwWallet>>
+  where
+    I: IntoIterator,
+    I::Item: Borrow<(K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+  {
+    let u = self.build_url_params("ncw/wallets", Some(params))?.0;
+    self.get_page::<NcwWallets>(u).await
+  }
+
+  /// See
+  /// * [getEmbeddedWallet](https://developers.fireblocks.com/reference/getembeddedwallet)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn ncw_wallet(&self, wallet_id: &str) -> Result<NcwWallet> {
+    let u = self.build_url(format!("ncw/wallets/{wallet_id}"))?.0;
+    self.get(u).await
+  }
+
+  /// Enable or disable an embedded wallet
+  ///
+  /// See
+  /// * [updateEmbeddedWalletStatus](https://developers.fireblocks.com/reference/updateembeddedwalletstatus)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn ncw_wallet_enable(&self, wallet_id: &str, enabled: bool) -> Result<()> {
+    #[derive(Debug, Serialize)]
+    struct Enabled {
+      enabled: bool,
+    }
+    let u = self.build_url(format!("ncw/wallets/{wallet_id}/enable"))?.0;
+    let (_, id) = self.put::<Success, _>(u, Some(&Enabled { enabled })).await?;
+    Ok(((), id))
+  }
+
+  /// See
+  /// * [createEmbeddedWalletAccount](https://developers.fireblocks.com/reference/createembeddedwalletaccount)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn ncw_account_create(&self, wallet_id: &str) -> Result<NcwAccount> {
+    let u = self.build_url(format!("ncw/wallets/{wallet_id}/accounts"))?.0;
+    self.post::<NcwAccount, ()>(u, None).await
+  }
+
+  /// See
+  /// * [getEmbeddedWalletAccounts](https://developers.fireblocks.com/reference/getembeddedwalletaccounts)
+  /// * [`crate::types::NcwListBuilder`]
+  #[tracing::instrument(level = "debug", skip(self, params))]
+  pub async fn ncw_accounts<I, K, V>(&self, wallet_id: &str, params: I) -> Result<Page<NcwAccount>>
+  where
+    I: IntoIterator,
+    I::Item: Borrow<(K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+  {
+    let u = self.build_url_params(format!("ncw/wallets/{wallet_id}/accounts"), Some(params))?.0;
+    self.get_page::<NcwAccounts>(u).await
+  }
+
+  /// See
+  /// * [getEmbeddedWalletAccount](https://developers.fireblocks.com/reference/getembeddedwalletaccount)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn ncw_account(&self, wallet_id: &str, account_id: &str) -> Result<NcwAccount> {
+    let u = self.build_url(format!("ncw/wallets/{wallet_id}/accounts/{account_id}"))?.0;
+    self.get(u).await
+  }
+
+  /// List the assets enabled in an account of an embedded wallet
+  ///
+  /// See
+  /// * [getEmbeddedWalletAssets](https://developers.fireblocks.com/reference/getembeddedwalletassets)
+  /// * [`crate::types::NcwListBuilder`]
+  #[tracing::instrument(level = "debug", skip(self, params))]
+  pub async fn ncw_assets<I, K, V>(&self, wallet_id: &str, account_id: &str, params: I) -> Result<Page<NcwAsset>>
+  where
+    I: IntoIterator,
+    I::Item: Borrow<(K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+  {
+    let u = self.build_url_params(format!("ncw/wallets/{wallet_id}/accounts/{account_id}/assets"), Some(params))?.0;
+    self.get_page::<NcwAssets>(u).await
+  }
+
+  /// See
+  /// * [getEmbeddedWalletAsset](https://developers.fireblocks.com/reference/getembeddedwalletasset)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn ncw_asset(&self, wallet_id: &str, account_id: &str, asset_id: &str) -> Result<NcwAsset> {
+    let u = self.build_url(format!("ncw/wallets/{wallet_id}/accounts/{account_id}/assets/{asset_id}"))?.0;
+    self.get(u).await
+  }
+
+  /// Enable an asset in an account of an embedded wallet, returning its address
+  ///
+  /// See
+  /// * [addEmbeddedWalletAsset](https://developers.fireblocks.com/reference/addembeddedwalletasset)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn ncw_asset_enable(&self, wallet_id: &str, account_id: &str, asset_id: &str) -> Result<NcwAddress> {
+    let u = self.build_url(format!("ncw/wallets/{wallet_id}/accounts/{account_id}/assets/{asset_id}"))?.0;
+    self.post::<NcwAddress, ()>(u, None).await
+  }
+
+  /// See
+  /// * [getEmbeddedWalletAssetBalance](https://developers.fireblocks.com/reference/getembeddedwalletassetbalance)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn ncw_asset_balance(&self, wallet_id: &str, account_id: &str, asset_id: &str) -> Result<NcwAssetBalance> {
+    let u = self.build_url(format!("ncw/wallets/{wallet_id}/accounts/{account_id}/assets/{asset_id}/balance"))?.0;
+    self.get(u).await
+  }
+
+  /// See
+  /// * [refreshEmbeddedWalletAssetBalance](https://developers.fireblocks.com/reference/refreshembeddedwalletassetbalance)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn ncw_asset_balance_refresh(
+    &self,
+    wallet_id: &str,
+    account_id: &str,
+    asset_id: &str,
+  ) -> Result<NcwAssetBalance> {
+    let u = self.build_url(format!("ncw/wallets/{wallet_id}/accounts/{account_id}/assets/{asset_id}/balance"))?.0;
+    self.put::<NcwAssetBalance, ()>(u, None).await
+  }
+
+  /// See
+  /// * [getEmbeddedWalletAddresses](https://developers.fireblocks.com/reference/getembeddedwalletaddresses)
+  /// * [`crate::types::NcwListBuilder`]
+  #[tracing::instrument(level = "debug", skip(self, params))]
+  pub async fn ncw_asset_addresses<I, K, V>(
+    &self,
+    wallet_id: &str,
+    account_id: &str,
+    asset_id: &str,
+    params: I,
+  ) -> Result<Page<NcwAddress>>
+  where
+    I: IntoIterator,
+    I::Item: Borrow<(K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+  {
+    let u = self
+      .build_url_params(
+        format!("ncw/wallets/{wallet_id}/accounts/{account_id}/assets/{asset_id}/addresses"),
+        Some(params),
+      )?
+      .0;
+    self.get_page::<NcwAddresses>(u).await
+  }
+
+  /// List the devices of an embedded wallet. A device is registered when it first invokes
+  /// [`Client::ncw_rpc`]
+  ///
+  /// See
+  /// * [getEmbeddedWalletDevices](https://developers.fireblocks.com/reference/getembeddedwalletdevices)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn ncw_devices(&self, wallet_id: &str) -> Result<Vec<NcwDevice>> {
+    let u = self.build_url(format!("ncw/wallets/{wallet_id}/devices"))?.0;
+    self.get(u).await
+  }
+
+  /// See
+  /// * [getEmbeddedWalletDeviceSetupState](https://developers.fireblocks.com/reference/getembeddedwalletdevicesetupstate)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn ncw_device_setup_status(&self, wallet_id: &str, device_id: &str) -> Result<NcwDeviceSetupStatus> {
+    let u = self.build_url(format!("ncw/wallets/{wallet_id}/devices/{device_id}"))?.0;
+    self.get(u).await
+  }
+
+  /// Enable or disable a device of an embedded wallet
+  ///
+  /// See
+  /// * [updateEmbeddedWalletDeviceStatus](https://developers.fireblocks.com/reference/updateembeddedwalletdevicestatus)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn ncw_device_enable(&self, wallet_id: &str, device_id: &str, enabled: bool) -> Result<()> {
+    #[derive(Debug, Serialize)]
+    struct Enabled {
+      enabled: bool,
+    }
+    let u = self.build_url(format!("ncw/wallets/{wallet_id}/devices/{device_id}/enable"))?.0;
+    let (_, id) = self.put::<Success, _>(u, Some(&Enabled { enabled })).await?;
+    Ok(((), id))
+  }
+
+  /// Relay an MPC message from the device SDK to Fireblocks. This drives both key
+  /// generation and transaction signing; the response is handed back to the device unchanged
+  ///
+  /// See
+  /// * [invokeWalletRpc](https://developers.fireblocks.com/reference/invokewalletrpc)
+  #[tracing::instrument(level = "debug", skip(self, payload))]
+  pub async fn ncw_rpc(&self, wallet_id: &str, device_id: &str, payload: &str) -> Result<NcwRpcResponse> {
+    #[derive(Debug, Serialize)]
+    struct Payload<'a> {
+      payload: &'a str,
+    }
+    let u = self.build_url(format!("ncw/wallets/{wallet_id}/devices/{device_id}/invoke"))?.0;
+    self.post(u, Some(&Payload { payload })).await
+  }
+
+  /// See
+  /// * [getEmbeddedWalletLatestBackup](https://developers.fireblocks.com/reference/getembeddedwalletlatestbackup)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn ncw_latest_backup(&self, wallet_id: &str) -> Result<NcwBackup> {
+    let u = self.build_url(format!("ncw/wallets/{wallet_id}/backup/latest"))?.0;
+    self.get(u).await
+  }
+}
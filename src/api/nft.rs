@@ -0,0 +1,177 @@
+use crate::api::Success;
+use crate::types::{
+  CreateTransactionResponse, DestinationTransferPeerPath, NftCollection, NftCollections, NftOwnedToken, NftOwnedTokens,
+  NftOwnershipStatus, NftSpamUpdate, NftStatusUpdate, NftToken, NftTokens,
+};
+use crate::{Client, Page};
+use crate::{FireblocksError, Result};
+use serde_derive::Serialize;
+use std::borrow::Borrow;
+
+impl Client {
+  /// List NFTs owned by vault accounts or embedded wallets
+  ///
+  /// See
+  /// * [getOwnershipTokens](https://developers.fireblocks.com/reference/getownershiptokens)
+  /// * [`crate::types::NftOwnershipListBuilder`]
+  #[tracing::instrument(level = "debug", skip(self, params))]
+  pub async fn nfts_owned<I, K, V>(&self, params: I) -> Result<Page<NftOwnedToken>>
+  where
+    I: IntoIterator,
+    I::Item: Borrow<(K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+  {
+    let u = self.build_url_params("nfts/ownership/tokens", Some(params))?.0;
+    self.get_page::<NftOwnedTokens>(u).await
+  }
+
+  /// List distinct NFTs owned across the workspace
+  ///
+  /// See
+  /// * [listOwnedTokens](https://developers.fireblocks.com/reference/listownedtokens)
+  #[tracing::instrument(level = "debug", skip(self, params))]
+  pub async fn nfts_owned_assets<I, K, V>(&self, params: I) -> Result<Page<NftToken>>
+  where
+    I: IntoIterator,
+    I::Item: Borrow<(K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+  {
+    let u = self.build_url_params("nfts/ownership/assets", Some(params))?.0;
+    self.get_page::<NftTokens>(u).await
+  }
+
+  /// List collections of owned NFTs
+  ///
+  /// See
+  /// * [listOwnedCollections](https://developers.fireblocks.com/reference/listownedcollections)
+  #[tracing::instrument(level = "debug", skip(self, params))]
+  pub async fn nfts_owned_collections<I, K, V>(&self, params: I) -> Result<Page<NftCollection>>
+  where
+    I: IntoIterator,
+    I::Item: Borrow<(K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+  {
+    let u = self.build_url_params("nfts/ownership/collections", Some(params))?.0;
+    self.get_page::<NftCollections>(u).await
+  }
+
+  /// List tokens by their ids
+  ///
+  /// See
+  /// * [getNFTs](https://developers.fireblocks.com/reference/getnfts)
+  #[tracing::instrument(level = "debug", skip(self, params))]
+  pub async fn nfts<I, K, V>(&self, params: I) -> Result<Page<NftToken>>
+  where
+    I: IntoIterator,
+    I::Item: Borrow<(K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+  {
+    let u = self.build_url_params("nfts/tokens", Some(params))?.0;
+    self.get_page::<NftTokens>(u).await
+  }
+
+  /// Get token metadata
+  ///
+  /// See
+  /// * [getNFT](https://developers.fireblocks.com/reference/getnft)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn nft(&self, id: &str) -> Result<NftToken> {
+    let u = self.build_url(format!("nfts/tokens/{id}"))?.0;
+    self.get(u).await
+  }
+
+  /// Refresh token metadata
+  ///
+  /// See
+  /// * [refreshNFTMetadata](https://developers.fireblocks.com/reference/refreshnftmetadata)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn nft_refresh_metadata(&self, id: &str) -> Result<()> {
+    let u = self.build_url(format!("nfts/tokens/{id}"))?.0;
+    let (_, id) = self.put::<Success, ()>(u, None).await?;
+    Ok(((), id))
+  }
+
+  /// Refresh token ownership of a vault account on a blockchain
+  ///
+  /// See
+  /// * [updateOwnershipTokens](https://developers.fireblocks.com/reference/updateownershiptokens)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn nft_refresh_ownership(&self, vault_id: i32, blockchain_descriptor: &str) -> Result<()> {
+    let params = [("blockchainDescriptor", blockchain_descriptor), ("vaultAccountId", &vault_id.to_string())];
+    let u = self.build_url_params("nfts/ownership/tokens", Some(params))?.0;
+    let (_, id) = self.put::<Success, ()>(u, None).await?;
+    Ok(((), id))
+  }
+
+  /// Update the ownership status of a single token, e.g. archive it to hide it from views
+  ///
+  /// See
+  /// * [updateOwnershipTokenStatus](https://developers.fireblocks.com/reference/updatetokenownershipstatus)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn nft_status(&self, id: &str, status: NftOwnershipStatus) -> Result<()> {
+    #[derive(Debug, Serialize)]
+    struct Status {
+      status: NftOwnershipStatus,
+    }
+    let u = self.build_url(format!("nfts/ownership/tokens/{id}/status"))?.0;
+    let (_, id) = self.put::<Success, Status>(u, Some(&Status { status })).await?;
+    Ok(((), id))
+  }
+
+  /// Update the ownership status of many tokens at once
+  ///
+  /// See
+  /// * [updateTokensOwnershipStatus](https://developers.fireblocks.com/reference/updatetokensownershipstatus)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn nfts_status(&self, updates: &[NftStatusUpdate]) -> Result<()> {
+    let u = self.build_url("nfts/ownership/tokens/status")?.0;
+    let (_, id) = self.put::<Success, _>(u, Some(updates)).await?;
+    Ok(((), id))
+  }
+
+  /// Flag (or unflag) tokens as spam, e.g. to filter junk airdrops from customer views
+  ///
+  /// See
+  /// * [updateTokensOwnershipSpam](https://developers.fireblocks.com/reference/updatetokensownershipspam)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn nfts_spam(&self, updates: &[NftSpamUpdate]) -> Result<()> {
+    let u = self.build_url("nfts/ownership/tokens/spam")?.0;
+    let (_, id) = self.put::<Success, _>(u, Some(updates)).await?;
+    Ok(((), id))
+  }
+
+  /// Transfer an NFT (ERC-721 / ERC-1155) from a vault account
+  ///
+  /// ```
+  /// use fireblocks_sdk::Client;
+  /// use fireblocks_sdk::types::{DestinationTransferPeerPath, PeerType};
+  ///
+  /// async fn transfer(c: Client) -> color_eyre::Result<()> {
+  ///   let (token, _) = c.nft("NFT-abcdef").await?;
+  ///   let dest = DestinationTransferPeerPath { peer_type: PeerType::VAULT_ACCOUNT, id: "1".to_string(), ..Default::default() };
+  ///   let (tx, _) = c.nft_transfer(0, dest, &token, 1, None).await?;
+  ///   println!("transaction {}", tx.id);
+  ///   Ok(())
+  /// }
+  /// ```
+  ///
+  /// See
+  /// * [`NftToken::transfer_args`]
+  /// * [createTransaction](https://docs.fireblocks.com/api/swagger-ui/#/Transactions/createTransaction)
+  #[tracing::instrument(level = "debug", skip(self, token), fields(nft = token.id))]
+  pub async fn nft_transfer(
+    &self,
+    source_vault: i32,
+    destination: DestinationTransferPeerPath,
+    token: &NftToken,
+    amount: u64,
+    note: Option<&str>,
+  ) -> Result<CreateTransactionResponse> {
+    let args = token.transfer_args(source_vault, destination, amount, note).map_err(FireblocksError::from)?;
+    self.create_transaction(&args).await
+  }
+}
@@ -0,0 +1,37 @@
+use crate::types::{CreatePayoutRequest, Payout, PayoutExecuteResponse};
+use crate::Client;
+use crate::Result;
+
+impl Client {
+  /// Create a payout instruction set
+  ///
+  /// The payout is not executed until [`Client::payout_execute`] is called
+  ///
+  /// See
+  /// * [createPayout](https://developers.fireblocks.com/reference/createpayout)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn payout_create(&self, request: &CreatePayoutRequest) -> Result<Payout> {
+    let u = self.build_url("payments/payout")?.0;
+    self.post(u, Some(request)).await
+  }
+
+  /// Execute a payout instruction set
+  ///
+  /// See
+  /// * [executePayoutAction](https://developers.fireblocks.com/reference/executepayoutaction)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn payout_execute(&self, payout_id: &str) -> Result<PayoutExecuteResponse> {
+    let u = self.build_url(format!("payments/payout/{payout_id}/actions/execute"))?.0;
+    self.post::<PayoutExecuteResponse, ()>(u, None).await
+  }
+
+  /// Get the status of a payout and its instructions
+  ///
+  /// See
+  /// * [getPayout](https://developers.fireblocks.com/reference/getpayout)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn payout(&self, payout_id: &str) -> Result<Payout> {
+    let u = self.build_url(format!("payments/payout/{payout_id}"))?.0;
+    self.get(u).await
+  }
+}
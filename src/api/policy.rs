@@ -0,0 +1,75 @@
+use crate::types::{
+  validate_policy_rules, PolicyAndValidation, PolicyDraftAndValidation, PolicyPublishResult, PolicyRule,
+};
+use crate::Client;
+use crate::Result;
+use serde_derive::Serialize;
+
+#[derive(Debug, Serialize)]
+struct Rules<'a> {
+  rules: &'a [PolicyRule],
+}
+
+impl Client {
+  /// See
+  /// * [getActivePolicy](https://developers.fireblocks.com/reference/getactivepolicy)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn policy_active(&self) -> Result<PolicyAndValidation> {
+    let u = self.build_url("tap/active_policy")?.0;
+    self.get(u).await
+  }
+
+  /// See
+  /// * [getDraft](https://developers.fireblocks.com/reference/getdraft)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn policy_draft(&self) -> Result<PolicyDraftAndValidation> {
+    let u = self.build_url("tap/draft")?.0;
+    self.get(u).await
+  }
+
+  /// Replace the rules of the draft. The response carries the validation of the new
+  /// rules, which is how a rule set is validated without publishing it
+  ///
+  /// See
+  /// * [updateDraft](https://developers.fireblocks.com/reference/updatedraft)
+  #[tracing::instrument(level = "debug", skip(self, rules))]
+  pub async fn policy_draft_update(&self, rules: &[PolicyRule]) -> Result<PolicyDraftAndValidation> {
+    let u = self.build_url("tap/draft")?.0;
+    self.put(u, Some(&Rules { rules })).await
+  }
+
+  /// Publish the draft, which must then be approved by the workspace quorum
+  ///
+  /// See
+  /// * [publishDraft](https://developers.fireblocks.com/reference/publishdraft)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn policy_draft_publish(&self, draft_id: &str) -> Result<PolicyPublishResult> {
+    #[derive(Debug, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Draft<'a> {
+      draft_id: &'a str,
+    }
+    let u = self.build_url("tap/draft")?.0;
+    self.post(u, Some(&Draft { draft_id })).await
+  }
+
+  /// Publish a rule set directly, bypassing the draft
+  ///
+  /// See
+  /// * [publishPolicyRules](https://developers.fireblocks.com/reference/publishpolicyrules)
+  #[tracing::instrument(level = "debug", skip(self, rules))]
+  pub async fn policy_publish(&self, rules: &[PolicyRule]) -> Result<PolicyPublishResult> {
+    let u = self.build_url("tap/publish")?.0;
+    self.post(u, Some(&Rules { rules })).await
+  }
+
+  /// Validate the rules locally with [`validate_policy_rules`] and publish them
+  ///
+  /// See
+  /// * [publishPolicyRules](https://developers.fireblocks.com/reference/publishpolicyrules)
+  #[tracing::instrument(level = "debug", skip(self, rules))]
+  pub async fn policy_validate_and_publish(&self, rules: &[PolicyRule]) -> Result<PolicyPublishResult> {
+    validate_policy_rules(rules)?;
+    self.policy_publish(rules).await
+  }
+}
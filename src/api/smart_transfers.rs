@@ -0,0 +1,202 @@
+use crate::queue::{default_priority, Priority};
+use crate::types::{
+  CreateSmartTransferTicket, SmartTransferFundTerm, SmartTransferTerm, SmartTransferTermRequest,
+  SmartTransferTermResponse, SmartTransferTicket, SmartTransferTicketResponse, SmartTransferTickets,
+};
+use crate::Result;
+use crate::{Client, Page};
+use serde_derive::Serialize;
+use std::borrow::Borrow;
+
+impl Client {
+  /// Search smart transfer tickets
+  ///
+  /// See
+  /// * [searchTickets](https://developers.fireblocks.com/reference/searchtickets)
+  /// * [`crate::types::SmartTransferListBuilder`]
+  #[tracing::instrument(level = "debug", skip(self, params))]
+  pub async fn smart_transfers<I, K, V>(&self, params: I) -> Result<Page<SmartTransferTicket>>
+  where
+    I: IntoIterator,
+    I::Item: Borrow<(K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+  {
+    let u = self.build_url_params("smart-transfers", Some(params))?.0;
+    self.get_page::<SmartTransferTickets>(u).await
+  }
+
+  /// Create a ticket
+  ///
+  /// See
+  /// * [createTicket](https://developers.fireblocks.com/reference/createticket)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn smart_transfer_create(&self, ticket: &CreateSmartTransferTicket) -> Result<SmartTransferTicket> {
+    let u = self.build_url("smart-transfers")?.0;
+    let (r, id) = self.post::<SmartTransferTicketResponse, _>(u, Some(ticket)).await?;
+    Ok((r.data, id))
+  }
+
+  /// See
+  /// * [findTicketById](https://developers.fireblocks.com/reference/findticketbyid)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn smart_transfer(&self, ticket_id: &str) -> Result<SmartTransferTicket> {
+    let u = self.build_url(format!("smart-transfers/{ticket_id}"))?.0;
+    let (r, id) = self.get::<SmartTransferTicketResponse>(u).await?;
+    Ok((r.data, id))
+  }
+
+  /// Set the expiration of a ticket, in hours
+  ///
+  /// See
+  /// * [setTicketExpiration](https://developers.fireblocks.com/reference/setticketexpiration)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn smart_transfer_expires_in(&self, ticket_id: &str, hours: u32) -> Result<SmartTransferTicket> {
+    #[derive(Debug, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct ExpiresIn {
+      expires_in: u32,
+    }
+    self.smart_transfer_put(ticket_id, "expires-in", Some(&ExpiresIn { expires_in: hours })).await
+  }
+
+  /// See
+  /// * [setExternalRefId](https://developers.fireblocks.com/reference/setexternalrefid)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn smart_transfer_external_id(
+    &self,
+    ticket_id: &str,
+    external_ref_id: &str,
+  ) -> Result<SmartTransferTicket> {
+    #[derive(Debug, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct ExternalId<'a> {
+      external_ref_id: &'a str,
+    }
+    self.smart_transfer_put(ticket_id, "external-id", Some(&ExternalId { external_ref_id })).await
+  }
+
+  /// Submit a draft ticket to the counterparty
+  ///
+  /// See
+  /// * [submitTicket](https://developers.fireblocks.com/reference/submitticket)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn smart_transfer_submit(&self, ticket_id: &str, expires_in: u32) -> Result<SmartTransferTicket> {
+    #[derive(Debug, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Submit {
+      expires_in: u32,
+    }
+    self.smart_transfer_put(ticket_id, "submit", Some(&Submit { expires_in })).await
+  }
+
+  /// Manually fulfill a ticket, when all terms were settled outside of Fireblocks
+  ///
+  /// See
+  /// * [fulfillTicket](https://developers.fireblocks.com/reference/fulfillticket)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn smart_transfer_fulfill(&self, ticket_id: &str) -> Result<SmartTransferTicket> {
+    self.smart_transfer_put::<()>(ticket_id, "fulfill", None).await
+  }
+
+  /// See
+  /// * [cancelTicket](https://developers.fireblocks.com/reference/cancelticket)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn smart_transfer_cancel(&self, ticket_id: &str) -> Result<SmartTransferTicket> {
+    default_priority(Priority::High, self.smart_transfer_put::<()>(ticket_id, "cancel", None)).await
+  }
+
+  /// Add a term to a ticket
+  ///
+  /// See
+  /// * [createTicketTerm](https://developers.fireblocks.com/reference/createticketterm)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn smart_transfer_term_add(
+    &self,
+    ticket_id: &str,
+    term: &SmartTransferTermRequest,
+  ) -> Result<SmartTransferTerm> {
+    let u = self.build_url(format!("smart-transfers/{ticket_id}/terms"))?.0;
+    let (r, id) = self.post::<SmartTransferTermResponse, _>(u, Some(term)).await?;
+    Ok((r.data, id))
+  }
+
+  /// See
+  /// * [findTicketTermById](https://developers.fireblocks.com/reference/findtickettermbyid)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn smart_transfer_term(&self, ticket_id: &str, term_id: &str) -> Result<SmartTransferTerm> {
+    let u = self.build_url(format!("smart-transfers/{ticket_id}/terms/{term_id}"))?.0;
+    let (r, id) = self.get::<SmartTransferTermResponse>(u).await?;
+    Ok((r.data, id))
+  }
+
+  /// See
+  /// * [updateTicketTerm](https://developers.fireblocks.com/reference/updateticketterm)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn smart_transfer_term_update(
+    &self,
+    ticket_id: &str,
+    term_id: &str,
+    term: &SmartTransferTermRequest,
+  ) -> Result<SmartTransferTerm> {
+    let u = self.build_url(format!("smart-transfers/{ticket_id}/terms/{term_id}"))?.0;
+    let (r, id) = self.put::<SmartTransferTermResponse, _>(u, Some(term)).await?;
+    Ok((r.data, id))
+  }
+
+  /// Remove a term from a ticket
+  ///
+  /// See
+  /// * [removeTicketTerm](https://developers.fireblocks.com/reference/removeticketterm)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn smart_transfer_term_remove(&self, ticket_id: &str, term_id: &str) -> Result<()> {
+    let u = self.build_url(format!("smart-transfers/{ticket_id}/terms/{term_id}"))?.0;
+    self.delete(u).await
+  }
+
+  /// Fund a term, creating a transaction from the source account
+  ///
+  /// See
+  /// * [fundTicketTerm](https://developers.fireblocks.com/reference/fundticketterm)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn smart_transfer_term_fund(
+    &self,
+    ticket_id: &str,
+    term_id: &str,
+    fund: &SmartTransferFundTerm,
+  ) -> Result<SmartTransferTerm> {
+    let u = self.build_url(format!("smart-transfers/{ticket_id}/terms/{term_id}/fund"))?.0;
+    let (r, id) = self.put::<SmartTransferTermResponse, _>(u, Some(fund)).await?;
+    Ok((r.data, id))
+  }
+
+  /// Mark a term as funded by a transaction made outside of Fireblocks
+  ///
+  /// See
+  /// * [manuallyFundTerm](https://developers.fireblocks.com/reference/manuallyfundterm)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn smart_transfer_term_manually_fund(
+    &self,
+    ticket_id: &str,
+    term_id: &str,
+    tx_hash: &str,
+  ) -> Result<SmartTransferTerm> {
+    #[derive(Debug, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct ManualFund<'a> {
+      tx_hash: &'a str,
+    }
+    let u = self.build_url(format!("smart-transfers/{ticket_id}/terms/{term_id}/manually-fund"))?.0;
+    let (r, id) = self.put::<SmartTransferTermResponse, _>(u, Some(&ManualFund { tx_hash })).await?;
+    Ok((r.data, id))
+  }
+
+  async fn smart_transfer_put<S>(&self, ticket_id: &str, action: &str, body: Option<&S>) -> Result<SmartTransferTicket>
+  where
+    S: serde::Serialize + std::fmt::Debug + Send + Sync,
+  {
+    let u = self.build_url(format!("smart-transfers/{ticket_id}/{action}"))?.0;
+    let (r, id) = self.put::<SmartTransferTicketResponse, S>(u, body).await?;
+    Ok((r.data, id))
+  }
+}
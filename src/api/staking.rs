@@ -1,5 +1,6 @@
+use crate::cache::CacheClass;
 use crate::client::Client;
-use crate::types::StakingProvider;
+use crate::types::{StakeRequest, StakeResponse, StakingPositionAction, StakingProvider, StakingVaultsSummary};
 use crate::{
   types::{
     asset::SupportedAsset,
@@ -13,7 +14,7 @@ impl Client {
   #[tracing::instrument(level = "debug", skip(self))]
   pub async fn supported_assets(&self) -> Result<Vec<SupportedAsset>> {
     let u = self.build_url("supported_assets")?.0;
-    self.get(u).await
+    self.cached(CacheClass::SupportedAssets, "", self.get(u)).await
   }
 
   /// Get info about available providers
@@ -27,6 +28,17 @@ impl Client {
     self.get(u).await
   }
 
+  /// Get a single provider, including its validators and fees, by id
+  ///
+  /// See
+  ///
+  /// * [`Client::staking_providers`]
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn staking_provider(&self, provider_id: &str) -> Result<Option<StakingProvider>> {
+    let (providers, id) = self.staking_providers().await?;
+    Ok((providers.into_iter().find(|p| p.id == provider_id), id))
+  }
+
   /// Approve TOS for a provider
   ///
   /// See
@@ -76,4 +88,103 @@ impl Client {
     let u = self.build_url("staking/positions/summary")?.0;
     self.get(u).await
   }
+
+  /// Get a staking position by id
+  ///
+  /// See
+  ///
+  /// * [getPosition](https://developers.fireblocks.com/reference/getposition)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn staking_position(&self, id: &str) -> Result<StakingPosition> {
+    let u = self.build_url(format!("staking/positions/{id}"))?.0;
+    self.get(u).await
+  }
+
+  /// Get positions for a single chain
+  ///
+  /// See
+  ///
+  /// * [getAllDelegations](https://developers.fireblocks.com/reference/getalldelegations)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn staking_positions_chain<T>(&self, chain: T) -> Result<Vec<StakingPosition>>
+  where
+    T: AsRef<str> + Display + Debug,
+  {
+    let u = self.build_url_params("staking/positions", Some([("chainDescriptor", chain.as_ref())]))?.0;
+    self.get(u).await
+  }
+
+  /// Get the staking summary of every vault account
+  ///
+  /// See
+  ///
+  /// * [getSummaryByVault](https://developers.fireblocks.com/reference/getsummarybyvault)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn staking_positions_summary_vaults(&self) -> Result<StakingVaultsSummary> {
+    let u = self.build_url("staking/positions/summary/vaults")?.0;
+    self.get(u).await
+  }
+
+  /// Stake funds, creating a new position
+  ///
+  /// See
+  ///
+  /// * [`StakeRequest`]
+  /// * [stake](https://developers.fireblocks.com/reference/stake)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn staking_stake<T>(&self, chain: T, request: &StakeRequest) -> Result<StakeResponse>
+  where
+    T: AsRef<str> + Display + Debug,
+  {
+    let u = self.build_url(format!("staking/chains/{chain}/stake"))?.0;
+    self.post(u, Some(request)).await
+  }
+
+  /// Unstake a position
+  ///
+  /// See
+  ///
+  /// * [unstake](https://developers.fireblocks.com/reference/unstake)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn staking_unstake<T>(&self, chain: T, action: &StakingPositionAction) -> Result<()>
+  where
+    T: AsRef<str> + Display + Debug,
+  {
+    self.staking_action(chain, "unstake", action).await
+  }
+
+  /// Withdraw an unstaked position back to the vault account
+  ///
+  /// See
+  ///
+  /// * [withdraw](https://developers.fireblocks.com/reference/withdraw)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn staking_withdraw<T>(&self, chain: T, action: &StakingPositionAction) -> Result<()>
+  where
+    T: AsRef<str> + Display + Debug,
+  {
+    self.staking_action(chain, "withdraw", action).await
+  }
+
+  /// Claim the rewards of a position
+  ///
+  /// See
+  ///
+  /// * [claimRewards](https://developers.fireblocks.com/reference/claimrewards)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn staking_claim_rewards<T>(&self, chain: T, action: &StakingPositionAction) -> Result<()>
+  where
+    T: AsRef<str> + Display + Debug,
+  {
+    self.staking_action(chain, "claimRewards", action).await
+  }
+
+  async fn staking_action<T>(&self, chain: T, action: &str, body: &StakingPositionAction) -> Result<()>
+  where
+    T: AsRef<str> + Display + Debug,
+  {
+    let u = self.build_url(format!("staking/chains/{chain}/{action}"))?.0;
+    let id = self.post::<serde_json::Value, StakingPositionAction>(u, Some(body)).await?.1;
+    Ok(((), id))
+  }
 }
@@ -0,0 +1,122 @@
+use crate::types::{
+  CreateTokenRequest, CreateTransactionResponse, TokenLink, TokenLinks, TransactionArguments, TransactionOperation,
+  TransferPeerPath,
+};
+use crate::Result;
+use crate::{Client, Page};
+use bigdecimal::BigDecimal;
+use std::borrow::Borrow;
+use std::fmt::{Debug, Display};
+
+impl Client {
+  /// List tokens linked to the workspace
+  ///
+  /// See
+  /// * [getLinkedTokens](https://developers.fireblocks.com/reference/getlinkedtokens)
+  /// * [`crate::types::TokenLinkListBuilder`]
+  #[tracing::instrument(level = "debug", skip(self, params))]
+  pub async fn token_links<I, K, V>(&self, params: I) -> Result<Page<TokenLink>>
+  where
+    I: IntoIterator,
+    I::Item: Borrow<(K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+  {
+    let u = self.build_url_params("tokenization/tokens", Some(params))?.0;
+    self.get_page::<TokenLinks>(u).await
+  }
+
+  /// Issue (deploy) a new token and link it to the workspace
+  ///
+  /// See
+  /// * [issueNewToken](https://developers.fireblocks.com/reference/issuenewtoken)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn token_issue(&self, request: &CreateTokenRequest) -> Result<TokenLink> {
+    let u = self.build_url("tokenization/tokens")?.0;
+    self.post(u, Some(request)).await
+  }
+
+  /// Get details of a linked token
+  ///
+  /// See
+  /// * [getLinkedToken](https://developers.fireblocks.com/reference/getlinkedtoken)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn token_link(&self, id: &str) -> Result<TokenLink> {
+    let u = self.build_url(format!("tokenization/tokens/{id}"))?.0;
+    self.get(u).await
+  }
+
+  /// Unlink a token. The token is not removed from the blockchain
+  ///
+  /// See
+  /// * [unlink](https://developers.fireblocks.com/reference/unlink)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn token_unlink(&self, id: &str) -> Result<()> {
+    let u = self.build_url(format!("tokenization/tokens/{id}"))?.0;
+    self.delete(u).await
+  }
+
+  /// Mint new supply of a token into a vault account
+  ///
+  /// See
+  /// * [createTransaction](https://docs.fireblocks.com/api/swagger-ui/#/Transactions/createTransaction)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn token_mint<T>(
+    &self,
+    vault: i32,
+    asset_id: T,
+    amount: BigDecimal,
+    note: Option<&str>,
+  ) -> Result<CreateTransactionResponse>
+  where
+    T: AsRef<str> + Debug + Display,
+  {
+    self.token_supply(TransactionOperation::MINT, vault, asset_id, amount, note).await
+  }
+
+  /// Burn supply of a token held by a vault account
+  ///
+  /// See
+  /// * [createTransaction](https://docs.fireblocks.com/api/swagger-ui/#/Transactions/createTransaction)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn token_burn<T>(
+    &self,
+    vault: i32,
+    asset_id: T,
+    amount: BigDecimal,
+    note: Option<&str>,
+  ) -> Result<CreateTransactionResponse>
+  where
+    T: AsRef<str> + Debug + Display,
+  {
+    self.token_supply(TransactionOperation::BURN, vault, asset_id, amount, note).await
+  }
+
+  async fn token_supply<T>(
+    &self,
+    operation: TransactionOperation,
+    vault: i32,
+    asset_id: T,
+    amount: BigDecimal,
+    note: Option<&str>,
+  ) -> Result<CreateTransactionResponse>
+  where
+    T: AsRef<str> + Debug + Display,
+  {
+    let args = &TransactionArguments {
+      asset_id: format!("{asset_id}"),
+      operation,
+      source: TransferPeerPath { id: Some(vault.to_string()), ..Default::default() },
+      destination: None,
+      amount: amount.to_string(),
+      gas_price: None,
+      gas_limit: None,
+      fee_level: None,
+      note: note.unwrap_or("created by fireblocks-sdk for rust").to_string(),
+      extra_parameters: None,
+      destinations: None,
+      external_tx_id: None,
+    };
+    self.create_transaction(args).await
+  }
+}
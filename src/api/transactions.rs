@@ -1,8 +1,12 @@
+use crate::api::Success;
+use crate::cache::CacheClass;
+use crate::deadline;
+use crate::queue::{default_priority, Priority};
 use crate::types::{
   CreateTransactionResponse, DestinationTransferPeerPath, EstimateFee, OneTimeAddress, PeerType, Transaction,
   TransactionArguments, TransactionOperation, TransactionStatus, TransferPeerPath,
 };
-use crate::Client;
+use crate::{Client, ItemStream, Page};
 use bigdecimal::BigDecimal;
 use std::borrow::Borrow;
 use std::fmt::{Debug, Display};
@@ -17,7 +21,7 @@ impl Client {
   /// * [getTransactions](https://docs.fireblocks.com/api/swagger-ui/#/Transactions/getTransactions)
   /// * [`crate::types::transaction::TransactionListBuilder`]
   #[tracing::instrument(level = "debug", skip(self, options))]
-  pub async fn transactions<I, K, V>(&self, options: I) -> crate::Result<Vec<Transaction>>
+  pub async fn transactions<I, K, V>(&self, options: I) -> crate::Result<Page<Transaction>>
   where
     I: IntoIterator,
     I::Item: Borrow<(K, V)>,
@@ -25,20 +29,53 @@ impl Client {
     V: AsRef<str>,
   {
     let u = self.build_url_params("transactions", Some(options))?.0;
-    self.get(u).await
+    self.get_page::<Vec<Transaction>>(u).await
+  }
+
+  /// Like [`Client::transactions`] but deserializes each transaction as the body arrives, keeping
+  /// memory bounded for pages of hundreds of transactions
+  ///
+  /// ```
+  /// use fireblocks_sdk::Client;
+  /// use fireblocks_sdk::types::TransactionListBuilder;
+  /// use futures::TryStreamExt;
+  ///
+  /// async fn transactions(c: Client) -> color_eyre::Result<()> {
+  ///   let params = TransactionListBuilder::new().limit(500).build()?;
+  ///   let (mut txs, _) = c.transactions_stream(params).await?;
+  ///   while let Some(tx) = txs.try_next().await? {
+  ///     println!("{} {:?}", tx.id, tx.status);
+  ///   }
+  ///   Ok(())
+  /// }
+  /// ```
+  #[tracing::instrument(level = "debug", skip(self, options))]
+  pub async fn transactions_stream<I, K, V>(&self, options: I) -> crate::Result<ItemStream<Transaction>>
+  where
+    I: IntoIterator,
+    I::Item: Borrow<(K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+  {
+    let u = self.build_url_params("transactions", Some(options))?.0;
+    self.get_items(u, None).await
   }
 
   /// Create a transaction
   ///
+  /// Fails without a request when a one-time address destination lacks a valid tag, see [`crate::types::tag`],
+  /// or a contract call reverts in simulation, see [`crate::simulate`]
+  ///
   /// [createTransaction](https://docs.fireblocks.com/api/swagger-ui/#/Transactions/createTransaction)
   #[tracing::instrument(level = "debug", skip(self))]
   pub async fn create_transaction(&self, args: &TransactionArguments) -> crate::Result<CreateTransactionResponse> {
+    self.preflight(args).await?;
     let u = self.build_url("transactions")?.0;
-    self.post(u, Some(args)).await
+    default_priority(Priority::High, self.post(u, Some(args))).await
   }
 
-  /// Create a vault-to-peer destination transaction (e.g. INTERNAL_WALLET)
-  /// create_transaction_whitelist(0, &id, PeerType::INTERNAL_WALLET, "SOL_TEST", BigDecimal::from_str("0.00001")?, None).await?
+  /// Create a vault-to-peer destination transaction (e.g. `INTERNAL_WALLET`)
+  /// `create_transaction_peer(0, &id, PeerType::INTERNAL_WALLET, "SOL_TEST", BigDecimal::from_str("0.00001")?, None).await?`
   ///
   /// [createTransaction](https://docs.fireblocks.com/api/swagger-ui/#/Transactions/createTransaction)
   #[tracing::instrument(level = "debug", skip(self))]
@@ -70,8 +107,11 @@ impl Client {
       amount: amount.to_string(),
       gas_price: None,
       gas_limit: None,
+      fee_level: None,
       note: note.unwrap_or("created by fireblocks-sdk for rust").to_string(),
       extra_parameters: None,
+      destinations: None,
+      external_tx_id: None,
     };
     self.create_transaction(args).await
   }
@@ -99,8 +139,11 @@ impl Client {
       amount: amount.to_string(),
       gas_price: None,
       gas_limit: None,
+      fee_level: None,
       note: note.unwrap_or("created by fireblocks-sdk for rust").to_string(),
       extra_parameters: None,
+      destinations: None,
+      external_tx_id: None,
     };
     self.create_transaction(args).await
   }
@@ -121,8 +164,42 @@ impl Client {
     A: AsRef<str> + Debug + Display,
     D: AsRef<str> + Debug + Display,
   {
+    let address = OneTimeAddress { address: destination.to_string(), tag: None };
+    self.create_transaction_one_time(source_vault, address, asset_id.as_ref(), amount, note).await
+  }
+
+  /// Create a transaction to external wallet of a chain crediting transfers by destination tag or memo, e.g.
+  /// an exchange deposit address on XRP, see [`crate::types::tag`]
+  ///
+  /// [createTransaction](https://docs.fireblocks.com/api/swagger-ui/#/Transactions/createTransaction)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn create_transaction_external_tagged<A, D>(
+    &self,
+    source_vault: i32,
+    destination: D,
+    tag: &str,
+    asset_id: A,
+    amount: BigDecimal,
+    note: Option<&str>,
+  ) -> crate::Result<CreateTransactionResponse>
+  where
+    A: AsRef<str> + Debug + Display,
+    D: AsRef<str> + Debug + Display,
+  {
+    let address = OneTimeAddress { address: destination.to_string(), tag: Some(String::from(tag)) };
+    self.create_transaction_one_time(source_vault, address, asset_id.as_ref(), amount, note).await
+  }
+
+  async fn create_transaction_one_time(
+    &self,
+    source_vault: i32,
+    address: OneTimeAddress,
+    asset_id: &str,
+    amount: BigDecimal,
+    note: Option<&str>,
+  ) -> crate::Result<CreateTransactionResponse> {
     let args = &TransactionArguments {
-      asset_id: format!("{asset_id}"),
+      asset_id: String::from(asset_id),
       operation: TransactionOperation::TRANSFER,
       source: TransferPeerPath {
         id: Some(source_vault.to_string()),
@@ -131,14 +208,17 @@ impl Client {
       },
       destination: Some(DestinationTransferPeerPath {
         peer_type: PeerType::ONE_TIME_ADDRESS,
-        one_time_address: Some(OneTimeAddress { address: destination.to_string(), tag: None }),
+        one_time_address: Some(address),
         ..Default::default()
       }),
       amount: amount.to_string(),
       gas_price: None,
       gas_limit: None,
+      fee_level: None,
       note: note.unwrap_or("created by fireblocks-sdk for rust").to_string(),
       extra_parameters: None,
+      destinations: None,
+      external_tx_id: None,
     };
     self.create_transaction(args).await
   }
@@ -152,6 +232,24 @@ impl Client {
     self.get(u).await
   }
 
+  /// Get a transaction by the external id set when it was created
+  ///
+  /// [getTransactionByExternalId](https://docs.fireblocks.com/api/swagger-ui/#/Transactions/getTransactionByExternalId)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn get_transaction_external(&self, external_tx_id: &str) -> crate::Result<Transaction> {
+    let u = self.build_url(format!("transactions/external_tx_id/{external_tx_id}"))?.0;
+    self.get(u).await
+  }
+
+  /// Cancel a transaction that has not been signed yet
+  ///
+  /// [cancelTransaction](https://docs.fireblocks.com/api/swagger-ui/#/Transactions/cancelTransaction)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn cancel_transaction(&self, id: &str) -> crate::Result<Success> {
+    let u = self.build_url(format!("transactions/{id}/cancel"))?.0;
+    default_priority(Priority::High, self.post::<Success, ()>(u, None)).await
+  }
+
   /// Pool transaction until
   /// * [`TransactionStatus::FAILED`]
   /// * [`TransactionStatus::COMPLETED`]
@@ -162,6 +260,9 @@ impl Client {
   /// * [`TransactionStatus::CANCELLING`]
   /// * [`TransactionStatus::CONFIRMING`]
   ///
+  /// or `timeout`, or the [`Deadline`](crate::deadline::Deadline) of the caller, whichever comes first. A failed
+  /// poll is retried, and returned once the retries of the deadline are spent
+  ///
   /// [getTransaction](https://docs.fireblocks.com/api/swagger-ui/#/Transactions/getTransaction)
   #[tracing::instrument(level = "debug", skip(self, callback))]
   pub async fn poll_transaction(
@@ -174,23 +275,31 @@ impl Client {
     let u = self.build_url(format!("transactions/{id}"))?.0;
     let mut total_time = time::Duration::from_millis(0);
     loop {
-      if let Ok(result) = self.get::<Transaction>(u.clone()).await {
-        let status = result.0.status.clone();
-        debug!("status {:#?}", status);
-        #[allow(clippy::match_same_arms)]
-        match status {
-          TransactionStatus::BLOCKED => break,
-          TransactionStatus::CANCELLING => break,
-          TransactionStatus::CANCELLED => break,
-          TransactionStatus::COMPLETED => break,
-          TransactionStatus::CONFIRMING => break,
-          TransactionStatus::FAILED => break,
-          TransactionStatus::REJECTED => break,
-          TransactionStatus::TIMEOUT => break,
-          _ => {
-            callback(&result.0);
-          },
-        }
+      match self.get::<Transaction>(u.clone()).await {
+        Ok(result) => {
+          let status = result.0.status.clone();
+          debug!("status {:#?}", status);
+          #[allow(clippy::match_same_arms)]
+          match status {
+            TransactionStatus::BLOCKED => break,
+            TransactionStatus::CANCELLING => break,
+            TransactionStatus::CANCELLED => break,
+            TransactionStatus::COMPLETED => break,
+            TransactionStatus::CONFIRMING => break,
+            TransactionStatus::FAILED => break,
+            TransactionStatus::REJECTED => break,
+            TransactionStatus::TIMEOUT => break,
+            _ => {
+              callback(&result.0);
+            },
+          }
+        },
+        // a failed poll is retried, charging the retry budget of the deadline
+        Err(e) if !deadline::retry(interval) => return Err(e),
+        Err(e) => debug!("polling {id} failed: {e}"),
+      }
+      if !deadline::fits(interval) {
+        break;
       }
       time::sleep(interval).await;
       total_time = total_time.add(interval);
@@ -204,7 +313,7 @@ impl Client {
   #[tracing::instrument(level = "debug", skip(self))]
   pub async fn estimate_fee(&self, asset: &str) -> crate::Result<EstimateFee> {
     let u = self.build_url(format!("estimate_network_fee?assetId={asset}"))?.0;
-    self.get(u).await
+    self.cached(CacheClass::NetworkFees, asset, self.get(u)).await
   }
 
   #[tracing::instrument(level = "debug", skip(self))]
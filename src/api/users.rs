@@ -0,0 +1,148 @@
+use crate::api::Success;
+use crate::types::{
+  ApiUsers, ConsoleUser, ConsoleUsers, Cosigner, CosignerApiKey, CosignerApiKeys, Cosigners, CreateApiUser,
+  CreateConsoleUser, UserGroup, UserGroupRequest, WhitelistedIps,
+};
+use crate::Result;
+use crate::{Client, Page};
+
+impl Client {
+  /// List all users of the workspace
+  ///
+  /// See
+  /// * [getUsers](https://developers.fireblocks.com/reference/getusers)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn users(&self) -> Result<Vec<ConsoleUser>> {
+    let u = self.build_url("users")?.0;
+    self.get(u).await
+  }
+
+  /// See
+  /// * [getConsoleUsers](https://developers.fireblocks.com/reference/getconsoleusers)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn console_users(&self) -> Result<ConsoleUsers> {
+    let u = self.build_url("management/users")?.0;
+    self.get(u).await
+  }
+
+  /// Invite a console user. The new user must be approved by the workspace quorum
+  ///
+  /// See
+  /// * [createConsoleUser](https://developers.fireblocks.com/reference/createconsoleuser)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn console_user_create(&self, user: &CreateConsoleUser) -> Result<()> {
+    let u = self.build_url("management/users")?.0;
+    let (_, id) = self.post::<Success, _>(u, Some(user)).await?;
+    Ok(((), id))
+  }
+
+  /// Request a reset of the mobile device of a console user
+  ///
+  /// See
+  /// * [resetDevice](https://developers.fireblocks.com/reference/resetdevice)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn console_user_reset_device(&self, user_id: &str) -> Result<()> {
+    let u = self.build_url(format!("management/users/{user_id}/reset_device"))?.0;
+    let (_, id) = self.post::<Success, ()>(u, None).await?;
+    Ok(((), id))
+  }
+
+  /// See
+  /// * [getApiUsers](https://developers.fireblocks.com/reference/getapiusers)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn api_users(&self) -> Result<ApiUsers> {
+    let u = self.build_url("management/api_users")?.0;
+    self.get(u).await
+  }
+
+  /// Create an API user. The new user must be approved by the workspace quorum, and
+  /// paired with a co-signer if it signs transactions
+  ///
+  /// See
+  /// * [createApiUser](https://developers.fireblocks.com/reference/createapiuser)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn api_user_create(&self, user: &CreateApiUser) -> Result<()> {
+    let u = self.build_url("management/api_users")?.0;
+    let (_, id) = self.post::<Success, _>(u, Some(user)).await?;
+    Ok(((), id))
+  }
+
+  /// See
+  /// * [getWhitelistIpAddresses](https://developers.fireblocks.com/reference/getwhitelistipaddresses)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn api_user_whitelist_ips(&self, user_id: &str) -> Result<WhitelistedIps> {
+    let u = self.build_url(format!("management/api_users/{user_id}/whitelist_ip_addresses"))?.0;
+    self.get(u).await
+  }
+
+  /// See
+  /// * [getCosigners](https://developers.fireblocks.com/reference/getcosigners)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn cosigners(&self) -> Result<Page<Cosigner>> {
+    let u = self.build_url("cosigners")?.0;
+    self.get_page::<Cosigners>(u).await
+  }
+
+  /// List the API keys paired, or pending pairing, with a co-signer
+  ///
+  /// See
+  /// * [getApiKeys](https://developers.fireblocks.com/reference/getapikeys)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn cosigner_api_keys(&self, cosigner_id: &str) -> Result<Page<CosignerApiKey>> {
+    let u = self.build_url(format!("cosigners/{cosigner_id}/api_keys"))?.0;
+    self.get_page::<CosignerApiKeys>(u).await
+  }
+
+  /// See
+  /// * [getApiKey](https://developers.fireblocks.com/reference/getapikey)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn cosigner_api_key(&self, cosigner_id: &str, api_key_id: &str) -> Result<CosignerApiKey> {
+    let u = self.build_url(format!("cosigners/{cosigner_id}/api_keys/{api_key_id}"))?.0;
+    self.get(u).await
+  }
+
+  /// See
+  /// * [getUserGroups](https://developers.fireblocks.com/reference/getusergroups)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn user_groups(&self) -> Result<Vec<UserGroup>> {
+    let u = self.build_url("management/user_groups")?.0;
+    self.get(u).await
+  }
+
+  /// See
+  /// * [getUserGroup](https://developers.fireblocks.com/reference/getusergroup)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn user_group(&self, group_id: &str) -> Result<UserGroup> {
+    let u = self.build_url(format!("management/user_groups/{group_id}"))?.0;
+    self.get(u).await
+  }
+
+  /// Create a user group. The group is active once approved by the workspace quorum
+  ///
+  /// See
+  /// * [createUserGroup](https://developers.fireblocks.com/reference/createusergroup)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn user_group_create(&self, group: &UserGroupRequest) -> Result<UserGroup> {
+    let u = self.build_url("management/user_groups")?.0;
+    self.post(u, Some(group)).await
+  }
+
+  /// Rename a user group and replace its members
+  ///
+  /// See
+  /// * [updateUserGroup](https://developers.fireblocks.com/reference/updateusergroup)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn user_group_update(&self, group_id: &str, group: &UserGroupRequest) -> Result<UserGroup> {
+    let u = self.build_url(format!("management/user_groups/{group_id}"))?.0;
+    self.put(u, Some(group)).await
+  }
+
+  /// See
+  /// * [deleteUserGroup](https://developers.fireblocks.com/reference/deleteusergroup)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn user_group_delete(&self, group_id: &str) -> Result<()> {
+    let u = self.build_url(format!("management/user_groups/{group_id}"))?.0;
+    let (_, id) = self.delete::<Success>(u).await?;
+    Ok(((), id))
+  }
+}
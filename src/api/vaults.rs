@@ -1,12 +1,14 @@
 use crate::api::Success;
 use crate::types::{
-  Account, Address, AddressContainer, CreateAccount, CreateAddressResponse, PaginatedAssetWallet, VaultAccounts,
-  VaultRenameResponse,
+  Account, AccountAsset, Address, AddressContainer, AssetResponse, CreateAccount, CreateAddressResponse,
+  MaxSpendableAmount, PaginatedAssetWallet, PublicKeyInfo, SigningAlgorithm, VaultAccounts, VaultRenameResponse,
 };
-use crate::Client;
-use crate::Result;
+use crate::{Client, Page};
+use crate::{FireblocksError, Result};
+use futures::StreamExt;
 use serde_derive::Serialize;
 use std::borrow::Borrow;
+use std::collections::HashMap;
 use std::fmt::{Debug, Display};
 
 impl Client {
@@ -45,7 +47,7 @@ impl Client {
   }
 
   #[tracing::instrument(level = "debug", skip(self, paging))]
-  pub async fn addresses_paginated<T, I, K, V>(&self, vault_id: i32, asset_id: T, paging: I) -> Result<AddressContainer>
+  pub async fn addresses_paginated<T, I, K, V>(&self, vault_id: i32, asset_id: T, paging: I) -> Result<Page<Address>>
   where
     T: AsRef<str> + Display + Debug,
     I: IntoIterator,
@@ -55,6 +57,106 @@ impl Client {
   {
     let p = format!("vault/accounts/{vault_id}/{asset_id}/addresses_paginated");
     let u = self.build_url_params(p, Some(paging))?.0;
+    self.get_page::<AddressContainer>(u).await
+  }
+
+  /// Get the balance of an asset in a vault account
+  ///
+  /// * [getVaultAccountAsset](https://docs.fireblocks.com/api/swagger-ui/#/Vaults/getVaultAccountAsset)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn vault_asset<T>(&self, vault_id: i32, asset_id: T) -> Result<AccountAsset>
+  where
+    T: AsRef<str> + Display + Debug,
+  {
+    let u = self.build_url(format!("vault/accounts/{vault_id}/{asset_id}"))?.0;
+    self.get(u).await
+  }
+
+  /// Balance of an asset in many vault accounts, with at most `max_concurrency` requests in flight
+  ///
+  /// Failures are reported per vault rather than failing the whole batch
+  ///
+  /// ```
+  /// use fireblocks_sdk::{ASSET_ETH_TEST, Client};
+  ///
+  /// async fn balances(c: Client) {
+  ///   let vaults: Vec<i32> = (0..1000).collect();
+  ///   for (vault, balance) in c.balances_for_vaults(&vaults, ASSET_ETH_TEST, 20).await {
+  ///     match balance {
+  ///       Ok(b) => println!("{vault} {}", b.total),
+  ///       Err(e) => println!("{vault} failed {e}"),
+  ///     }
+  ///   }
+  /// }
+  /// ```
+  ///
+  /// See
+  /// * [`Client::vault_asset`]
+  #[tracing::instrument(level = "debug", skip(self, vault_ids))]
+  pub async fn balances_for_vaults<T>(
+    &self,
+    vault_ids: &[i32],
+    asset_id: T,
+    max_concurrency: usize,
+  ) -> HashMap<i32, std::result::Result<AccountAsset, FireblocksError>>
+  where
+    T: AsRef<str> + Display + Debug,
+  {
+    let asset_id = &asset_id;
+    futures::stream::iter(vault_ids.iter().copied())
+      .map(|vault_id| async move { (vault_id, self.vault_asset(vault_id, asset_id).await.map(|(asset, _)| asset)) })
+      .buffer_unordered(max_concurrency.max(1))
+      .collect()
+      .await
+  }
+
+  /// Get the maximum amount that can be sent from a vault account, after fees
+  ///
+  /// * [getMaxSpendableAmount](https://docs.fireblocks.com/api/swagger-ui/#/Vaults/getMaxSpendableAmount)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn vault_max_spendable<T>(&self, vault_id: i32, asset_id: T) -> Result<MaxSpendableAmount>
+  where
+    T: AsRef<str> + Display + Debug,
+  {
+    let u = self.build_url(format!("vault/accounts/{vault_id}/{asset_id}/max_spendable_amount"))?.0;
+    self.get(u).await
+  }
+
+  /// Get the public key at a derivation path, e.g. `[44, 0, 0, 0, 0]` for the first BTC address of vault
+  /// account 0
+  ///
+  /// * [getPublicKeyInfo](https://docs.fireblocks.com/api/swagger-ui/#/Vaults/getPublicKeyInfo)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn public_key_info(
+    &self,
+    derivation_path: &[u32],
+    algorithm: SigningAlgorithm,
+    compressed: bool,
+  ) -> Result<PublicKeyInfo> {
+    let path = format!("[{}]", derivation_path.iter().map(u32::to_string).collect::<Vec<_>>().join(","));
+    let params =
+      [("derivationPath", path), ("algorithm", algorithm.to_string()), ("compressed", compressed.to_string())];
+    let u = self.build_url_params("vault/public_key_info", Some(&params))?.0;
+    self.get(u).await
+  }
+
+  /// Get the public key of an address of a vault account
+  ///
+  /// * [getPublicKeyInfoForAddress](https://docs.fireblocks.com/api/swagger-ui/#/Vaults/getPublicKeyInfoForAddress)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn vault_public_key_info<T>(
+    &self,
+    vault_id: i32,
+    asset_id: T,
+    change: u32,
+    address_index: u32,
+    compressed: bool,
+  ) -> Result<PublicKeyInfo>
+  where
+    T: AsRef<str> + Display + Debug,
+  {
+    let p = format!("vault/accounts/{vault_id}/{asset_id}/{change}/{address_index}/public_key_info");
+    let u = self.build_url_params(p, Some([("compressed", compressed.to_string())]))?.0;
     self.get(u).await
   }
 
@@ -83,7 +185,7 @@ impl Client {
   /// * [`crate::PagingVaultRequestBuilder`]
   /// * [getPagedVaultAccounts](https://docs.fireblocks.com/api/swagger-ui/#/Vaults/getPagedVaultAccounts)
   #[tracing::instrument(level = "debug", skip(self, page))]
-  pub async fn vaults<I, K, V>(&self, page: I) -> Result<VaultAccounts>
+  pub async fn vaults<I, K, V>(&self, page: I) -> Result<Page<Account>>
   where
     I: IntoIterator,
     I::Item: Borrow<(K, V)>,
@@ -91,7 +193,7 @@ impl Client {
     V: AsRef<str>,
   {
     let u = self.build_url_params("vault/accounts_paged", Some(page))?.0;
-    self.get(u).await
+    self.get_page::<VaultAccounts>(u).await
   }
 
   #[tracing::instrument(level = "debug", skip(self))]
@@ -123,7 +225,7 @@ impl Client {
   }
 
   #[tracing::instrument(level = "debug", skip(self, page))]
-  pub async fn assets<I, K, V>(&self, page: I) -> Result<PaginatedAssetWallet>
+  pub async fn assets<I, K, V>(&self, page: I) -> Result<Page<AssetResponse>>
   where
     I: IntoIterator,
     I::Item: Borrow<(K, V)>,
@@ -131,6 +233,6 @@ impl Client {
     V: AsRef<str>,
   {
     let u = self.build_url_params("vault/asset_wallets", Some(page))?.0;
-    self.get(u).await
+    self.get_page::<PaginatedAssetWallet>(u).await
   }
 }
@@ -1,11 +1,32 @@
-use crate::types::connect::{PagedWalletConnectResponse, WalletApprove, WalletConnectRequest, WalletConnectResponse};
-use crate::Client;
+use crate::types::connect::{
+  PagedWalletConnectResponse, WalletApprove, WalletConnectRequest, WalletConnectResponse, WalletConnection,
+};
+use crate::types::{Transaction, TransactionOperation, TransactionStatus};
+use crate::{Client, Page};
+use std::borrow::Borrow;
 
 impl Client {
   #[tracing::instrument(level = "debug", skip(self))]
-  pub async fn wallet_connections(&self) -> crate::Result<PagedWalletConnectResponse> {
+  pub async fn wallet_connections(&self) -> crate::Result<Page<WalletConnection>> {
     let u = self.build_url("connections")?.0;
-    self.get(u).await
+    self.get_page::<PagedWalletConnectResponse>(u).await
+  }
+
+  /// List dApp connections with filters and paging
+  ///
+  /// See
+  /// * [get](https://developers.fireblocks.com/reference/get)
+  /// * [`crate::types::connect::WalletConnectionListBuilder`]
+  #[tracing::instrument(level = "debug", skip(self, params))]
+  pub async fn wallet_connections_list<I, K, V>(&self, params: I) -> crate::Result<Page<WalletConnection>>
+  where
+    I: IntoIterator,
+    I::Item: Borrow<(K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+  {
+    let u = self.build_url_params("connections", Some(params))?.0;
+    self.get_page::<PagedWalletConnectResponse>(u).await
   }
 
   #[tracing::instrument(level = "debug", skip(self))]
@@ -25,4 +46,147 @@ impl Client {
     let u = self.build_url(format!("connections/wc/{id}"))?.0;
     self.put(u, Some(&WalletApprove { approve })).await
   }
+
+  /// Open a dApp connection and approve or deny it based on `policy`
+  ///
+  /// `policy` receives the pending connection (including the dApp session metadata)
+  /// and returns `true` to approve. Returns the decision that was applied.
+  ///
+  /// ```
+  /// use fireblocks_sdk::Client;
+  /// use fireblocks_sdk::types::connect::WalletConnectRequest;
+  ///
+  /// async fn connect(c: Client, request: WalletConnectRequest) -> color_eyre::Result<()> {
+  ///   let allowed = ["https://app.uniswap.org"];
+  ///   let (approved, _) = c
+  ///     .wallet_connect_review(&request, |pending| {
+  ///       pending.metadata.as_ref().is_some_and(|m| allowed.contains(&m.app_url.as_str()))
+  ///     })
+  ///     .await?;
+  ///   println!("approved {approved}");
+  ///   Ok(())
+  /// }
+  /// ```
+  ///
+  /// See
+  /// * [`Client::wallet_connect`]
+  /// * [`Client::wallet_connection_approve`]
+  #[tracing::instrument(level = "debug", skip(self, policy))]
+  pub async fn wallet_connect_review(
+    &self,
+    request: &WalletConnectRequest,
+    policy: impl Fn(&WalletConnectResponse) -> bool + Send + Sync,
+  ) -> crate::Result<bool> {
+    let pending = self.wallet_connect(request).await?.0;
+    let approve = policy(&pending);
+    let id = self.wallet_connection_approve(&pending.id, approve).await?.1;
+    Ok((approve, id))
+  }
+
+  /// Review a pending dApp signature request (a [`TransactionOperation::TYPED_MESSAGE`] transaction)
+  ///
+  /// `policy` receives the transaction, including the raw messages in `extra_parameters`,
+  /// and returns `true` to let signing proceed. Rejected requests are cancelled.
+  /// Requests that are not typed messages, or are no longer pending, are left untouched and reported as not approved.
+  ///
+  /// See
+  /// * [`Client::cancel_transaction`]
+  #[tracing::instrument(level = "debug", skip(self, policy))]
+  pub async fn typed_message_review(
+    &self,
+    tx_id: &str,
+    policy: impl Fn(&Transaction) -> bool + Send + Sync,
+  ) -> crate::Result<bool> {
+    let (tx, id) = self.get_transaction(tx_id).await?;
+    if tx.operation != Some(TransactionOperation::TYPED_MESSAGE) {
+      return Ok((false, id));
+    }
+    match tx.status {
+      TransactionStatus::SUBMITTED
+      | TransactionStatus::QUEUED
+      | TransactionStatus::PENDING_AUTHORIZATION
+      | TransactionStatus::PENDING_SIGNATURE => {},
+      _ => return Ok((false, id)),
+    }
+    if policy(&tx) {
+      return Ok((true, id));
+    }
+    let id = self.cancel_transaction(tx_id).await?.1;
+    Ok((false, id))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::mock::{self, MockTransport};
+  use crate::transport::{HttpRequest, HttpResponse};
+  use crate::types::connect::{WalletConnectRequest, WalletConnectResponse};
+  use http::{Method, StatusCode};
+
+  /// A dApp connection `c1` to Uniswap, typed message `tx1` awaiting signature, signed message `tx2` and
+  /// transfer `tx3`
+  fn fireblocks(request: &HttpRequest) -> HttpResponse {
+    let path = request.url.path().trim_start_matches("/v1/");
+    let body = match (&request.method, path) {
+      (&Method::POST, "connections/wc") => {
+        r#"{"id": "c1", "sessionMetadata": {"appUrl": "https://app.uniswap.org", "appName": "Uniswap"}}"#.to_string()
+      },
+      (&Method::PUT, "connections/wc/c1") => String::new(),
+      (&Method::GET, _) => {
+        let (status, operation) = match path.trim_start_matches("transactions/") {
+          "tx1" => ("PENDING_SIGNATURE", "TYPED_MESSAGE"),
+          "tx2" => ("COMPLETED", "TYPED_MESSAGE"),
+          _ => ("PENDING_SIGNATURE", "TRANSFER"),
+        };
+        format!(
+          r#"{{"id": "tx", "assetId": "ETH", "status": "{status}", "operation": "{operation}", "createdAt": 0,
+            "lastUpdated": 0, "source": {{"id": "0", "type": "VAULT_ACCOUNT", "name": "Default"}}, "amountInfo": {{}}, "feeInfo": {{}},
+            "signedBy": [], "rejectedBy": "", "createdBy": "u", "destinationAddress": "", "sourceAddress": "",
+            "destinationAddressDescription": "", "destinationTag": "", "addressType": "", "note": "",
+            "exchangeTxId": "", "feeCurrency": "ETH", "signedMessages": [], "numOfConfirmations": 0,
+            "networkRecords": []}}"#
+        )
+      },
+      (&Method::POST, _) => String::from(r#"{"success": true}"#),
+      _ => return mock::json(StatusCode::NOT_FOUND, "{}"),
+    };
+    mock::json(StatusCode::OK, body)
+  }
+
+  #[tokio::test]
+  async fn test_wallet_connect_review() -> color_eyre::Result<()> {
+    let request = WalletConnectRequest { uri: String::from("wc:1"), ..Default::default() };
+    for allowed in ["https://app.uniswap.org", "https://other.app"] {
+      let transport = MockTransport::new(fireblocks);
+      let client = mock::client(transport.clone())?;
+      let policy = |pending: &WalletConnectResponse| pending.metadata.as_ref().is_some_and(|m| m.app_url == allowed);
+      let approved = client.wallet_connect_review(&request, policy).await?.0;
+      assert_eq!(approved, allowed == "https://app.uniswap.org");
+      let answer = transport.requests().pop().and_then(|r| r.body).unwrap_or_default();
+      assert_eq!(serde_json::from_slice::<serde_json::Value>(&answer)?, serde_json::json!({ "approve": approved }));
+    }
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn test_typed_message_review() -> color_eyre::Result<()> {
+    let transport = MockTransport::new(fireblocks);
+    let client = mock::client(transport.clone())?;
+    let cancels = || transport.requests().iter().filter(|r| r.method == Method::POST).count();
+
+    assert!(client.typed_message_review("tx1", |_| true).await?.0);
+    assert_eq!(cancels(), 0);
+    assert!(!client.typed_message_review("tx1", |_| false).await?.0);
+    assert_eq!(cancels(), 1);
+    assert_eq!(
+      transport.requests().pop().map(|r| r.url.path().to_string()),
+      Some("/v1/transactions/tx1/cancel".into())
+    );
+
+    // signed messages and transfers are left alone
+    assert!(!client.typed_message_review("tx2", |_| false).await?.0);
+    assert!(!client.typed_message_review("tx3", |_| false).await?.0);
+    assert_eq!(cancels(), 1);
+    Ok(())
+  }
 }
@@ -0,0 +1,25 @@
+use crate::types::OtaStatus;
+use crate::Client;
+use crate::Result;
+
+impl Client {
+  /// See
+  /// * [getOtaStatus](https://developers.fireblocks.com/reference/getotastatus)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn ota_status(&self) -> Result<OtaStatus> {
+    let u = self.build_url("management/ota")?.0;
+    self.get(u).await
+  }
+
+  /// Enable or disable one-time addresses. The change is applied asynchronously, poll
+  /// [`Client::ota_status`] to confirm it
+  ///
+  /// See
+  /// * [setOtaStatus](https://developers.fireblocks.com/reference/setotastatus)
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn ota_set(&self, enabled: bool) -> Result<()> {
+    let u = self.build_url("management/ota")?.0;
+    let (_, id) = self.post::<serde_json::Value, _>(u, Some(&OtaStatus { enabled })).await?;
+    Ok(((), id))
+  }
+}
@@ -0,0 +1,137 @@
+//! Command line access to a Fireblocks workspace
+//!
+//! ```sh
+//! export FIREBLOCKS_API_KEY=...
+//! export FIREBLOCKS_SECRET="$(cat fireblocks_secret.key)"
+//! cargo run --features cli --bin fireblocks -- --sandbox vaults
+//! ```
+use bigdecimal::BigDecimal;
+use clap::{Parser, Subcommand};
+use fireblocks_sdk::types::TransactionListBuilder;
+use fireblocks_sdk::{Client, ClientBuilder, PagedClient};
+use futures::TryStreamExt;
+use std::io::Write;
+use std::sync::Arc;
+
+type CliResult = Result<(), Box<dyn std::error::Error>>;
+
+#[derive(Parser)]
+#[command(name = "fireblocks", version, about = "Fireblocks workspace from the command line")]
+struct Cli {
+  #[arg(long, env = "FIREBLOCKS_API_KEY", hide_env_values = true)]
+  api_key: String,
+  /// RSA private key (PEM) of the API user
+  #[arg(long, env = "FIREBLOCKS_SECRET", hide_env_values = true)]
+  secret: String,
+  /// Use the sandbox environment
+  #[arg(long)]
+  sandbox: bool,
+  #[command(subcommand)]
+  command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+  /// List vault accounts
+  Vaults {
+    #[arg(long, default_value_t = 100)]
+    batch: u16,
+  },
+  /// Show the balances of a vault account
+  Balance { vault: i32, asset: Option<String> },
+  /// Print transactions as they are received, newest first
+  Transactions {
+    #[arg(long, default_value_t = 100)]
+    limit: u16,
+  },
+  /// Transfer between two vault accounts
+  Transfer {
+    #[arg(long)]
+    from: i32,
+    #[arg(long)]
+    to: i32,
+    #[arg(long)]
+    asset: String,
+    #[arg(long)]
+    amount: BigDecimal,
+    #[arg(long)]
+    note: Option<String>,
+    /// Skip the confirmation prompt
+    #[arg(long, short)]
+    yes: bool,
+  },
+}
+
+async fn vaults(client: Client, batch: u16) -> CliResult {
+  let mut pages = PagedClient::new(Arc::new(client)).vaults(batch);
+  while let Some((page, _)) = pages.try_next().await? {
+    for account in page {
+      println!("{:>6}  {}", account.id, account.name);
+    }
+  }
+  Ok(())
+}
+
+async fn balance(client: Client, vault: i32, asset: Option<String>) -> CliResult {
+  let assets = match asset {
+    Some(asset) => vec![client.vault_asset(vault, asset).await?.0],
+    None => client.vault(vault).await?.0.assets,
+  };
+  for a in assets {
+    println!("{:<20} total {:<24} available {}", a.id, a.total, a.available);
+  }
+  Ok(())
+}
+
+async fn transactions(client: Client, limit: u16) -> CliResult {
+  let params = TransactionListBuilder::new().limit(limit).sort_desc().build()?;
+  let (mut txs, _) = client.transactions_stream(params).await?;
+  while let Some(tx) = txs.try_next().await? {
+    println!("{}  {:<24} {:<10} {:?}", tx.id, format!("{:?}", tx.status), tx.asset_id, tx.amount);
+  }
+  Ok(())
+}
+
+fn confirm(prompt: &str) -> std::io::Result<bool> {
+  print!("{prompt} [y/N] ");
+  std::io::stdout().flush()?;
+  let mut answer = String::new();
+  std::io::stdin().read_line(&mut answer)?;
+  Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+async fn transfer(
+  client: Client,
+  from: i32,
+  to: i32,
+  asset: String,
+  amount: BigDecimal,
+  note: Option<String>,
+  yes: bool,
+) -> CliResult {
+  if !yes && !confirm(&format!("transfer {amount} {asset} from vault {from} to vault {to}?"))? {
+    println!("aborted");
+    return Ok(());
+  }
+  let (tx, request_id) = client.create_transaction_vault(from, to, asset, amount, note.as_deref()).await?;
+  println!("created {} {:?} (request id {request_id})", tx.id, tx.status);
+  Ok(())
+}
+
+#[tokio::main]
+async fn main() -> CliResult {
+  let cli = Cli::parse();
+  let mut builder = ClientBuilder::new(&cli.api_key, cli.secret.as_bytes());
+  if cli.sandbox {
+    builder = builder.with_sandbox();
+  }
+  let client = builder.build()?;
+  match cli.command {
+    Command::Vaults { batch } => vaults(client, batch).await,
+    Command::Balance { vault, asset } => balance(client, vault, asset).await,
+    Command::Transactions { limit } => transactions(client, limit).await,
+    Command::Transfer { from, to, asset, amount, note, yes } => {
+      transfer(client, from, to, asset, amount, note, yes).await
+    },
+  }
+}
@@ -0,0 +1,122 @@
+//! Optional TTL cache for responses that rarely change
+//!
+//! ```
+//! use fireblocks_sdk::cache::{CacheClass, CacheConfig};
+//! use fireblocks_sdk::ClientBuilder;
+//! use std::time::Duration;
+//!
+//! fn client(api_key: &str, secret: &[u8]) -> color_eyre::Result<()> {
+//!   let cache = CacheConfig::default().with_ttl(CacheClass::NetworkFees, Duration::from_secs(10));
+//!   let client = ClientBuilder::new(api_key, secret).with_cache(cache).build()?;
+//!   Ok(())
+//! }
+//! ```
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Endpoints sharing a TTL
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheClass {
+  /// [`crate::Client::supported_assets`]
+  SupportedAssets,
+  /// [`crate::Client::blockchains`] and [`crate::Client::blockchain`]
+  Blockchains,
+  /// [`crate::Client::estimate_fee`]
+  NetworkFees,
+}
+
+#[derive(Debug, Clone)]
+pub struct CacheConfig {
+  ttls: HashMap<CacheClass, Duration>,
+}
+
+impl Default for CacheConfig {
+  /// One hour for assets and blockchains, 30 seconds for fees
+  fn default() -> Self {
+    let ttls = HashMap::from([
+      (CacheClass::SupportedAssets, Duration::from_hours(1)),
+      (CacheClass::Blockchains, Duration::from_hours(1)),
+      (CacheClass::NetworkFees, Duration::from_secs(30)),
+    ]);
+    Self { ttls }
+  }
+}
+
+impl CacheConfig {
+  /// A zero TTL disables caching of the class
+  #[must_use]
+  pub fn with_ttl(mut self, class: CacheClass, ttl: Duration) -> Self {
+    self.ttls.insert(class, ttl);
+    self
+  }
+
+  pub fn ttl(&self, class: CacheClass) -> Duration {
+    self.ttls.get(&class).copied().unwrap_or_default()
+  }
+}
+
+/// Expiry, `None` for a TTL too long to represent, and the value
+type Entry = (Option<Instant>, Arc<dyn Any + Send + Sync>);
+
+#[derive(Debug)]
+pub(crate) struct ResponseCache {
+  config: CacheConfig,
+  entries: Mutex<HashMap<(CacheClass, String), Entry>>,
+}
+
+impl ResponseCache {
+  pub fn new(config: CacheConfig) -> Self {
+    Self { config, entries: Mutex::default() }
+  }
+
+  pub fn get<T: Clone + 'static>(&self, class: CacheClass, key: &str) -> Option<T> {
+    let (expires, value) = self.entries.lock().ok()?.get(&(class, key.to_string())).cloned()?;
+    if expires.is_some_and(|expires| expires <= Instant::now()) {
+      return None;
+    }
+    value.downcast_ref::<T>().cloned()
+  }
+
+  pub fn insert<T: Send + Sync + 'static>(&self, class: CacheClass, key: &str, value: T) {
+    let ttl = self.config.ttl(class);
+    if ttl.is_zero() {
+      return;
+    }
+    if let Ok(mut entries) = self.entries.lock() {
+      let now = Instant::now();
+      entries.retain(|_, (expires, _)| expires.is_none_or(|expires| expires > now));
+      entries.insert((class, key.to_string()), (now.checked_add(ttl), Arc::new(value)));
+    }
+  }
+
+  pub fn clear(&self) {
+    if let Ok(mut entries) = self.entries.lock() {
+      entries.clear();
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_cache() {
+    let config = CacheConfig::default().with_ttl(CacheClass::NetworkFees, Duration::ZERO);
+    let cache = ResponseCache::new(config);
+    cache.insert(CacheClass::SupportedAssets, "", (vec![1u8], "req".to_string()));
+    cache.insert(CacheClass::NetworkFees, "ETH", (2u8, "req".to_string()));
+    assert_eq!(cache.get::<(Vec<u8>, String)>(CacheClass::SupportedAssets, ""), Some((vec![1], "req".to_string())));
+    assert_eq!(cache.get::<(u8, String)>(CacheClass::NetworkFees, "ETH"), None);
+    assert_eq!(cache.get::<(u8, String)>(CacheClass::SupportedAssets, ""), None);
+    cache.clear();
+    assert_eq!(cache.get::<(Vec<u8>, String)>(CacheClass::SupportedAssets, ""), None);
+
+    // a TTL past the end of time never expires
+    let cache = ResponseCache::new(CacheConfig::default().with_ttl(CacheClass::SupportedAssets, Duration::MAX));
+    cache.insert(CacheClass::SupportedAssets, "", 1u8);
+    assert_eq!(cache.get::<u8>(CacheClass::SupportedAssets, ""), Some(1));
+  }
+}
@@ -0,0 +1,365 @@
+//! Raw signing of Bitcoin PSBTs
+//!
+//! For workflows native transfers do not cover, e.g. multisig, coinjoin or PSBTs built by another wallet.
+//! [`PsbtSigner`] finds the vault keys of the inputs through the public key info endpoints, builds a `RAW`
+//! transaction signing each input's sighash at the key's derivation path, and puts the returned signatures
+//! into the PSBT. Only ECDSA inputs are supported, not taproot.
+//!
+//! ```
+//! use fireblocks_sdk::chain::bitcoin::PsbtSigner;
+//! use fireblocks_sdk::types::TransactionStatus;
+//! use fireblocks_sdk::{Client, ASSET_BTC};
+//! use std::time::Duration;
+//!
+//! async fn sign(c: &Client, psbt: &str) -> color_eyre::Result<String> {
+//!   let mut signer = PsbtSigner::from_base64(psbt)?;
+//!   // the first five receive addresses of vault account 0
+//!   let addresses = (0..5).map(|index| (0, index)).collect::<Vec<_>>();
+//!   signer.derive(c, 0, ASSET_BTC.as_ref(), &addresses).await?;
+//!   let (created, _) = c.create_transaction(&signer.raw_signing(0, ASSET_BTC.as_ref(), "sign psbt")?).await?;
+//!   let (signed, _) = c.poll_transaction(&created.id, Duration::from_secs(60), Duration::from_secs(2), |_| {}).await?;
+//!   assert_eq!(signed.status, TransactionStatus::COMPLETED);
+//!   signer.apply(&signed)?;
+//!   signer.finalize();
+//!   Ok(signer.to_base64())
+//! }
+//! ```
+use crate::error::ParamError;
+use crate::types::{
+  ExtraParameters, PublicKeyInfo, SigningAlgorithm, Transaction, TransactionArguments, TransactionOperation,
+  TransferPeerPath, TypedMessages, UnsignedMessage,
+};
+use crate::{Client, FireblocksError};
+use ::bitcoin::ecdsa;
+use ::bitcoin::psbt::Psbt;
+use ::bitcoin::script::{Builder, PushBytesBuf};
+use ::bitcoin::secp256k1;
+use ::bitcoin::sighash::{EcdsaSighashType, SighashCache};
+use ::bitcoin::{CompressedPublicKey, PublicKey, Script, ScriptBuf, Witness};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+/// The vault key signing an input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputKey {
+  pub public_key: CompressedPublicKey,
+  /// BIP44 path, e.g. `[44, 0, 0, 0, 3]`
+  pub derivation_path: Vec<u32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PsbtSigner {
+  psbt: Psbt,
+  /// The key of each input, `None` for inputs not signed by Fireblocks
+  keys: Vec<Option<InputKey>>,
+}
+
+impl PsbtSigner {
+  pub fn new(psbt: Psbt) -> Self {
+    let keys = vec![None; psbt.inputs.len()];
+    Self { psbt, keys }
+  }
+
+  pub fn from_base64(psbt: &str) -> Result<Self, ParamError> {
+    let bytes = STANDARD.decode(psbt).map_err(|e| invalid(format!("psbt base64: {e}")))?;
+    let psbt = Psbt::deserialize(&bytes).map_err(|e| invalid(format!("psbt: {e}")))?;
+    Ok(Self::new(psbt))
+  }
+
+  pub const fn psbt(&self) -> &Psbt {
+    &self.psbt
+  }
+
+  pub fn into_psbt(self) -> Psbt {
+    self.psbt
+  }
+
+  pub fn to_base64(&self) -> String {
+    STANDARD.encode(self.psbt.serialize())
+  }
+
+  /// The vault key of each input
+  pub fn keys(&self) -> &[Option<InputKey>] {
+    &self.keys
+  }
+
+  /// Use the key of `info` for the inputs it can sign, returning how many
+  ///
+  /// An input matches when it spends a P2WPKH, P2SH-P2WPKH or P2PKH output of the key, when its redeem
+  /// or witness script contains the key, or when its BIP32 derivations list the key.
+  pub fn assign(&mut self, info: &PublicKeyInfo) -> Result<usize, ParamError> {
+    let bytes = hex::decode(&info.public_key).map_err(|e| invalid(format!("public key {}: {e}", info.public_key)))?;
+    let public_key = CompressedPublicKey::from_slice(&bytes)
+      .map_err(|e| invalid(format!("public key {} is not a compressed key: {e}", info.public_key)))?;
+    let mut assigned = 0;
+    for index in 0..self.keys.len() {
+      if self.keys[index].is_none() && self.spends(index, &public_key) {
+        self.keys[index] = Some(InputKey { public_key, derivation_path: info.derivation_path.clone() });
+        assigned += 1;
+      }
+    }
+    Ok(assigned)
+  }
+
+  /// Look up the vault keys of the inputs, returning how many inputs have a key
+  ///
+  /// Keys listed in the BIP32 derivations of the inputs with a BIP44 path are checked against the key
+  /// Fireblocks has at that path, then the `(change, address index)` pairs of `addresses` in vault
+  /// account `vault` are tried for the remaining inputs.
+  pub async fn derive(
+    &mut self,
+    client: &Client,
+    vault: i32,
+    asset_id: &str,
+    addresses: &[(u32, u32)],
+  ) -> Result<usize, FireblocksError> {
+    for path in self.bip44_paths() {
+      let (info, _) = client.public_key_info(&path, SigningAlgorithm::MPC_ECDSA_SECP256K1, true).await?;
+      self.assign(&info)?;
+    }
+    for &(change, address_index) in addresses {
+      if self.keys.iter().all(Option::is_some) {
+        break;
+      }
+      let (info, _) = client.vault_public_key_info(vault, asset_id, change, address_index, true).await?;
+      self.assign(&info)?;
+    }
+    Ok(self.keys.iter().flatten().count())
+  }
+
+  /// A `RAW` transaction signing the sighash of every input with a key, e.g. for `BTC` or `BTC_TEST`
+  pub fn raw_signing(&self, vault: i32, asset_id: &str, note: &str) -> Result<TransactionArguments, ParamError> {
+    let messages = self
+      .sighashes()?
+      .into_iter()
+      .map(|(index, sighash, _)| UnsignedMessage {
+        content: hex::encode(sighash),
+        message_type: String::new(),
+        index: 0,
+        derivation_path: self.keys[index].as_ref().map(|key| key.derivation_path.clone()),
+      })
+      .collect::<Vec<_>>();
+    if messages.is_empty() {
+      return Err(invalid(String::from("no input has a vault key, see PsbtSigner::derive")));
+    }
+    Ok(TransactionArguments {
+      asset_id: String::from(asset_id),
+      operation: TransactionOperation::RAW,
+      source: TransferPeerPath { id: Some(vault.to_string()), ..Default::default() },
+      destination: None,
+      amount: String::from("0"),
+      gas_price: None,
+      gas_limit: None,
+      fee_level: None,
+      note: String::from(note),
+      extra_parameters: Some(ExtraParameters::RawMessageData(TypedMessages { messages })),
+      destinations: None,
+      external_tx_id: None,
+    })
+  }
+
+  /// Add the signatures of a completed raw signing transaction to the partial signatures of the inputs,
+  /// returning how many inputs were signed
+  pub fn apply(&mut self, tx: &Transaction) -> Result<usize, ParamError> {
+    let messages = tx.signed_messages.as_deref().unwrap_or_default();
+    if messages.is_empty() {
+      return Err(invalid(format!("transaction {} has no signed messages", tx.id)));
+    }
+    let sighashes = self.sighashes()?;
+    let mut signed = 0;
+    for message in messages {
+      let bytes = hex::decode(&message.public_key).map_err(|e| invalid(format!("public key: {e}")))?;
+      let public_key = CompressedPublicKey::from_slice(&bytes).map_err(|e| invalid(format!("public key: {e}")))?;
+      let full_sig = message.signature.full_sig.as_deref().unwrap_or_default();
+      let bytes = hex::decode(full_sig).map_err(|e| invalid(format!("signature {full_sig}: {e}")))?;
+      let mut signature =
+        secp256k1::ecdsa::Signature::from_compact(&bytes).map_err(|e| invalid(format!("signature {full_sig}: {e}")))?;
+      signature.normalize_s();
+      for (index, sighash, sighash_type) in &sighashes {
+        let key = self.keys[*index].as_ref().map(|key| key.public_key);
+        if key == Some(public_key) && message.content.eq_ignore_ascii_case(&hex::encode(sighash)) {
+          let signature = ecdsa::Signature { signature, sighash_type: *sighash_type };
+          self.psbt.inputs[*index].partial_sigs.insert(PublicKey::from(public_key), signature);
+          signed += 1;
+        }
+      }
+    }
+    Ok(signed)
+  }
+
+  /// Finalize the signed P2WPKH, P2SH-P2WPKH and P2PKH inputs, returning how many were finalized
+  ///
+  /// Other inputs keep their partial signatures for the wallet that completes them.
+  pub fn finalize(&mut self) -> usize {
+    let mut finalized = 0;
+    for (index, key) in self.keys.iter().enumerate() {
+      let Some(key) = key else { continue };
+      let Ok(utxo) = self.psbt.spend_utxo(index) else { continue };
+      let script_pubkey = utxo.script_pubkey.clone();
+      let input = &mut self.psbt.inputs[index];
+      let public_key = PublicKey::from(key.public_key);
+      let Some(signature) = input.partial_sigs.get(&public_key).copied() else { continue };
+      let nested = input.redeem_script.as_deref().is_some_and(Script::is_p2wpkh);
+      if script_pubkey.is_p2wpkh() || (script_pubkey.is_p2sh() && nested) {
+        if nested {
+          let redeem_script = input.redeem_script.as_deref().map(Script::to_bytes).unwrap_or_default();
+          let push = PushBytesBuf::try_from(redeem_script).unwrap_or_default();
+          input.final_script_sig = Some(Builder::new().push_slice(push).into_script());
+        }
+        input.final_script_witness = Some(Witness::p2wpkh(&signature, &key.public_key.0));
+      } else if script_pubkey.is_p2pkh() {
+        let script_sig = Builder::new().push_slice(signature.serialize()).push_key(&public_key).into_script();
+        input.final_script_sig = Some(script_sig);
+      } else {
+        continue;
+      }
+      // BIP174 finalizers clear everything but the UTXO and the final scripts
+      input.partial_sigs.clear();
+      input.sighash_type = None;
+      input.redeem_script = None;
+      input.witness_script = None;
+      input.bip32_derivation.clear();
+      finalized += 1;
+    }
+    finalized
+  }
+
+  /// Sighash of every input with a key
+  fn sighashes(&self) -> Result<Vec<(usize, [u8; 32], EcdsaSighashType)>, ParamError> {
+    let mut cache = SighashCache::new(&self.psbt.unsigned_tx);
+    let mut sighashes = vec![];
+    for (index, _) in self.keys.iter().enumerate().filter(|(_, key)| key.is_some()) {
+      let (message, sighash_type) =
+        self.psbt.sighash_ecdsa(index, &mut cache).map_err(|e| invalid(format!("input {index}: {e}")))?;
+      sighashes.push((index, *message.as_ref(), sighash_type));
+    }
+    Ok(sighashes)
+  }
+
+  /// BIP44 paths in the BIP32 derivations of inputs without a key
+  fn bip44_paths(&self) -> Vec<Vec<u32>> {
+    let mut paths = vec![];
+    for (input, _) in self.psbt.inputs.iter().zip(&self.keys).filter(|(_, key)| key.is_none()) {
+      for (_, path) in input.bip32_derivation.values() {
+        let path = path.into_iter().map(|child| u32::from(*child) & !(1 << 31)).collect::<Vec<_>>();
+        if path.len() == 5 && path[0] == 44 && !paths.contains(&path) {
+          paths.push(path);
+        }
+      }
+    }
+    paths
+  }
+
+  fn spends(&self, index: usize, public_key: &CompressedPublicKey) -> bool {
+    let input = &self.psbt.inputs[index];
+    if input.bip32_derivation.contains_key(&public_key.0) {
+      return true;
+    }
+    let key = public_key.to_bytes();
+    let in_script = |script: &ScriptBuf| script.as_bytes().windows(key.len()).any(|w| w == key);
+    if input.redeem_script.iter().chain(&input.witness_script).any(in_script) {
+      return true;
+    }
+    let Ok(utxo) = self.psbt.spend_utxo(index) else {
+      return false;
+    };
+    let p2wpkh = ScriptBuf::new_p2wpkh(&public_key.wpubkey_hash());
+    utxo.script_pubkey == p2wpkh
+      || utxo.script_pubkey == ScriptBuf::new_p2pkh(&public_key.pubkey_hash())
+      || utxo.script_pubkey == ScriptBuf::new_p2sh(&p2wpkh.script_hash())
+  }
+}
+
+const fn invalid(msg: String) -> ParamError {
+  ParamError::InvalidParams { msg }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::types::SignedMessage;
+  use ::bitcoin::absolute::LockTime;
+  use ::bitcoin::bip32::Fingerprint;
+  use ::bitcoin::transaction::Version;
+  use ::bitcoin::{Amount, OutPoint, TxIn, TxOut};
+
+  fn psbt(script_pubkey: &ScriptBuf) -> color_eyre::Result<Psbt> {
+    let tx = ::bitcoin::Transaction {
+      version: Version::TWO,
+      lock_time: LockTime::ZERO,
+      input: (0..2)
+        .map(|vout| TxIn { previous_output: OutPoint { vout, ..Default::default() }, ..Default::default() })
+        .collect(),
+      output: vec![TxOut { value: Amount::from_sat(9_000), script_pubkey: ScriptBuf::new() }],
+    };
+    let mut psbt = Psbt::from_unsigned_tx(tx)?;
+    for input in &mut psbt.inputs {
+      input.witness_utxo = Some(TxOut { value: Amount::from_sat(5_000), script_pubkey: script_pubkey.clone() });
+    }
+    Ok(psbt)
+  }
+
+  #[test]
+  fn test_sign_p2wpkh() -> color_eyre::Result<()> {
+    let secp = secp256k1::Secp256k1::new();
+    let secret = secp256k1::SecretKey::from_slice(&[7; 32])?;
+    let public_key = CompressedPublicKey(secret.public_key(&secp));
+    let mut signer = PsbtSigner::new(psbt(&ScriptBuf::new_p2wpkh(&public_key.wpubkey_hash()))?);
+    assert!(signer.raw_signing(0, "BTC_TEST", "psbt").is_err());
+
+    let other = secp256k1::SecretKey::from_slice(&[8; 32])?.public_key(&secp);
+    let other = PublicKeyInfo { public_key: hex::encode(other.serialize()), ..Default::default() };
+    assert_eq!(signer.assign(&other)?, 0);
+    assert!(signer.assign(&PublicKeyInfo { public_key: String::from("zz"), ..Default::default() }).is_err());
+    let info = PublicKeyInfo {
+      derivation_path: vec![44, 1, 0, 0, 3],
+      public_key: hex::encode(public_key.to_bytes()),
+      ..Default::default()
+    };
+    assert_eq!(signer.assign(&info)?, 2);
+
+    let args = signer.raw_signing(0, "BTC_TEST", "psbt")?;
+    let json = serde_json::to_value(&args)?;
+    let messages = json["extraParameters"]["rawMessageData"]["messages"].as_array().cloned().unwrap_or_default();
+    assert_eq!(messages.len(), 2);
+    assert_eq!(messages[0]["derivationPath"], serde_json::json!([44, 1, 0, 0, 3]));
+
+    let signed_messages = messages
+      .iter()
+      .map(|unsigned| -> color_eyre::Result<SignedMessage> {
+        let content = unsigned["content"].as_str().unwrap_or_default();
+        let digest: [u8; 32] = hex::decode(content)?.try_into().map_err(|_| color_eyre::eyre::eyre!("digest"))?;
+        let signature = secp.sign_ecdsa(&secp256k1::Message::from_digest(digest), &secret);
+        let mut message = SignedMessage {
+          content: String::from(content),
+          derivation_path: vec![44, 1, 0, 0, 3],
+          public_key: hex::encode(public_key.to_bytes()),
+          ..Default::default()
+        };
+        message.signature.full_sig = Some(hex::encode(signature.serialize_compact()));
+        Ok(message)
+      })
+      .collect::<color_eyre::Result<Vec<_>>>()?;
+    let tx = Transaction { signed_messages: Some(signed_messages), ..Default::default() };
+    assert_eq!(signer.apply(&tx)?, 2);
+    assert_eq!(signer.psbt().inputs[0].partial_sigs.len(), 1);
+
+    assert_eq!(signer.finalize(), 2);
+    let round_trip = PsbtSigner::from_base64(&signer.to_base64())?;
+    let tx = round_trip.into_psbt().extract_tx_unchecked_fee_rate();
+    assert_eq!(tx.input[0].witness.len(), 2);
+    Ok(())
+  }
+
+  #[test]
+  fn test_bip44_paths() -> color_eyre::Result<()> {
+    let secp = secp256k1::Secp256k1::new();
+    let key = secp256k1::SecretKey::from_slice(&[9; 32])?.public_key(&secp);
+    let mut psbt = psbt(&ScriptBuf::new())?;
+    let path = "m/44'/0'/2'/0/5".parse()?;
+    psbt.inputs[0].bip32_derivation.insert(key, (Fingerprint::default(), path));
+    let signer = PsbtSigner::new(psbt);
+    assert_eq!(signer.bip44_paths(), [vec![44, 0, 2, 0, 5]]);
+    Ok(())
+  }
+}
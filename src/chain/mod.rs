@@ -0,0 +1,4 @@
+//! Helpers turning chain native transactions into Fireblocks raw signing requests and back
+#[cfg(feature = "bitcoin")]
+pub mod bitcoin;
+pub mod solana;
@@ -0,0 +1,366 @@
+//! Raw signing of Solana transactions
+//!
+//! Fireblocks signs the serialized message of a transaction with the Ed25519 key of a vault account.
+//! [`RawTransaction`] splits a transaction into that message and its signature slots, builds the `RAW`
+//! transaction for Fireblocks, and puts the returned signature back into a transaction ready to broadcast.
+//! With the `solana` feature it also converts `solana-sdk` transactions.
+//!
+//! ```
+//! use fireblocks_sdk::chain::solana::RawTransaction;
+//! use fireblocks_sdk::types::TransactionStatus;
+//! use fireblocks_sdk::{Client, ASSET_SOL};
+//! use std::time::Duration;
+//!
+//! async fn sign(c: &Client, unsigned: &[u8]) -> color_eyre::Result<String> {
+//!   let mut raw = RawTransaction::from_bytes(unsigned)?;
+//!   let (created, _) = c.create_transaction(&raw.raw_signing(0, ASSET_SOL.as_ref(), "sign swap")).await?;
+//!   let (signed, _) = c.poll_transaction(&created.id, Duration::from_secs(60), Duration::from_secs(2), |_| {}).await?;
+//!   assert_eq!(signed.status, TransactionStatus::COMPLETED);
+//!   raw.apply(&signed)?;
+//!   Ok(raw.to_base64())
+//! }
+//! ```
+use crate::error::ParamError;
+use crate::types::{
+  ExtraParameters, Transaction, TransactionArguments, TransactionOperation, TransferPeerPath, TypedMessages,
+  UnsignedMessage,
+};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+const SIGNATURE_LEN: usize = 64;
+const KEY_LEN: usize = 32;
+/// Set on the first byte of versioned messages
+const VERSION_PREFIX: u8 = 0x80;
+
+type Signature = [u8; SIGNATURE_LEN];
+type PublicKey = [u8; KEY_LEN];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawTransaction {
+  signatures: Vec<Signature>,
+  message: Vec<u8>,
+  /// Required signers, in the order of `signatures`
+  signers: Vec<PublicKey>,
+}
+
+impl RawTransaction {
+  /// A serialized transaction, legacy or versioned, e.g. decoded from the base64 a dApp or RPC returned
+  pub fn from_bytes(tx: &[u8]) -> Result<Self, ParamError> {
+    let mut reader = Reader::new(tx);
+    let count = reader.short_vec_len()?;
+    let signatures = (0..count).map(|_| reader.array::<SIGNATURE_LEN>()).collect::<Result<Vec<_>, _>>()?;
+    let mut raw = Self::from_message(reader.rest())?;
+    if signatures.len() != raw.signers.len() {
+      return Err(invalid(format!("{} signatures for {} signers", signatures.len(), raw.signers.len())));
+    }
+    raw.signatures = signatures;
+    Ok(raw)
+  }
+
+  /// A serialized message, with every signature still empty
+  pub fn from_message(message: &[u8]) -> Result<Self, ParamError> {
+    let mut reader = Reader::new(message);
+    if reader.peek()? & VERSION_PREFIX != 0 {
+      let version = reader.byte()? & !VERSION_PREFIX;
+      if version != 0 {
+        return Err(invalid(format!("unsupported message version {version}")));
+      }
+    }
+    let required = usize::from(reader.byte()?);
+    // read-only signed and unsigned account counts
+    reader.array::<2>()?;
+    let accounts = reader.short_vec_len()?;
+    if accounts < required {
+      return Err(invalid(format!("{required} signers but only {accounts} accounts")));
+    }
+    let signers = (0..required).map(|_| reader.array::<KEY_LEN>()).collect::<Result<Vec<_>, _>>()?;
+    Ok(Self { signatures: vec![[0; SIGNATURE_LEN]; required], message: message.to_vec(), signers })
+  }
+
+  /// The bytes Fireblocks signs
+  pub fn message(&self) -> &[u8] {
+    &self.message
+  }
+
+  /// Public keys of the required signers, the fee payer first
+  pub fn signers(&self) -> &[PublicKey] {
+    &self.signers
+  }
+
+  pub fn signatures(&self) -> &[Signature] {
+    &self.signatures
+  }
+
+  /// Whether every signer has signed
+  pub fn is_signed(&self) -> bool {
+    self.signatures.iter().all(|s| s != &[0; SIGNATURE_LEN])
+  }
+
+  /// A `RAW` transaction signing the message with the `asset_id` key of vault account `vault`, e.g.
+  /// `SOL` or `SOL_TEST`
+  pub fn raw_signing(&self, vault: i32, asset_id: &str, note: &str) -> TransactionArguments {
+    let message = UnsignedMessage {
+      content: hex::encode(&self.message),
+      message_type: String::new(),
+      index: 0,
+      derivation_path: None,
+    };
+    TransactionArguments {
+      asset_id: String::from(asset_id),
+      operation: TransactionOperation::RAW,
+      source: TransferPeerPath { id: Some(vault.to_string()), ..Default::default() },
+      destination: None,
+      amount: String::from("0"),
+      gas_price: None,
+      gas_limit: None,
+      fee_level: None,
+      note: String::from(note),
+      extra_parameters: Some(ExtraParameters::RawMessageData(TypedMessages { messages: vec![message] })),
+      destinations: None,
+      external_tx_id: None,
+    }
+  }
+
+  /// Put `signature` in the slot of `signer`
+  pub fn sign(&mut self, signer: &PublicKey, signature: Signature) -> Result<(), ParamError> {
+    let slot = self
+      .signers
+      .iter()
+      .position(|s| s == signer)
+      .ok_or_else(|| invalid(format!("{} is not a signer of the transaction", hex::encode(signer))))?;
+    self.signatures[slot] = signature;
+    Ok(())
+  }
+
+  /// Take the signatures of a completed raw signing transaction
+  pub fn apply(&mut self, tx: &Transaction) -> Result<(), ParamError> {
+    let messages = tx.signed_messages.as_deref().unwrap_or_default();
+    if messages.is_empty() {
+      return Err(invalid(format!("transaction {} has no signed messages", tx.id)));
+    }
+    for message in messages {
+      let signer = decode_hex::<KEY_LEN>("public key", &message.public_key)?;
+      let signature =
+        decode_hex::<SIGNATURE_LEN>("signature", message.signature.full_sig.as_deref().unwrap_or_default())?;
+      self.sign(&signer, signature)?;
+    }
+    Ok(())
+  }
+
+  /// The serialized transaction
+  pub fn to_bytes(&self) -> Vec<u8> {
+    let mut tx = short_vec(self.signatures.len());
+    for signature in &self.signatures {
+      tx.extend_from_slice(signature);
+    }
+    tx.extend_from_slice(&self.message);
+    tx
+  }
+
+  /// The serialized transaction in base64, as `sendTransaction` takes it with `"encoding": "base64"`
+  pub fn to_base64(&self) -> String {
+    STANDARD.encode(self.to_bytes())
+  }
+}
+
+#[cfg(feature = "solana")]
+impl TryFrom<&solana_sdk::transaction::VersionedTransaction> for RawTransaction {
+  type Error = ParamError;
+
+  fn try_from(tx: &solana_sdk::transaction::VersionedTransaction) -> Result<Self, Self::Error> {
+    let mut raw = Self::from_message(&tx.message.serialize())?;
+    raw.copy_signatures(&tx.signatures)?;
+    Ok(raw)
+  }
+}
+
+#[cfg(feature = "solana")]
+impl TryFrom<&solana_sdk::transaction::Transaction> for RawTransaction {
+  type Error = ParamError;
+
+  fn try_from(tx: &solana_sdk::transaction::Transaction) -> Result<Self, Self::Error> {
+    let mut raw = Self::from_message(&tx.message_data())?;
+    raw.copy_signatures(&tx.signatures)?;
+    Ok(raw)
+  }
+}
+
+#[cfg(feature = "solana")]
+impl RawTransaction {
+  /// The signatures for `signatures` of the `solana-sdk` transaction this was made from
+  pub fn solana_signatures(&self) -> Vec<solana_sdk::signature::Signature> {
+    self.signatures.iter().map(|s| solana_sdk::signature::Signature::from(*s)).collect()
+  }
+
+  fn copy_signatures(&mut self, signatures: &[solana_sdk::signature::Signature]) -> Result<(), ParamError> {
+    // a transaction not signed yet may have no signatures at all
+    if signatures.is_empty() {
+      return Ok(());
+    }
+    if signatures.len() != self.signers.len() {
+      return Err(invalid(format!("{} signatures for {} signers", signatures.len(), self.signers.len())));
+    }
+    for (slot, signature) in self.signatures.iter_mut().zip(signatures) {
+      slot.copy_from_slice(signature.as_ref());
+    }
+    Ok(())
+  }
+}
+
+const fn invalid(msg: String) -> ParamError {
+  ParamError::InvalidParams { msg }
+}
+
+fn decode_hex<const N: usize>(what: &str, value: &str) -> Result<[u8; N], ParamError> {
+  let bytes = hex::decode(value).map_err(|e| invalid(format!("{what} {value}: {e}")))?;
+  bytes.try_into().map_err(|_| invalid(format!("{what} {value} is not {N} bytes")))
+}
+
+/// Compact-u16 length prefix
+fn short_vec(mut len: usize) -> Vec<u8> {
+  let mut out = vec![];
+  loop {
+    let byte = u8::try_from(len & 0x7f).unwrap_or_default();
+    len >>= 7;
+    if len == 0 {
+      out.push(byte);
+      return out;
+    }
+    out.push(byte | 0x80);
+  }
+}
+
+struct Reader<'a> {
+  bytes: &'a [u8],
+  pos: usize,
+}
+
+impl<'a> Reader<'a> {
+  const fn new(bytes: &'a [u8]) -> Self {
+    Self { bytes, pos: 0 }
+  }
+
+  fn peek(&self) -> Result<u8, ParamError> {
+    self.bytes.get(self.pos).copied().ok_or_else(|| invalid(String::from("truncated solana transaction")))
+  }
+
+  fn byte(&mut self) -> Result<u8, ParamError> {
+    let b = self.peek()?;
+    self.pos += 1;
+    Ok(b)
+  }
+
+  fn array<const N: usize>(&mut self) -> Result<[u8; N], ParamError> {
+    let slice =
+      self.bytes.get(self.pos..self.pos + N).ok_or_else(|| invalid(String::from("truncated solana transaction")))?;
+    self.pos += N;
+    Ok(slice.try_into().unwrap_or([0; N]))
+  }
+
+  fn short_vec_len(&mut self) -> Result<usize, ParamError> {
+    let mut len = 0;
+    for shift in [0, 7, 14] {
+      let b = self.byte()?;
+      len |= usize::from(b & 0x7f) << shift;
+      if b & 0x80 == 0 {
+        return Ok(len);
+      }
+    }
+    Err(invalid(String::from("invalid compact-u16 length")))
+  }
+
+  fn rest(&self) -> &'a [u8] {
+    &self.bytes[self.pos..]
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::types::{SignedMessage, SigningAlgorithm};
+
+  /// A transfer-like legacy message: one signer, one program, one instruction
+  fn message(versioned: bool) -> Vec<u8> {
+    let mut m = if versioned { vec![VERSION_PREFIX] } else { vec![] };
+    m.extend([1, 0, 1, 2]);
+    m.extend([1; KEY_LEN]);
+    m.extend([2; KEY_LEN]);
+    m.extend([3; 32]); // recent blockhash
+    m.extend([1, 1, 1, 0, 1, 9]); // program 1, account 0, data [9]
+    if versioned {
+      m.push(0); // no address table lookups
+    }
+    m
+  }
+
+  #[test]
+  fn test_round_trip() -> color_eyre::Result<()> {
+    let mut tx = vec![1];
+    tx.extend([0; SIGNATURE_LEN]);
+    tx.extend(message(false));
+    let mut raw = RawTransaction::from_bytes(&tx)?;
+    assert_eq!(raw.signers(), [[1; KEY_LEN]]);
+    assert_eq!(raw.message(), message(false));
+    assert!(!raw.is_signed());
+    assert!(raw.sign(&[2; KEY_LEN], [7; SIGNATURE_LEN]).is_err());
+    raw.sign(&[1; KEY_LEN], [7; SIGNATURE_LEN])?;
+    assert!(raw.is_signed());
+    assert_eq!(raw.to_bytes()[1..=SIGNATURE_LEN], [7; SIGNATURE_LEN]);
+    assert_eq!(RawTransaction::from_bytes(&raw.to_bytes())?, raw);
+
+    let versioned = RawTransaction::from_message(&message(true))?;
+    assert_eq!(versioned.signers(), [[1; KEY_LEN]]);
+    assert!(RawTransaction::from_message(&[VERSION_PREFIX | 1, 1, 0, 0]).is_err());
+    assert!(RawTransaction::from_bytes(&[1, 0, 0]).is_err());
+    Ok(())
+  }
+
+  #[test]
+  fn test_raw_signing() -> color_eyre::Result<()> {
+    let mut raw = RawTransaction::from_message(&message(false))?;
+    let args = raw.raw_signing(3, "SOL_TEST", "swap");
+    let json = serde_json::to_value(&args)?;
+    assert_eq!(json["operation"], "RAW");
+    assert_eq!(json["source"]["id"], "3");
+    assert_eq!(json["extraParameters"]["rawMessageData"]["messages"][0]["content"], hex::encode(message(false)));
+    assert!(json["extraParameters"]["rawMessageData"]["messages"][0].get("type").is_none());
+
+    let mut signed = Transaction { id: String::from("tx"), ..Default::default() };
+    assert!(raw.apply(&signed).is_err());
+    let mut message = SignedMessage {
+      derivation_path: vec![44, 501, 3, 0, 0],
+      algorithm: SigningAlgorithm::MPC_EDDSA_ED25519,
+      public_key: hex::encode([1; KEY_LEN]),
+      ..Default::default()
+    };
+    message.signature.full_sig = Some(hex::encode([5; SIGNATURE_LEN]));
+    signed.signed_messages = Some(vec![message]);
+    raw.apply(&signed)?;
+    assert_eq!(raw.signatures(), [[5; SIGNATURE_LEN]]);
+    assert_eq!(STANDARD.decode(raw.to_base64())?, raw.to_bytes());
+    assert_eq!(short_vec(300), [0xac, 0x02]);
+    Ok(())
+  }
+
+  #[cfg(feature = "solana")]
+  #[test]
+  fn test_solana_sdk() -> color_eyre::Result<()> {
+    use solana_sdk::hash::Hash;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+    use solana_sdk::message::Message;
+    use solana_sdk::pubkey::Pubkey;
+
+    let payer = Pubkey::new_from_array([1; KEY_LEN]);
+    let program = Pubkey::new_from_array([2; KEY_LEN]);
+    let instruction = Instruction::new_with_bytes(program, &[9], vec![AccountMeta::new(payer, true)]);
+    let message = Message::new_with_blockhash(&[instruction], Some(&payer), &Hash::new_from_array([3; 32]));
+    let mut tx = solana_sdk::transaction::Transaction::new_unsigned(message);
+    let mut raw = RawTransaction::try_from(&tx)?;
+    assert_eq!(raw.signers(), [payer.to_bytes()]);
+    raw.sign(&payer.to_bytes(), [5; SIGNATURE_LEN])?;
+    tx.signatures = raw.solana_signatures();
+    assert_eq!(RawTransaction::try_from(&tx)?, raw);
+    Ok(())
+  }
+}
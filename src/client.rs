@@ -1,29 +1,69 @@
+use crate::cache::{CacheClass, CacheConfig, ResponseCache};
+use crate::decode::Strictness;
 use crate::error::FireblocksError;
-use crate::jwt::Signer;
+use crate::json_stream::{item_stream, ItemStream};
+use crate::jwt::{JwtError, Signer};
+use crate::queue::{current_priority, Priority, QueueConfig, RequestQueue};
+use crate::rate::RateBudget;
+use crate::shutdown::Lifecycle;
+use crate::simulate::Simulator;
+#[cfg(feature = "reqwest")]
+use crate::transport::ReqwestTransport;
+use crate::transport::{HttpRequest, HttpTransport};
 use crate::{error, FIREBLOCKS_API, FIREBLOCKS_SANDBOX_API};
+use http::header::{HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use http::{HeaderMap, Method, StatusCode};
 use jsonwebtoken::EncodingKey;
-use reqwest::{Method, RequestBuilder, StatusCode};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use std::borrow::Borrow;
 use std::fmt::{Debug, Display};
-use std::sync::Arc;
+use std::future::Future;
+use std::sync::{Arc, PoisonError, RwLock};
 use std::time::Duration;
 use tracing::debug;
 use url::Url;
 
 #[derive(Clone)]
+#[allow(clippy::struct_field_names)]
 pub struct Client {
-  signer: Arc<Signer>,
-  client: reqwest::Client,
+  signer: Arc<RwLock<Signer>>,
+  transport: Arc<dyn HttpTransport>,
   host: String,
+  cache: Option<Arc<ResponseCache>>,
+  strictness: Strictness,
+  tag_validation: bool,
+  required_tags: bool,
+  simulator: Option<Arc<dyn Simulator>>,
+  queue: Option<Arc<RequestQueue>>,
+  rate_budget: Option<Arc<RateBudget>>,
+  lifecycle: Arc<Lifecycle>,
 }
 
+/// Without the `reqwest` feature the timeout, pool and user agent settings have no effect, a transport must be
+/// given with [`ClientBuilder::with_transport`]
+#[cfg_attr(not(feature = "reqwest"), allow(dead_code))]
+#[allow(clippy::struct_excessive_bools)]
 pub struct ClientBuilder {
   api_key: String,
+  #[cfg(feature = "reqwest")]
   client: Option<reqwest::Client>,
+  transport: Option<Arc<dyn HttpTransport>>,
   timeout: Duration,
   connect_timeout: Duration,
+  pool_max_idle_per_host: Option<usize>,
+  pool_idle_timeout: Option<Duration>,
+  tcp_keepalive: Option<Duration>,
+  http2_prior_knowledge: bool,
+  http2_keep_alive: Option<(Duration, Duration)>,
+  http2_adaptive_window: bool,
+  cache: Option<CacheConfig>,
+  strictness: Strictness,
+  tag_validation: bool,
+  required_tags: bool,
+  simulator: Option<Arc<dyn Simulator>>,
+  request_queue: Option<QueueConfig>,
+  rate_budget: Option<Arc<RateBudget>>,
   user_agent: String,
   secret: Vec<u8>,
   url: String,
@@ -33,9 +73,24 @@ impl Default for ClientBuilder {
   fn default() -> Self {
     Self {
       api_key: String::new(),
+      #[cfg(feature = "reqwest")]
       client: None,
+      transport: None,
       timeout: Duration::from_secs(15),
       connect_timeout: Duration::from_secs(5),
+      pool_max_idle_per_host: None,
+      pool_idle_timeout: None,
+      tcp_keepalive: None,
+      http2_prior_knowledge: false,
+      http2_keep_alive: None,
+      http2_adaptive_window: false,
+      cache: None,
+      strictness: Strictness::Lenient,
+      tag_validation: true,
+      required_tags: true,
+      simulator: None,
+      request_queue: None,
+      rate_budget: None,
       user_agent: format!("fireblocks-sdk-rs {}", env!["CARGO_PKG_VERSION"]),
       secret: vec![],
       url: String::from(FIREBLOCKS_API),
@@ -76,44 +131,301 @@ impl ClientBuilder {
     self
   }
 
+  /// Maximum idle connections kept per host, unlimited by default
+  #[allow(clippy::return_self_not_must_use)]
+  pub const fn with_pool_max_idle_per_host(mut self, max: usize) -> Self {
+    self.pool_max_idle_per_host = Some(max);
+    self
+  }
+
+  /// How long idle connections are kept in the pool, 90 seconds by default
+  #[allow(clippy::return_self_not_must_use)]
+  pub const fn with_pool_idle_timeout(mut self, timeout: Duration) -> Self {
+    self.pool_idle_timeout = Some(timeout);
+    self
+  }
+
+  /// Send TCP keep-alive probes at this interval
+  #[allow(clippy::return_self_not_must_use)]
+  pub const fn with_tcp_keepalive(mut self, interval: Duration) -> Self {
+    self.tcp_keepalive = Some(interval);
+    self
+  }
+
+  /// Skip ALPN negotiation and speak HTTP/2 right away, requests are then multiplexed over a single
+  /// connection per host. Without it HTTP/2 is still used when the server negotiates it
+  #[allow(clippy::return_self_not_must_use)]
+  pub const fn with_http2_prior_knowledge(mut self) -> Self {
+    self.http2_prior_knowledge = true;
+    self
+  }
+
+  /// Ping idle HTTP/2 connections every `interval`, closing them when no pong arrives within `timeout`
+  #[allow(clippy::return_self_not_must_use)]
+  pub const fn with_http2_keep_alive(mut self, interval: Duration, timeout: Duration) -> Self {
+    self.http2_keep_alive = Some((interval, timeout));
+    self
+  }
+
+  /// Grow HTTP/2 flow control windows with the measured bandwidth, helps large pages
+  #[allow(clippy::return_self_not_must_use)]
+  pub const fn with_http2_adaptive_window(mut self, enabled: bool) -> Self {
+    self.http2_adaptive_window = enabled;
+    self
+  }
+
+  /// Reject unknown enum values and fields in responses instead of tolerating them, see [`Strictness`]
+  #[allow(clippy::return_self_not_must_use)]
+  pub const fn with_strictness(mut self, strictness: Strictness) -> Self {
+    self.strictness = strictness;
+    self
+  }
+
+  /// Check destination tags and memos before creating transactions, on by default, see [`crate::types::tag`]
+  #[allow(clippy::return_self_not_must_use)]
+  pub const fn with_tag_validation(mut self, enabled: bool) -> Self {
+    self.tag_validation = enabled;
+    self
+  }
+
+  /// Whether one-time address transfers of tagged assets without a tag are rejected, the default, or only
+  /// logged as a warning. Some addresses of these chains, e.g. personal XRP wallets, take no tag
+  #[allow(clippy::return_self_not_must_use)]
+  pub const fn with_required_tags(mut self, required: bool) -> Self {
+    self.required_tags = required;
+    self
+  }
+
+  /// Simulate contract calls before creating them, see [`crate::simulate`]
+  #[allow(clippy::return_self_not_must_use)]
+  pub fn with_simulator(mut self, simulator: Arc<dyn Simulator>) -> Self {
+    self.simulator = Some(simulator);
+    self
+  }
+
+  /// Queue requests in priority lanes, see [`crate::queue`]
+  #[allow(clippy::return_self_not_must_use)]
+  pub const fn with_request_queue(mut self, config: QueueConfig) -> Self {
+    self.request_queue = Some(config);
+    self
+  }
+
+  /// Wait for a token of `budget` before each request, share it between clients using the same API key,
+  /// see [`crate::rate`]
+  #[allow(clippy::return_self_not_must_use)]
+  pub fn with_rate_budget(mut self, budget: Arc<RateBudget>) -> Self {
+    self.rate_budget = Some(budget);
+    self
+  }
+
+  /// Cache supported assets, blockchains and fee estimates, see [`crate::cache`]
+  #[allow(clippy::return_self_not_must_use)]
+  pub fn with_cache(mut self, config: CacheConfig) -> Self {
+    self.cache = Some(config);
+    self
+  }
+
   #[allow(clippy::return_self_not_must_use)]
   pub fn with_user_agent(mut self, ua: &str) -> Self {
     self.user_agent = String::from(ua);
     self
   }
 
+  /// Replace the RSA secret given to [`ClientBuilder::new`]
+  #[allow(clippy::return_self_not_must_use)]
+  pub fn with_secret(mut self, secret: &[u8]) -> Self {
+    self.secret = Vec::from(secret);
+    self
+  }
+
+  /// Use a preconfigured client, the timeout, pool and user agent settings of this builder are ignored
+  #[cfg(feature = "reqwest")]
   #[allow(clippy::return_self_not_must_use)]
   pub fn with_client(mut self, client: reqwest::Client) -> Self {
     self.client = Some(client);
     self
   }
 
+  /// Send requests with another HTTP stack, see [`crate::transport`]. The timeout, pool and user agent
+  /// settings of this builder and [`ClientBuilder::with_client`] are then ignored
+  #[allow(clippy::return_self_not_must_use)]
+  pub fn with_transport(mut self, transport: Arc<dyn HttpTransport>) -> Self {
+    self.transport = Some(transport);
+    self
+  }
+
   pub fn build(&self) -> Result<Client, error::ClientError> {
-    let c = match self.client.as_ref() {
-      None => reqwest::ClientBuilder::new()
-        .timeout(self.timeout)
-        .connect_timeout(self.connect_timeout)
-        .user_agent(String::from(&self.user_agent))
-        .build()?,
-      Some(cl) => cl.clone(),
+    let transport = match &self.transport {
+      Some(transport) => Arc::clone(transport),
+      None => self.default_transport()?,
     };
     let key = EncodingKey::from_rsa_pem(&self.secret[..])?;
     let signer = Signer::new(key, &self.api_key);
-    Ok(Client::new_with_url(signer, &self.url, c))
+    let mut client = Client::new_with_url(signer, &self.url, transport);
+    client.cache = self.cache.clone().map(|config| Arc::new(ResponseCache::new(config)));
+    client.strictness = self.strictness;
+    client.tag_validation = self.tag_validation;
+    client.required_tags = self.required_tags;
+    client.simulator.clone_from(&self.simulator);
+    client.queue = self.request_queue.map(|config| Arc::new(RequestQueue::new(config)));
+    client.rate_budget.clone_from(&self.rate_budget);
+    Ok(client)
+  }
+
+  #[cfg(feature = "reqwest")]
+  fn default_transport(&self) -> Result<Arc<dyn HttpTransport>, error::ClientError> {
+    if let Some(cl) = &self.client {
+      return Ok(Arc::new(ReqwestTransport::new(cl.clone())));
+    }
+    let mut builder = reqwest::ClientBuilder::new()
+      .timeout(self.timeout)
+      .connect_timeout(self.connect_timeout)
+      .user_agent(String::from(&self.user_agent));
+    if let Some(max) = self.pool_max_idle_per_host {
+      builder = builder.pool_max_idle_per_host(max);
+    }
+    if let Some(timeout) = self.pool_idle_timeout {
+      builder = builder.pool_idle_timeout(timeout);
+    }
+    if let Some(interval) = self.tcp_keepalive {
+      builder = builder.tcp_keepalive(interval);
+    }
+    if self.http2_prior_knowledge {
+      builder = builder.http2_prior_knowledge();
+    }
+    if let Some((interval, timeout)) = self.http2_keep_alive {
+      builder =
+        builder.http2_keep_alive_interval(interval).http2_keep_alive_timeout(timeout).http2_keep_alive_while_idle(true);
+    }
+    builder = builder.http2_adaptive_window(self.http2_adaptive_window);
+    Ok(Arc::new(ReqwestTransport::new(builder.build()?)))
+  }
+
+  #[cfg(not(feature = "reqwest"))]
+  #[allow(clippy::unused_self)]
+  fn default_transport(&self) -> Result<Arc<dyn HttpTransport>, error::ClientError> {
+    Err(error::ClientError::Config(String::from("no HTTP transport, enable the reqwest feature or use with_transport")))
   }
 }
 
 impl Client {
-  fn new_with_url(signer: Signer, url: &str, client: reqwest::Client) -> Self {
-    Self { signer: Arc::new(signer), client, host: url.to_owned() }
+  fn new_with_url(signer: Signer, url: &str, transport: Arc<dyn HttpTransport>) -> Self {
+    Self {
+      signer: Arc::new(RwLock::new(signer)),
+      transport,
+      host: url.to_owned(),
+      cache: None,
+      strictness: Strictness::Lenient,
+      tag_validation: true,
+      required_tags: true,
+      simulator: None,
+      queue: None,
+      rate_budget: None,
+      lifecycle: Arc::default(),
+    }
+  }
+
+  pub(crate) const fn lifecycle(&self) -> &Arc<Lifecycle> {
+    &self.lifecycle
+  }
+
+  pub(crate) const fn tag_validation(&self) -> bool {
+    self.tag_validation
+  }
+
+  pub(crate) const fn required_tags(&self) -> bool {
+    self.required_tags
   }
+
+  pub(crate) fn simulator(&self) -> Option<&dyn Simulator> {
+    self.simulator.as_deref()
+  }
+
+  /// Sign requests with a new RSA secret from now on, e.g. after the key was rotated
+  pub fn rotate_secret(&self, secret: &[u8]) -> Result<(), error::ClientError> {
+    let key = EncodingKey::from_rsa_pem(secret)?;
+    let api_key = self.signer.read().unwrap_or_else(PoisonError::into_inner).api_key().to_string();
+    *self.signer.write().unwrap_or_else(PoisonError::into_inner) = Signer::new(key, &api_key);
+    Ok(())
+  }
+
+  /// Drop all cached responses
+  pub fn clear_cache(&self) {
+    if let Some(cache) = &self.cache {
+      cache.clear();
+    }
+  }
+
+  /// Serve `fetch` from the cache when enabled, a cache hit returns the original request id
+  pub(crate) async fn cached<T, F>(&self, class: CacheClass, key: &str, fetch: F) -> crate::Result<T>
+  where
+    T: Clone + Send + Sync + 'static,
+    F: Future<Output = crate::Result<T>>,
+  {
+    let Some(cache) = &self.cache else {
+      return fetch.await;
+    };
+    if let Some(hit) = cache.get(class, key) {
+      return Ok(hit);
+    }
+    let result = fetch.await?;
+    cache.insert(class, key, result.clone());
+    Ok(result)
+  }
+}
+
+fn status_error(status: StatusCode, request_id: String, path: String, text: String) -> FireblocksError {
+  match status {
+    StatusCode::NOT_FOUND => FireblocksError::NotFound { request_id, path },
+    StatusCode::BAD_REQUEST => FireblocksError::BadRequest { request_id, path, text },
+    StatusCode::UNAUTHORIZED => FireblocksError::Unauthorized { request_id, path, text },
+    StatusCode::FORBIDDEN => FireblocksError::Forbidden { request_id, path, text },
+    StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT | StatusCode::INTERNAL_SERVER_ERROR => {
+      FireblocksError::InternalError { request_id, path, code: status.as_u16(), text }
+    },
+    _ => FireblocksError::Unknown { request_id, path, code: status.as_u16(), text },
+  }
+}
+
+fn request_id(headers: &HeaderMap) -> String {
+  headers.get("x-request-id").and_then(|value| value.to_str().ok()).unwrap_or_default().to_string()
+}
+
+fn header_value(value: &str) -> Result<HeaderValue, FireblocksError> {
+  let mut value = HeaderValue::from_str(value).map_err(|e| FireblocksError::Transport(Box::new(e)))?;
+  value.set_sensitive(true);
+  Ok(value)
 }
 
 // This impl block contains the underlying GET/POST helpers for authing to fireblocks
 impl Client {
+  pub(crate) async fn send<T, S>(&self, method: Method, url: Url, body: Option<&S>) -> crate::Result<T>
+  where
+    T: DeserializeOwned + Default,
+    S: Serialize + ?Sized + Debug + Send + Sync,
+  {
+    let ((t, _), request_id) = self.send_with_headers(method, url, body).await?;
+    Ok((t, request_id))
+  }
+
+  /// Like [`Client::send`], also returning the response headers
+  pub(crate) async fn send_with_headers<T, S>(
+    &self,
+    method: Method,
+    url: Url,
+    body: Option<&S>,
+  ) -> crate::Result<(T, HeaderMap)>
+  where
+    T: DeserializeOwned + Default,
+    S: Serialize + ?Sized + Debug + Send + Sync,
+  {
+    let flight = self.lifecycle.enter(url.path())?;
+    flight.run(Box::pin(self.dispatch(method, url, body))).await
+  }
+
   #[allow(clippy::option_if_let_else)]
   #[tracing::instrument(skip(self, url, body), fields(path))]
-  pub(crate) async fn send<T, S>(&self, method: Method, url: Url, body: Option<&S>) -> crate::Result<T>
+  async fn dispatch<T, S>(&self, method: Method, url: Url, body: Option<&S>) -> crate::Result<(T, HeaderMap)>
   where
     T: DeserializeOwned + Default,
     S: Serialize + ?Sized + Debug + Send + Sync,
@@ -135,25 +447,24 @@ impl Client {
       },
     }
 
-    let req = match method {
-      Method::GET => self.client.get(url),
-      Method::POST => self.client.post(url),
-      Method::DELETE => self.client.delete(url),
-      Method::PATCH => self.client.patch(url),
-      Method::PUT => self.client.put(url),
-      _ => todo!(),
+    // the token expires, so wait for the queue and the rate budget before signing
+    let _permit = self.permit(Priority::Normal).await;
+    self.spend_budget().await;
+    let mut headers = self.authed(&path, body)?.0;
+    let body = match body {
+      None => None,
+      Some(b) => {
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        Some(serde_json::to_vec(b).map_err(JwtError::from)?)
+      },
     };
-    let mut req = self.authed(&path, req, body)?.0;
-    if let Some(b) = body {
-      req = req.json(b);
-    }
-
-    let resp = req.send().await?;
-    let status = resp.status();
-    let request_id =
-      resp.headers().get("x-request-id").and_then(|value| value.to_str().ok()).unwrap_or_default().to_string();
-    let json_response = resp
-      .headers()
+    let req = HttpRequest { method, url, headers, body };
+    let resp = self.transport.send(req).await?;
+    let status = resp.status;
+    let headers = resp.headers.clone();
+    self.record(status, &headers);
+    let request_id = request_id(&headers);
+    let json_response = headers
       .get("content-type")
       .and_then(|value| value.to_str().ok())
       .unwrap_or_default()
@@ -161,32 +472,82 @@ impl Client {
       .contains("json");
     #[cfg(debug_assertions)]
     debug!("got response with x-request-id={}", request_id);
-    let text = resp.text().await?;
+    let text = resp.bytes().await?;
 
-    let r: crate::Result<T> = match status {
+    let r: crate::Result<(T, HeaderMap)> = match status {
       StatusCode::OK | StatusCode::ACCEPTED | StatusCode::CREATED => {
         if text.is_empty() || !json_response {
-          Ok((T::default(), request_id))
+          Ok(((T::default(), headers), request_id))
         } else {
-          //debug!("body: {text}");
-          match serde_json::from_str::<T>(&text) {
-            Ok(deserialized) => Ok((deserialized, request_id)),
-            Err(err) => Err(FireblocksError::SerdeJson { request_id, err, text }),
-          }
+          let deserialized = FireblocksError::decode::<T>(&text, &request_id, self.strictness)?;
+          Ok(((deserialized, headers), request_id))
         }
       },
-      StatusCode::NOT_FOUND => Err(FireblocksError::NotFound { request_id, path }),
-      StatusCode::BAD_REQUEST => Err(FireblocksError::BadRequest { request_id, path, text }),
-      StatusCode::UNAUTHORIZED => Err(FireblocksError::Unauthorized { request_id, path, text }),
-      StatusCode::FORBIDDEN => Err(FireblocksError::Forbidden { request_id, path, text }),
-      StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT | StatusCode::INTERNAL_SERVER_ERROR => {
-        Err(FireblocksError::InternalError { request_id, path, code: status.as_u16(), text })
-      },
-      _ => Err(FireblocksError::Unknown { request_id, path, code: status.as_u16(), text }),
+      _ => Err(status_error(status, request_id, path, String::from_utf8_lossy(&text).to_string())),
     };
     r
   }
 
+  /// GET a JSON array, or the array under `field` of a JSON object, deserializing its items as the
+  /// body arrives
+  pub(crate) async fn get_items<T>(&self, url: Url, field: Option<&str>) -> crate::Result<ItemStream<T>>
+  where
+    T: DeserializeOwned + Send + 'static,
+  {
+    let flight = self.lifecycle.enter(url.path())?;
+    let (stream, request_id) = flight.run(self.open_items(url, field)).await?;
+    Ok((flight.guard(stream), request_id))
+  }
+
+  #[tracing::instrument(skip(self, url), fields(path))]
+  async fn open_items<T>(&self, url: Url, field: Option<&str>) -> crate::Result<ItemStream<T>>
+  where
+    T: DeserializeOwned + Send + 'static,
+  {
+    let mut path = String::from(url.path());
+    if let Some(q) = url.query() {
+      path = format!("{path}?{q}");
+    }
+    tracing::Span::current().record("path", &path);
+    debug!("streaming request GET {path}");
+    let permit = self.permit(Priority::Bulk).await;
+    self.spend_budget().await;
+    let headers = self.authed::<()>(&path, None)?.0;
+    let req = HttpRequest { method: Method::GET, url, headers, body: None };
+    let resp = self.transport.send(req).await?;
+    drop(permit);
+    let status = resp.status;
+    self.record(status, &resp.headers);
+    let request_id = request_id(&resp.headers);
+    if status.is_success() {
+      return Ok((item_stream(resp.body, field, request_id.clone(), self.strictness), request_id));
+    }
+    let text = resp.bytes().await?;
+    Err(status_error(status, request_id, path, String::from_utf8_lossy(&text).to_string()))
+  }
+
+  /// A slot of the request queue when enabled, at the priority chosen by the caller or `default`
+  async fn permit(&self, default: Priority) -> Option<crate::queue::Permit> {
+    match &self.queue {
+      Some(queue) => Some(queue.acquire(current_priority(default)).await),
+      None => None,
+    }
+  }
+
+  async fn spend_budget(&self) {
+    if let Some(budget) = &self.rate_budget {
+      budget.acquire().await;
+    }
+  }
+
+  /// Record the response for [`crate::meta::capture`] and the rate budget
+  fn record(&self, status: StatusCode, headers: &HeaderMap) {
+    crate::meta::record(status, headers);
+    if let Some(budget) = &self.rate_budget {
+      budget.observe(&crate::meta::RateLimit::from_headers(headers));
+    }
+  }
+
   pub(crate) fn build_url(&self, path: impl AsRef<str> + Display) -> crate::Result<Url> {
     self.build_url_params::<Vec<(&str, &str)>, &str, &str>(path, None)
   }
@@ -217,6 +578,13 @@ impl Client {
     self.send(Method::GET, url, None as Option<&()>).await
   }
 
+  pub(crate) async fn get_with_headers<R: DeserializeOwned + Default>(
+    &self,
+    url: Url,
+  ) -> crate::Result<(R, HeaderMap)> {
+    self.send_with_headers(Method::GET, url, None as Option<&()>).await
+  }
+
   pub(crate) async fn delete<R: DeserializeOwned + Default>(&self, url: Url) -> crate::Result<R> {
     self.send(Method::DELETE, url, None as Option<&()>).await
   }
@@ -237,18 +605,76 @@ impl Client {
     self.send(Method::PUT, url, body).await
   }
 
-  pub(crate) fn authed<S>(&self, url: &str, req: RequestBuilder, body: Option<&S>) -> crate::Result<RequestBuilder>
+  pub(crate) async fn patch<R, S>(&self, url: Url, body: Option<&S>) -> crate::Result<R>
   where
+    R: DeserializeOwned + Default,
     S: Serialize + ?Sized + Debug + Send + Sync,
   {
-    let jwt = self.signer.sign(url, body)?;
-    Ok((req.header("X-API-Key", self.signer.api_key()).bearer_auth(jwt), String::new()))
+    self.send(Method::PATCH, url, body).await
+  }
+
+  /// `X-API-Key` and the signed JWT of a request
+  pub(crate) fn authed<S>(&self, url: &str, body: Option<&S>) -> crate::Result<HeaderMap>
+  where
+    S: Serialize + ?Sized + Debug + Send + Sync,
+  {
+    let signer = self.signer.read().unwrap_or_else(PoisonError::into_inner).clone();
+    let jwt = signer.sign(url, body)?;
+    let mut headers = HeaderMap::new();
+    headers.insert("X-API-Key", header_value(signer.api_key())?);
+    headers.insert(AUTHORIZATION, header_value(&format!("Bearer {jwt}"))?);
+    Ok((headers, String::new()))
   }
 }
 
 mod tests {
   #[test]
   fn client_builder() {
+    #[cfg(feature = "reqwest")]
     super::ClientBuilder::new("", b"secret").with_client(reqwest::ClientBuilder::new().build().expect("oh no"));
+    super::ClientBuilder::new("", b"secret")
+      .with_pool_max_idle_per_host(32)
+      .with_pool_idle_timeout(std::time::Duration::from_secs(30))
+      .with_tcp_keepalive(std::time::Duration::from_secs(15))
+      .with_http2_prior_knowledge()
+      .with_http2_keep_alive(std::time::Duration::from_secs(30), std::time::Duration::from_secs(10))
+      .with_http2_adaptive_window(true)
+      .with_request_queue(crate::queue::QueueConfig::new(8).with_reserved(2))
+      .with_rate_budget(std::sync::Arc::new(crate::rate::RateBudget::new(20, std::time::Duration::from_secs(1))));
+  }
+
+  #[tokio::test(start_paused = true)]
+  async fn test_sign_after_budget() -> color_eyre::Result<()> {
+    use crate::mock::{self, MockTransport};
+    use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+    use std::time::Duration;
+
+    // Fireblocks reports the limit spent for longer than a token lives
+    let budget = std::sync::Arc::new(crate::rate::RateBudget::new(20, Duration::from_secs(1)));
+    let reset = Some(Duration::from_mins(2));
+    budget.observe(&crate::meta::RateLimit { limit: Some(20), remaining: Some(0), reset });
+    let transport = MockTransport::new(|_| mock::json(http::StatusCode::OK, ""));
+    let client = mock::builder(transport.clone())?.with_rate_budget(budget).build()?;
+
+    // the key rotates while the request waits, so a token signed up front carries the old key
+    let start = tokio::time::Instant::now();
+    let rotated = std::fs::read(concat!(env!("CARGO_MANIFEST_DIR"), "/src/webhook/testdata/rotated_private.pem"))?;
+    let rotate = async {
+      tokio::time::sleep(Duration::from_mins(1)).await;
+      client.rotate_secret(&rotated)
+    };
+    let (sent, rotate) = tokio::join!(client.vault(0), rotate);
+    sent?;
+    rotate?;
+    assert!(start.elapsed() >= Duration::from_mins(2));
+
+    let request = transport.requests().remove(0);
+    let token = request.headers[http::header::AUTHORIZATION].to_str()?.trim_start_matches("Bearer ").to_string();
+    let old = DecodingKey::from_rsa_pem(include_bytes!("webhook/testdata/webhook_public.pem"))?;
+    let mut validation = Validation::new(Algorithm::RS256);
+    validation.validate_exp = false;
+    validation.required_spec_claims.clear();
+    assert!(jsonwebtoken::decode::<serde_json::Value>(&token, &old, &validation).is_err());
+    Ok(())
   }
 }
@@ -0,0 +1,174 @@
+//! Client configuration from environment variables or a TOML file
+//!
+//! | Variable                          | TOML key               |                                   |
+//! |-----------------------------------|------------------------|-----------------------------------|
+//! | `FIREBLOCKS_API_KEY`              | `api_key`              | required                          |
+//! | `FIREBLOCKS_SECRET`               | `secret`               | PEM of the API user's RSA key     |
+//! | `FIREBLOCKS_SECRET_PATH`          | `secret_path`          | file with the PEM                 |
+//! | `FIREBLOCKS_ENV`                  | `environment`          | `production` (default), `sandbox` |
+//! | `FIREBLOCKS_API_URL`              | `url`                  | overrides `environment`           |
+//! | `FIREBLOCKS_TIMEOUT_SECS`         | `timeout_secs`         |                                   |
+//! | `FIREBLOCKS_CONNECT_TIMEOUT_SECS` | `connect_timeout_secs` |                                   |
+//! | `FIREBLOCKS_USER_AGENT`           | `user_agent`           |                                   |
+//! | `FIREBLOCKS_STRICT`               | `strict`               | `true` rejects unknown values     |
+//!
+//! ```toml
+//! api_key = "00000000-0000-0000-0000-000000000000"
+//! secret_path = "/run/secrets/fireblocks.key"
+//! environment = "sandbox"
+//! timeout_secs = 30
+//! ```
+use crate::error::ClientError;
+use crate::{Client, ClientBuilder, Strictness, FIREBLOCKS_API, FIREBLOCKS_SANDBOX_API};
+use serde_derive::Deserialize;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Environment {
+  #[default]
+  Production,
+  Sandbox,
+}
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ClientConfig {
+  pub api_key: String,
+  pub secret: Option<String>,
+  pub secret_path: Option<PathBuf>,
+  #[serde(default)]
+  pub environment: Environment,
+  pub url: Option<String>,
+  pub timeout_secs: Option<u64>,
+  pub connect_timeout_secs: Option<u64>,
+  pub user_agent: Option<String>,
+  /// See [`crate::Strictness`]
+  #[serde(default)]
+  pub strict: bool,
+}
+
+impl std::fmt::Debug for ClientConfig {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("ClientConfig")
+      .field("api_key", &self.api_key)
+      .field("secret_path", &self.secret_path)
+      .field("environment", &self.environment)
+      .field("url", &self.url)
+      .finish_non_exhaustive()
+  }
+}
+
+impl ClientConfig {
+  pub fn from_env() -> Result<Self, ClientError> {
+    Self::from_lookup(|name| std::env::var(name).ok())
+  }
+
+  pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ClientError> {
+    Ok(toml::from_str(&std::fs::read_to_string(path)?)?)
+  }
+
+  fn from_lookup(var: impl Fn(&str) -> Option<String>) -> Result<Self, ClientError> {
+    let secs = |name: &str| -> Result<Option<u64>, ClientError> {
+      var(name).map(|v| v.parse().map_err(|_| ClientError::Config(format!("{name} is not a number")))).transpose()
+    };
+    let environment = match var("FIREBLOCKS_ENV").as_deref() {
+      None | Some("production") => Environment::Production,
+      Some("sandbox") => Environment::Sandbox,
+      Some(other) => return Err(ClientError::Config(format!("unknown FIREBLOCKS_ENV {other}"))),
+    };
+    Ok(Self {
+      api_key: var("FIREBLOCKS_API_KEY").ok_or_else(|| ClientError::Config("FIREBLOCKS_API_KEY is not set".into()))?,
+      secret: var("FIREBLOCKS_SECRET"),
+      secret_path: var("FIREBLOCKS_SECRET_PATH").map(PathBuf::from),
+      environment,
+      url: var("FIREBLOCKS_API_URL"),
+      timeout_secs: secs("FIREBLOCKS_TIMEOUT_SECS")?,
+      connect_timeout_secs: secs("FIREBLOCKS_CONNECT_TIMEOUT_SECS")?,
+      user_agent: var("FIREBLOCKS_USER_AGENT"),
+      strict: var("FIREBLOCKS_STRICT").is_some_and(|v| v == "true" || v == "1"),
+    })
+  }
+
+  /// A builder with the settings applied, reading the secret file if needed
+  pub fn builder(&self) -> Result<ClientBuilder, ClientError> {
+    let secret = match (&self.secret, &self.secret_path) {
+      (Some(pem), _) => pem.as_bytes().to_vec(),
+      (None, Some(path)) => std::fs::read(path)?,
+      (None, None) => return Err(ClientError::Config("one of secret or secret_path is required".into())),
+    };
+    let url = self.url.as_deref().unwrap_or(match self.environment {
+      Environment::Production => FIREBLOCKS_API,
+      Environment::Sandbox => FIREBLOCKS_SANDBOX_API,
+    });
+    let mut builder = ClientBuilder::new(&self.api_key, &secret).with_url(url);
+    if let Some(secs) = self.timeout_secs {
+      builder = builder.with_timeout(Duration::from_secs(secs));
+    }
+    if let Some(secs) = self.connect_timeout_secs {
+      builder = builder.with_connect_timeout(Duration::from_secs(secs));
+    }
+    if let Some(ua) = &self.user_agent {
+      builder = builder.with_user_agent(ua);
+    }
+    if self.strict {
+      builder = builder.with_strictness(Strictness::Strict);
+    }
+    Ok(builder)
+  }
+}
+
+impl Client {
+  /// See [`crate::config`] for the variables
+  pub fn from_env() -> Result<Self, ClientError> {
+    ClientConfig::from_env()?.builder()?.build()
+  }
+
+  /// See [`crate::config`] for the keys
+  pub fn from_config(path: impl AsRef<Path>) -> Result<Self, ClientError> {
+    ClientConfig::from_file(path)?.builder()?.build()
+  }
+}
+
+#[cfg(all(test, feature = "reqwest"))]
+mod test {
+  use super::*;
+  use crate::mock::SECRET_PATH;
+  use std::collections::HashMap;
+
+  #[test]
+  fn test_from_lookup() -> color_eyre::Result<()> {
+    let vars = HashMap::from([
+      ("FIREBLOCKS_API_KEY", "key"),
+      ("FIREBLOCKS_SECRET_PATH", SECRET_PATH),
+      ("FIREBLOCKS_ENV", "sandbox"),
+      ("FIREBLOCKS_TIMEOUT_SECS", "30"),
+      ("FIREBLOCKS_STRICT", "true"),
+    ]);
+    let config = ClientConfig::from_lookup(|name| vars.get(name).map(ToString::to_string))?;
+    assert_eq!(config.environment, Environment::Sandbox);
+    assert_eq!(config.timeout_secs, Some(30));
+    assert!(config.strict);
+    config.builder()?.build()?;
+
+    assert!(ClientConfig::from_lookup(|_| None).is_err());
+    let bad = ClientConfig::from_lookup(|name| {
+      (name == "FIREBLOCKS_API_KEY" || name == "FIREBLOCKS_TIMEOUT_SECS").then(|| "x".into())
+    });
+    assert!(matches!(bad, Err(ClientError::Config(_))));
+    Ok(())
+  }
+
+  #[test]
+  fn test_from_file() -> color_eyre::Result<()> {
+    let path = std::env::temp_dir().join(format!("fireblocks-config-{}.toml", std::process::id()));
+    std::fs::write(&path, format!("api_key = \"key\"\nsecret_path = \"{SECRET_PATH}\"\nconnect_timeout_secs = 2\n"))?;
+    let config = ClientConfig::from_file(&path);
+    let client = Client::from_config(&path);
+    std::fs::remove_file(&path)?;
+    assert_eq!(config?.environment, Environment::Production);
+    client?;
+    Ok(())
+  }
+}
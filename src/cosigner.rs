@@ -0,0 +1,207 @@
+//! Helpers for writing an API co-signer callback handler
+//!
+//! The co-signer posts a JWT, signed with its private key, for every transaction (`/v2/tx_sign_request`)
+//! and configuration change (`/v2/config_change_sign_request`) it is about to sign. The handler answers
+//! with a JWT signed by the key whose public half was configured on the co-signer.
+//!
+//! ```
+//! use fireblocks_sdk::cosigner::{CallbackAction, CosignerCallback, TxSignRequest};
+//!
+//! fn handle(callback: &CosignerCallback, body: &str) -> color_eyre::Result<String> {
+//!   let request: TxSignRequest = callback.parse(body)?;
+//!   if request.asset.as_deref() == Some("BTC") {
+//!     return Ok(callback.reject(&request.request_id, "BTC is not allowed")?);
+//!   }
+//!   Ok(callback.approve(&request.request_id)?)
+//! }
+//! ```
+//!
+//! See
+//! * [Callback handler](https://developers.fireblocks.com/reference/callback-handler)
+use crate::types::TransactionOperation;
+use bigdecimal::BigDecimal;
+use jsonwebtoken::errors::Error as JwtError;
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use serde::de::DeserializeOwned;
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum CallbackAction {
+  APPROVE,
+  REJECT,
+  /// Ask the co-signer to send the request again later
+  RETRY,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CallbackRequester {
+  pub user_id: Option<String>,
+  pub user_role: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CallbackDestination {
+  #[serde(rename = "amountStr")]
+  pub amount: Option<BigDecimal>,
+  pub dest_type: Option<String>,
+  pub dest_id: Option<String>,
+  pub dest_address: Option<String>,
+  pub dest_address_type: Option<String>,
+}
+
+/// A payload the co-signer is asked to sign
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CallbackRawTx {
+  #[serde(default)]
+  pub key_derivation_path: Vec<u32>,
+  /// Hex encoded
+  pub raw_tx: Option<String>,
+  pub payload: Option<String>,
+}
+
+/// Body of `/v2/tx_sign_request`
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TxSignRequest {
+  pub request_id: String,
+  pub tx_id: String,
+  pub operation: Option<TransactionOperation>,
+  pub source_type: Option<String>,
+  pub source_id: Option<String>,
+  pub dest_type: Option<String>,
+  pub dest_id: Option<String>,
+  pub asset: Option<String>,
+  #[serde(rename = "amountStr")]
+  pub amount: Option<BigDecimal>,
+  #[serde(rename = "requestedAmountStr")]
+  pub requested_amount: Option<BigDecimal>,
+  #[serde(rename = "feeStr")]
+  pub fee: Option<BigDecimal>,
+  pub dest_address_type: Option<String>,
+  pub dest_address: Option<String>,
+  #[serde(default)]
+  pub destinations: Vec<CallbackDestination>,
+  pub note: Option<String>,
+  /// Contract call or raw message data, see [`crate::types::ExtraParameters`]
+  pub extra_parameters: Option<Value>,
+  #[serde(default)]
+  pub raw_tx: Vec<CallbackRawTx>,
+  #[serde(default)]
+  pub players: Vec<String>,
+  pub requester: Option<CallbackRequester>,
+}
+
+/// Body of `/v2/config_change_sign_request`
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigChangeSignRequest {
+  pub request_id: String,
+  #[serde(rename = "type")]
+  pub change_type: String,
+  pub extra_info: Option<Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CallbackResponse {
+  pub action: CallbackAction,
+  pub request_id: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub rejection_reason: Option<String>,
+}
+
+/// Verifies co-signer requests and signs the responses
+#[derive(Clone)]
+pub struct CosignerCallback {
+  cosigner_key: DecodingKey,
+  handler_key: EncodingKey,
+}
+
+impl std::fmt::Debug for CosignerCallback {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("CosignerCallback").finish_non_exhaustive()
+  }
+}
+
+impl CosignerCallback {
+  /// `cosigner_public_key` is printed by the co-signer during setup, `handler_private_key` is the key
+  /// of the callback handler
+  pub fn new(cosigner_public_key: &[u8], handler_private_key: &[u8]) -> Result<Self, JwtError> {
+    Ok(Self {
+      cosigner_key: DecodingKey::from_rsa_pem(cosigner_public_key)?,
+      handler_key: EncodingKey::from_rsa_pem(handler_private_key)?,
+    })
+  }
+
+  /// Verify the raw request body and deserialize it, usually into [`TxSignRequest`] or
+  /// [`ConfigChangeSignRequest`]
+  pub fn parse<T: DeserializeOwned>(&self, body: &str) -> Result<T, JwtError> {
+    let mut validation = Validation::new(Algorithm::RS256);
+    validation.required_spec_claims.clear();
+    validation.validate_exp = false;
+    Ok(jsonwebtoken::decode(body.trim(), &self.cosigner_key, &validation)?.claims)
+  }
+
+  /// The signed response body
+  pub fn respond(&self, response: &CallbackResponse) -> Result<String, JwtError> {
+    jsonwebtoken::encode(&Header::new(Algorithm::RS256), response, &self.handler_key)
+  }
+
+  pub fn approve(&self, request_id: &str) -> Result<String, JwtError> {
+    self.respond(&CallbackResponse {
+      action: CallbackAction::APPROVE,
+      request_id: request_id.to_string(),
+      rejection_reason: None,
+    })
+  }
+
+  pub fn reject(&self, request_id: &str, reason: &str) -> Result<String, JwtError> {
+    self.respond(&CallbackResponse {
+      action: CallbackAction::REJECT,
+      request_id: request_id.to_string(),
+      rejection_reason: Some(reason.to_string()),
+    })
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  const PUBLIC_KEY: &[u8] = include_bytes!("webhook/testdata/webhook_public.pem");
+  const PRIVATE_KEY: &[u8] = include_bytes!("webhook/testdata/webhook_private.pem");
+
+  #[test]
+  fn test_callback() -> color_eyre::Result<()> {
+    // the same key pair plays both the co-signer and the handler
+    let callback = CosignerCallback::new(PUBLIC_KEY, PRIVATE_KEY)?;
+    let claims = serde_json::json!({
+      "requestId": "req-1",
+      "txId": "tx-1",
+      "operation": "TRANSFER",
+      "asset": "ETH",
+      "amountStr": "1.5",
+      "rawTx": [{"keyDerivationPath": [44, 60, 0, 0, 0], "rawTx": "deadbeef"}],
+      "players": ["p1"],
+    });
+    let body = jsonwebtoken::encode(&Header::new(Algorithm::RS256), &claims, &EncodingKey::from_rsa_pem(PRIVATE_KEY)?)?;
+    let request: TxSignRequest = callback.parse(&body)?;
+    assert_eq!(request.tx_id, "tx-1");
+    assert_eq!(request.amount, Some(BigDecimal::new(15.into(), 1)));
+    assert_eq!(request.raw_tx[0].key_derivation_path.len(), 5);
+
+    let response = callback.reject(&request.request_id, "denied")?;
+    let response: CallbackResponse = callback.parse(&response)?;
+    assert_eq!(response.action, CallbackAction::REJECT);
+    assert_eq!(response.rejection_reason.as_deref(), Some("denied"));
+
+    assert!(callback.parse::<TxSignRequest>(&format!("{body}x")).is_err());
+    Ok(())
+  }
+}
@@ -0,0 +1,55 @@
+//! Minimal RFC 4180 style CSV helpers used by the import/export utilities
+
+/// Split a CSV line into fields, honouring double quoted fields and `""` escapes
+pub fn parse_line(line: &str) -> Vec<String> {
+  let mut fields = Vec::new();
+  let mut field = String::new();
+  let mut quoted = false;
+  let mut chars = line.trim_end_matches(['\r', '\n']).chars().peekable();
+  while let Some(c) = chars.next() {
+    match (c, quoted) {
+      ('"', true) if chars.peek() == Some(&'"') => {
+        field.push('"');
+        chars.next();
+      },
+      ('"', true) => quoted = false,
+      ('"', false) if field.is_empty() => quoted = true,
+      (',', false) => fields.push(std::mem::take(&mut field).trim().to_string()),
+      _ => field.push(c),
+    }
+  }
+  fields.push(field.trim().to_string());
+  fields
+}
+
+/// Quote a field if it contains a delimiter, quote or newline
+pub fn escape(field: &str) -> String {
+  if field.contains([',', '"', '\n', '\r']) {
+    format!("\"{}\"", field.replace('"', "\"\""))
+  } else {
+    String::from(field)
+  }
+}
+
+/// Join fields into a CSV line (without the trailing newline)
+pub fn line<I, S>(fields: I) -> String
+where
+  I: IntoIterator<Item = S>,
+  S: AsRef<str>,
+{
+  fields.into_iter().map(|f| escape(f.as_ref())).collect::<Vec<_>>().join(",")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn round_trip() {
+    let fields = vec!["plain", "with,comma", "with \"quote\"", ""];
+    let l = line(&fields);
+    assert_eq!("plain,\"with,comma\",\"with \"\"quote\"\"\",", l);
+    assert_eq!(fields, parse_line(&l));
+    assert_eq!(vec!["a", "b"], parse_line(" a , b \r\n"));
+  }
+}
@@ -0,0 +1,194 @@
+//! An overall time limit and retry budget for an operation spanning many calls
+//!
+//! [`with_deadline`] sets a [`Deadline`] for every call a future makes, including the pages fetched by streams
+//! polled inside it. A call still waiting for the request queue, the rate budget or the response when the
+//! deadline passes fails with [`FireblocksError::DeadlineExceeded`](crate::FireblocksError::DeadlineExceeded),
+//! [`Client::poll_transaction`](crate::Client::poll_transaction) stops polling, and retry loops, e.g. the
+//! [`Scheduler`](crate::scheduler::Scheduler), stop retrying when the backoff would pass the deadline or the
+//! retries of [`Deadline::with_retries`] are spent. The budget is shared by every layer, so nested retries do not
+//! multiply. A deadline set inside another one cannot extend it, nor its retries: a retry is taken from both.
+//!
+//! ```
+//! use fireblocks_sdk::deadline::{with_deadline, Deadline};
+//! use fireblocks_sdk::Client;
+//! use std::time::Duration;
+//!
+//! async fn settle(c: &Client, id: &str) -> color_eyre::Result<()> {
+//!   let deadline = Deadline::after(Duration::from_secs(60)).with_retries(3);
+//!   let (tx, _) = with_deadline(deadline, c.poll_transaction(id, Duration::MAX, Duration::from_secs(2), |_| {})).await?;
+//!   println!("{:?}", tx.status);
+//!   Ok(())
+//! }
+//! ```
+use std::future::Future;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+
+tokio::task_local! {
+  static DEADLINE: Deadline;
+}
+
+#[derive(Debug, Clone)]
+pub struct Deadline {
+  at: Instant,
+  /// Retries left in this scope and the enclosing ones, shared by the clones of the deadline. Empty when unlimited
+  retries: Vec<Arc<AtomicU32>>,
+}
+
+impl Deadline {
+  pub fn after(timeout: Duration) -> Self {
+    Self::at(Instant::now() + timeout)
+  }
+
+  pub const fn at(at: Instant) -> Self {
+    Self { at, retries: Vec::new() }
+  }
+
+  /// Allow at most `retries` retries in total, across all layers. Unlimited by default
+  #[must_use]
+  pub fn with_retries(mut self, retries: u32) -> Self {
+    self.retries = vec![Arc::new(AtomicU32::new(retries))];
+    self
+  }
+
+  pub const fn instant(&self) -> Instant {
+    self.at
+  }
+
+  pub fn remaining(&self) -> Duration {
+    self.at.saturating_duration_since(Instant::now())
+  }
+
+  pub fn is_expired(&self) -> bool {
+    Instant::now() >= self.at
+  }
+
+  /// `None` when retries are unlimited
+  pub fn retries_left(&self) -> Option<u32> {
+    self.retries.iter().map(|r| r.load(Ordering::SeqCst)).min()
+  }
+
+  /// Take a retry to run after waiting `backoff`, `false` when the retries are spent or the deadline passes
+  /// before then
+  pub fn try_retry(&self, backoff: Duration) -> bool {
+    if self.remaining() <= backoff {
+      return false;
+    }
+    for (n, budget) in self.retries.iter().enumerate() {
+      if budget.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1)).is_err() {
+        // give back what the other scopes were charged
+        self.retries[..n].iter().for_each(|r| _ = r.fetch_add(1, Ordering::SeqCst));
+        return false;
+      }
+    }
+    true
+  }
+}
+
+/// Run `call` under `deadline`, or the deadline of the caller if that passes first
+pub async fn with_deadline<F: Future>(deadline: Deadline, call: F) -> F::Output {
+  let deadline = match current() {
+    Some(outer) => {
+      let mut retries = deadline.retries;
+      for budget in outer.retries {
+        if !retries.iter().any(|r| Arc::ptr_eq(r, &budget)) {
+          retries.push(budget);
+        }
+      }
+      Deadline { at: deadline.at.min(outer.at), retries }
+    },
+    None => deadline,
+  };
+  DEADLINE.scope(deadline, call).await
+}
+
+/// The deadline set with [`with_deadline`]
+pub fn current() -> Option<Deadline> {
+  DEADLINE.try_with(Clone::clone).ok()
+}
+
+/// Whether waiting `wait` leaves time before the current deadline
+pub(crate) fn fits(wait: Duration) -> bool {
+  current().is_none_or(|d| d.remaining() > wait)
+}
+
+/// Whether a retry after `backoff` fits the current deadline, taking it from its budget
+pub(crate) fn retry(backoff: Duration) -> bool {
+  current().is_none_or(|d| d.try_retry(backoff))
+}
+
+/// Resolves when `deadline` passes, never without one
+pub(crate) async fn expired(deadline: Option<&Deadline>) {
+  match deadline {
+    Some(d) => tokio::time::sleep_until(d.at).await,
+    None => std::future::pending().await,
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::mock::{self, MockTransport};
+  use http::StatusCode;
+
+  #[tokio::test]
+  async fn test_nested() {
+    assert!(current().is_none());
+    assert!(retry(Duration::from_hours(1)));
+    let outer = Deadline::after(Duration::from_secs(10)).with_retries(2);
+    let at = outer.instant();
+    with_deadline(outer, async {
+      with_deadline(Deadline::after(Duration::from_mins(1)), async {
+        let deadline = current().expect("deadline");
+        assert_eq!(deadline.instant(), at);
+        assert!(retry(Duration::from_secs(1)));
+        assert!(!retry(Duration::from_secs(11)));
+        assert!(!fits(Duration::from_secs(11)));
+        assert_eq!(deadline.retries_left(), Some(1));
+      })
+      .await;
+      assert!(retry(Duration::ZERO));
+      assert!(!retry(Duration::ZERO));
+    })
+    .await;
+  }
+
+  #[tokio::test]
+  async fn test_poll_retries() -> color_eyre::Result<()> {
+    let down = MockTransport::new(|_| mock::json(StatusCode::BAD_REQUEST, r#"{"message": "unavailable"}"#));
+    let client = mock::client(down.clone())?;
+    let deadline = Deadline::after(Duration::from_mins(1)).with_retries(2);
+    let poll = client.poll_transaction("tx", Duration::MAX, Duration::from_millis(1), |_| {});
+    assert!(with_deadline(deadline, poll).await.is_err());
+    assert_eq!(down.requests().len(), 3);
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn test_nested_retries() {
+    let outer = Deadline::after(Duration::from_mins(1)).with_retries(2);
+    with_deadline(outer.clone(), async {
+      // the smaller budget wins, whichever scope sets it
+      with_deadline(Deadline::after(Duration::from_mins(1)).with_retries(5), async {
+        assert_eq!(current().and_then(|d| d.retries_left()), Some(2));
+        assert!(retry(Duration::ZERO));
+      })
+      .await;
+      assert_eq!(outer.retries_left(), Some(1));
+      with_deadline(Deadline::after(Duration::from_mins(1)).with_retries(0), async {
+        assert!(!retry(Duration::ZERO));
+      })
+      .await;
+      // a refused retry is not charged to the outer budget
+      assert_eq!(outer.retries_left(), Some(1));
+      with_deadline(current().expect("deadline"), async {
+        assert!(retry(Duration::ZERO));
+        assert!(!retry(Duration::ZERO));
+      })
+      .await;
+    })
+    .await;
+  }
+}
@@ -0,0 +1,134 @@
+//! Decimals of every asset, to convert between base units and display amounts
+//!
+//! Built from [`Client::supported_assets`], which is cached when the client has a
+//! [`CacheConfig`](crate::cache::CacheConfig).
+//!
+//! ```
+//! use bigdecimal::BigDecimal;
+//! use fireblocks_sdk::Client;
+//! use std::str::FromStr;
+//!
+//! async fn amounts(c: &Client) -> color_eyre::Result<()> {
+//!   let (decimals, _) = c.asset_decimals().await?;
+//!   let wei = decimals.to_base_units("ETH", &BigDecimal::from_str("0.5")?)?;
+//!   assert_eq!(wei.to_string(), "500000000000000000");
+//!   assert_eq!(decimals.from_base_units("ETH", &wei)?, BigDecimal::from_str("0.5")?);
+//!   println!("{} BTC", decimals.format("BTC", &BigDecimal::from_str("0.123456789")?)?);
+//!   Ok(())
+//! }
+//! ```
+use crate::error::ParamError;
+use crate::types::SupportedAsset;
+use crate::Client;
+use bigdecimal::num_bigint::BigInt;
+use bigdecimal::{BigDecimal, RoundingMode};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Default)]
+pub struct AssetDecimals {
+  /// By upper case asset id
+  decimals: HashMap<String, u32>,
+}
+
+impl AssetDecimals {
+  pub fn new<'a>(assets: impl IntoIterator<Item = &'a SupportedAsset>) -> Self {
+    let decimals = assets
+      .into_iter()
+      .filter_map(|asset| Some((asset.id.as_ref().to_uppercase(), u32::try_from(asset.decimals).ok()?)))
+      .collect();
+    Self { decimals }
+  }
+
+  /// Add or replace the decimals of an asset, e.g. one Fireblocks does not list yet
+  pub fn insert(&mut self, asset_id: &str, decimals: u32) {
+    self.decimals.insert(asset_id.to_uppercase(), decimals);
+  }
+
+  pub fn decimals(&self, asset_id: &str) -> Option<u32> {
+    self.decimals.get(&asset_id.to_uppercase()).copied()
+  }
+
+  /// `amount` in base units, e.g. satoshi or wei, failing when it has more decimals than the asset
+  pub fn to_base_units(&self, asset_id: &str, amount: &BigDecimal) -> Result<BigInt, ParamError> {
+    let decimals = i64::from(self.lookup(asset_id)?);
+    let scaled = amount.with_scale(decimals);
+    if &scaled != amount {
+      return Err(ParamError::InvalidParams { msg: format!("{amount} {asset_id} has more than {decimals} decimals") });
+    }
+    Ok(scaled.into_bigint_and_exponent().0)
+  }
+
+  pub fn from_base_units(&self, asset_id: &str, units: &BigInt) -> Result<BigDecimal, ParamError> {
+    Ok(BigDecimal::new(units.clone(), i64::from(self.lookup(asset_id)?)))
+  }
+
+  /// `amount` cut to the decimals of the asset, rounding toward zero as Fireblocks does when sending
+  pub fn round(&self, asset_id: &str, amount: &BigDecimal) -> Result<BigDecimal, ParamError> {
+    Ok(amount.with_scale_round(i64::from(self.lookup(asset_id)?), RoundingMode::Down))
+  }
+
+  /// `amount` cut to the decimals of the asset, without trailing zeros or exponent, e.g. `0.1` or `1500`
+  pub fn format(&self, asset_id: &str, amount: &BigDecimal) -> Result<String, ParamError> {
+    Ok(self.round(asset_id, amount)?.normalized().to_plain_string())
+  }
+
+  fn lookup(&self, asset_id: &str) -> Result<u32, ParamError> {
+    self
+      .decimals(asset_id)
+      .ok_or_else(|| ParamError::InvalidParams { msg: format!("decimals of {asset_id} are not known") })
+  }
+}
+
+impl Client {
+  /// Decimals of the supported assets, see [`crate::decimals`]
+  pub async fn asset_decimals(&self) -> crate::Result<AssetDecimals> {
+    let (assets, request_id) = self.supported_assets().await?;
+    Ok((AssetDecimals::new(&assets), request_id))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::Asset;
+  use std::str::FromStr;
+
+  fn asset(id: &str, decimals: i32) -> SupportedAsset {
+    SupportedAsset {
+      id: Asset::new(id),
+      name: String::from(id),
+      asset_type: String::from("BASE_ASSET"),
+      contract_address: String::new(),
+      native_asset: String::from(id),
+      decimals,
+    }
+  }
+
+  #[test]
+  fn test_units() -> color_eyre::Result<()> {
+    let mut decimals = AssetDecimals::new(&[asset("BTC", 8), asset("ETH", 18), asset("BAD", -1)]);
+    decimals.insert("usdc", 6);
+    assert_eq!(decimals.decimals("btc"), Some(8));
+    assert_eq!(decimals.decimals("BAD"), None);
+
+    let sats = decimals.to_base_units("BTC", &BigDecimal::from_str("1.5")?)?;
+    assert_eq!(sats, BigInt::from(150_000_000));
+    assert_eq!(decimals.from_base_units("BTC", &sats)?, BigDecimal::from_str("1.5")?);
+    assert!(decimals.to_base_units("BTC", &BigDecimal::from_str("0.000000001")?).is_err());
+    assert!(decimals.to_base_units("DOT", &BigDecimal::from(1)).is_err());
+    let wei = decimals.to_base_units("ETH", &BigDecimal::from(1000))?;
+    assert_eq!(wei.to_string(), format!("1000{}", "0".repeat(18)));
+    Ok(())
+  }
+
+  #[test]
+  fn test_format() -> color_eyre::Result<()> {
+    let decimals = AssetDecimals::new(&[asset("BTC", 8)]);
+    assert_eq!(decimals.format("BTC", &BigDecimal::from_str("0.123456789")?)?, "0.12345678");
+    assert_eq!(decimals.format("BTC", &BigDecimal::from_str("-0.999999999")?)?, "-0.99999999");
+    assert_eq!(decimals.format("BTC", &BigDecimal::from_str("1500.000")?)?, "1500");
+    assert_eq!(decimals.format("BTC", &BigDecimal::from_str("1E-9")?)?, "0");
+    assert_eq!(decimals.round("BTC", &BigDecimal::from_str("2.000000019")?)?, BigDecimal::from_str("2.00000001")?);
+    Ok(())
+  }
+}
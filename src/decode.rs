@@ -0,0 +1,460 @@
+//! Response decoding that either tolerates or rejects API drift
+//!
+//! Serde only sees the enum variants and struct fields a type was compiled with, [`Strictness`] is applied
+//! by wrapping the deserializer: enum values and object keys are checked against those lists as they
+//! are read. Values buffered by internally tagged, untagged or flattened types are decoded by serde
+//! itself and keep the behaviour of their attributes.
+use serde::de::{self, DeserializeSeed, Deserializer, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor};
+use std::fmt;
+
+/// How responses that do not match the types of this crate are decoded, see
+/// [`crate::ClientBuilder::with_strictness`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Strictness {
+  /// Unknown enum values become the `UNKNOWN` variant when the enum has one, unknown fields are ignored
+  #[default]
+  Lenient,
+  /// Unknown enum values and unknown fields are errors, to detect API drift early (e.g. in CI against
+  /// the sandbox)
+  Strict,
+}
+
+const UNKNOWN: &str = "UNKNOWN";
+
+/// A deserializer applying [`Strictness`] to everything below it
+pub struct Checked<D> {
+  de: D,
+  mode: Strictness,
+}
+
+impl<D> Checked<D> {
+  pub const fn new(de: D, mode: Strictness) -> Self {
+    Self { de, mode }
+  }
+}
+
+struct CheckedVisitor<V> {
+  visitor: V,
+  mode: Strictness,
+  /// Known fields when visiting a struct
+  fields: Option<&'static [&'static str]>,
+}
+
+impl<V> CheckedVisitor<V> {
+  const fn new(visitor: V, mode: Strictness) -> Self {
+    Self { visitor, mode, fields: None }
+  }
+}
+
+struct CheckedSeed<S> {
+  seed: S,
+  mode: Strictness,
+}
+
+macro_rules! forward_deserialize {
+  ($($method:ident($($arg:ident: $ty:ty),*)),* $(,)?) => {
+    $(
+      fn $method<V: Visitor<'de>>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, Self::Error> {
+        self.de.$method($($arg,)* CheckedVisitor::new(visitor, self.mode))
+      }
+    )*
+  };
+}
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for Checked<D> {
+  type Error = D::Error;
+
+  forward_deserialize!(
+    deserialize_any(),
+    deserialize_bool(),
+    deserialize_i8(),
+    deserialize_i16(),
+    deserialize_i32(),
+    deserialize_i64(),
+    deserialize_i128(),
+    deserialize_u8(),
+    deserialize_u16(),
+    deserialize_u32(),
+    deserialize_u64(),
+    deserialize_u128(),
+    deserialize_f32(),
+    deserialize_f64(),
+    deserialize_char(),
+    deserialize_str(),
+    deserialize_string(),
+    deserialize_bytes(),
+    deserialize_byte_buf(),
+    deserialize_option(),
+    deserialize_unit(),
+    deserialize_unit_struct(name: &'static str),
+    deserialize_newtype_struct(name: &'static str),
+    deserialize_seq(),
+    deserialize_tuple(len: usize),
+    deserialize_tuple_struct(name: &'static str, len: usize),
+    deserialize_map(),
+    deserialize_identifier(),
+    deserialize_ignored_any(),
+  );
+
+  fn deserialize_struct<V: Visitor<'de>>(
+    self,
+    name: &'static str,
+    fields: &'static [&'static str],
+    visitor: V,
+  ) -> Result<V::Value, Self::Error> {
+    let visitor = CheckedVisitor { visitor, mode: self.mode, fields: Some(fields) };
+    self.de.deserialize_struct(name, fields, visitor)
+  }
+
+  fn deserialize_enum<V: Visitor<'de>>(
+    self,
+    name: &'static str,
+    variants: &'static [&'static str],
+    visitor: V,
+  ) -> Result<V::Value, Self::Error> {
+    self.de.deserialize_enum(name, variants, EnumVisitor { visitor, mode: self.mode, variants })
+  }
+
+  fn is_human_readable(&self) -> bool {
+    self.de.is_human_readable()
+  }
+}
+
+macro_rules! forward_visit {
+  ($($method:ident($ty:ty)),* $(,)?) => {
+    $(
+      fn $method<E: de::Error>(self, v: $ty) -> Result<Self::Value, E> {
+        self.visitor.$method(v)
+      }
+    )*
+  };
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for CheckedVisitor<V> {
+  type Value = V::Value;
+
+  fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    self.visitor.expecting(f)
+  }
+
+  forward_visit!(
+    visit_bool(bool),
+    visit_i8(i8),
+    visit_i16(i16),
+    visit_i32(i32),
+    visit_i64(i64),
+    visit_i128(i128),
+    visit_u8(u8),
+    visit_u16(u16),
+    visit_u32(u32),
+    visit_u64(u64),
+    visit_u128(u128),
+    visit_f32(f32),
+    visit_f64(f64),
+    visit_char(char),
+    visit_str(&str),
+    visit_borrowed_str(&'de str),
+    visit_string(String),
+    visit_bytes(&[u8]),
+    visit_borrowed_bytes(&'de [u8]),
+    visit_byte_buf(Vec<u8>),
+  );
+
+  fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+    self.visitor.visit_none()
+  }
+
+  fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+    self.visitor.visit_unit()
+  }
+
+  fn visit_some<D: Deserializer<'de>>(self, de: D) -> Result<Self::Value, D::Error> {
+    self.visitor.visit_some(Checked::new(de, self.mode))
+  }
+
+  fn visit_newtype_struct<D: Deserializer<'de>>(self, de: D) -> Result<Self::Value, D::Error> {
+    self.visitor.visit_newtype_struct(Checked::new(de, self.mode))
+  }
+
+  fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+    self.visitor.visit_seq(CheckedSeq { seq, mode: self.mode })
+  }
+
+  fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+    self.visitor.visit_map(CheckedMap { map, mode: self.mode, fields: self.fields })
+  }
+
+  fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+    self.visitor.visit_enum(CheckedEnum { data, mode: self.mode, variants: &[] })
+  }
+}
+
+impl<'de, S: DeserializeSeed<'de>> DeserializeSeed<'de> for CheckedSeed<S> {
+  type Value = S::Value;
+
+  fn deserialize<D: Deserializer<'de>>(self, de: D) -> Result<Self::Value, D::Error> {
+    self.seed.deserialize(Checked::new(de, self.mode))
+  }
+}
+
+struct CheckedSeq<A> {
+  seq: A,
+  mode: Strictness,
+}
+
+impl<'de, A: SeqAccess<'de>> SeqAccess<'de> for CheckedSeq<A> {
+  type Error = A::Error;
+
+  fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error> {
+    self.seq.next_element_seed(CheckedSeed { seed, mode: self.mode })
+  }
+
+  fn size_hint(&self) -> Option<usize> {
+    self.seq.size_hint()
+  }
+}
+
+struct CheckedMap<A> {
+  map: A,
+  mode: Strictness,
+  fields: Option<&'static [&'static str]>,
+}
+
+impl<'de, A: MapAccess<'de>> MapAccess<'de> for CheckedMap<A> {
+  type Error = A::Error;
+
+  fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error> {
+    match (self.mode, self.fields) {
+      (Strictness::Strict, Some(fields)) => self.map.next_key_seed(NameSeed { seed, names: fields, kind: Name::Field }),
+      _ => self.map.next_key_seed(CheckedSeed { seed, mode: self.mode }),
+    }
+  }
+
+  fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Self::Error> {
+    self.map.next_value_seed(CheckedSeed { seed, mode: self.mode })
+  }
+
+  fn size_hint(&self) -> Option<usize> {
+    self.map.size_hint()
+  }
+}
+
+struct EnumVisitor<V> {
+  visitor: V,
+  mode: Strictness,
+  variants: &'static [&'static str],
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for EnumVisitor<V> {
+  type Value = V::Value;
+
+  fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    self.visitor.expecting(f)
+  }
+
+  fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+    self.visitor.visit_enum(CheckedEnum { data, mode: self.mode, variants: self.variants })
+  }
+
+  // formats representing enums without `visit_enum`, e.g. after buffering
+  fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+    self.visitor.visit_str(v)
+  }
+
+  fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+    self.visitor.visit_map(CheckedMap { map, mode: self.mode, fields: None })
+  }
+}
+
+struct CheckedEnum<A> {
+  data: A,
+  mode: Strictness,
+  variants: &'static [&'static str],
+}
+
+impl<'de, A: EnumAccess<'de>> EnumAccess<'de> for CheckedEnum<A> {
+  type Error = A::Error;
+  type Variant = CheckedVariant<A::Variant>;
+
+  fn variant_seed<S: DeserializeSeed<'de>>(self, seed: S) -> Result<(S::Value, Self::Variant), Self::Error> {
+    let (value, variant) = if self.variants.is_empty() {
+      self.data.variant_seed(seed)?
+    } else {
+      self.data.variant_seed(NameSeed { seed, names: self.variants, kind: Name::Variant(self.mode) })?
+    };
+    Ok((value, CheckedVariant { variant, mode: self.mode }))
+  }
+}
+
+struct CheckedVariant<A> {
+  variant: A,
+  mode: Strictness,
+}
+
+impl<'de, A: VariantAccess<'de>> VariantAccess<'de> for CheckedVariant<A> {
+  type Error = A::Error;
+
+  fn unit_variant(self) -> Result<(), Self::Error> {
+    self.variant.unit_variant()
+  }
+
+  fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Self::Error> {
+    self.variant.newtype_variant_seed(CheckedSeed { seed, mode: self.mode })
+  }
+
+  fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error> {
+    self.variant.tuple_variant(len, CheckedVisitor::new(visitor, self.mode))
+  }
+
+  fn struct_variant<V: Visitor<'de>>(
+    self,
+    fields: &'static [&'static str],
+    visitor: V,
+  ) -> Result<V::Value, Self::Error> {
+    self.variant.struct_variant(fields, CheckedVisitor { visitor, mode: self.mode, fields: Some(fields) })
+  }
+}
+
+#[derive(Clone, Copy)]
+enum Name {
+  /// Object key of a struct, only checked in strict mode
+  Field,
+  Variant(Strictness),
+}
+
+/// Checks an identifier, an object key or an enum value, against the names the type knows
+struct NameSeed<S> {
+  seed: S,
+  names: &'static [&'static str],
+  kind: Name,
+}
+
+impl<'de, S: DeserializeSeed<'de>> DeserializeSeed<'de> for NameSeed<S> {
+  type Value = S::Value;
+
+  fn deserialize<D: Deserializer<'de>>(self, de: D) -> Result<Self::Value, D::Error> {
+    self.seed.deserialize(NameDeserializer { de, names: self.names, kind: self.kind })
+  }
+}
+
+struct NameDeserializer<D> {
+  de: D,
+  names: &'static [&'static str],
+  kind: Name,
+}
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for NameDeserializer<D> {
+  type Error = D::Error;
+
+  fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+    self.de.deserialize_any(NameVisitor { visitor, names: self.names, kind: self.kind })
+  }
+
+  fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+    self.de.deserialize_identifier(NameVisitor { visitor, names: self.names, kind: self.kind })
+  }
+
+  serde::forward_to_deserialize_any! {
+    bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option unit
+    unit_struct newtype_struct seq tuple tuple_struct map struct enum ignored_any
+  }
+}
+
+struct NameVisitor<V> {
+  visitor: V,
+  names: &'static [&'static str],
+  kind: Name,
+}
+
+impl<'de, V: Visitor<'de>> NameVisitor<V> {
+  fn name<E: de::Error>(self, v: &str) -> Result<V::Value, E> {
+    if self.names.contains(&v) {
+      return self.visitor.visit_str(v);
+    }
+    match self.kind {
+      Name::Field => Err(E::unknown_field(v, self.names)),
+      Name::Variant(Strictness::Strict) => Err(E::unknown_variant(v, self.names)),
+      Name::Variant(Strictness::Lenient) if self.names.contains(&UNKNOWN) => {
+        tracing::debug!("unknown enum value {v}, using {UNKNOWN}");
+        self.visitor.visit_str(UNKNOWN)
+      },
+      Name::Variant(Strictness::Lenient) => self.visitor.visit_str(v),
+    }
+  }
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for NameVisitor<V> {
+  type Value = V::Value;
+
+  fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    self.visitor.expecting(f)
+  }
+
+  fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+    self.name(v)
+  }
+
+  fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+    self.name(v)
+  }
+
+  fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+    self.name(&v)
+  }
+
+  fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+    self.visitor.visit_u64(v)
+  }
+
+  fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+    match std::str::from_utf8(v) {
+      Ok(s) => self.name(s),
+      Err(_) => self.visitor.visit_bytes(v),
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::types::{TransactionStatus, WhitelistedIps};
+  use serde::de::DeserializeOwned;
+  use serde_json::json;
+
+  fn decode<T: DeserializeOwned>(value: &serde_json::Value, mode: Strictness) -> Result<T, serde_json::Error> {
+    let body = value.to_string();
+    let mut de = serde_json::Deserializer::from_str(&body);
+    T::deserialize(Checked::new(&mut de, mode))
+  }
+
+  #[test]
+  fn test_enum() -> color_eyre::Result<()> {
+    let status = json!("SOME_NEW_STATUS");
+    assert!(serde_json::from_value::<TransactionStatus>(status.clone()).is_err());
+    assert_eq!(decode::<TransactionStatus>(&status, Strictness::Lenient)?, TransactionStatus::UNKNOWN);
+    assert!(decode::<TransactionStatus>(&status, Strictness::Strict).is_err());
+    assert_eq!(decode::<TransactionStatus>(&json!("FAILED"), Strictness::Strict)?, TransactionStatus::FAILED);
+
+    // `#[serde(other)]` is rejected too in strict mode
+    let role = json!("SOME_NEW_ROLE");
+    assert_eq!(decode::<crate::types::UserRole>(&role, Strictness::Lenient)?, crate::types::UserRole::UNKNOWN);
+    assert!(decode::<crate::types::UserRole>(&role, Strictness::Strict).is_err());
+    Ok(())
+  }
+
+  #[test]
+  fn test_fields() -> color_eyre::Result<()> {
+    let mut ips = json!({"userId": "u", "whitelistedIps": ["127.0.0.1"]});
+    assert_eq!(decode::<WhitelistedIps>(&ips, Strictness::Strict)?.whitelist_ips.len(), 1);
+    ips["someNewField"] = json!({"nested": [1, 2]});
+    decode::<WhitelistedIps>(&ips, Strictness::Lenient)?;
+    let err = decode::<WhitelistedIps>(&ips, Strictness::Strict).expect_err("unknown field");
+    assert!(err.to_string().contains("someNewField"));
+
+    // nested in sequences and options
+    let list = json!([null, ips]);
+    assert_eq!(decode::<Vec<Option<WhitelistedIps>>>(&list, Strictness::Lenient)?.len(), 2);
+    assert!(decode::<Vec<Option<WhitelistedIps>>>(&list, Strictness::Strict).is_err());
+    Ok(())
+  }
+}
@@ -0,0 +1,546 @@
+//! Batch disbursements driven by a CSV file or any iterator of rows
+//!
+//! ```
+//! use fireblocks_sdk::Client;
+//! use fireblocks_sdk::disbursement::{Disbursement, DisbursementMode, DisbursementRow};
+//! use std::time::Duration;
+//!
+//! async fn pay(c: Client) -> color_eyre::Result<()> {
+//!   let csv = "destination,asset,amount,reference\n\
+//!              EXTERNAL_WALLET:7a1c,USDC,125.50,invoice-1\n\
+//!              EXTERNAL_WALLET:9f3e,USDC,80,invoice-2\n";
+//!   let rows = DisbursementRow::from_csv(csv.as_bytes())?;
+//!   let report = Disbursement::new(c, 0)
+//!     .with_mode(DisbursementMode::Payout { max_instructions: 100 })
+//!     .with_interval(Duration::from_millis(500))
+//!     .submit(rows)
+//!     .await;
+//!   println!("{}", report.to_csv());
+//!   Ok(())
+//! }
+//! ```
+use crate::error::{ParamError, StoreError};
+use crate::types::{
+  CreatePayoutRequest, DestinationTransferPeerPath, OneTimeAddress, PayoutAccount, PayoutAccountType, PayoutAmount,
+  PayoutInstruction, PayoutState, PeerType, TransactionArguments, TransactionOperation, TransactionRequestDestination,
+  TransferPeerPath,
+};
+use crate::{csv, Client, FireblocksError};
+use bigdecimal::BigDecimal;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::io::BufRead;
+use std::str::FromStr;
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+/// A single payment: `amount` of `asset` to `destination`, identified by a unique `reference`
+#[derive(Debug, Clone)]
+pub struct DisbursementRow {
+  pub destination: DestinationTransferPeerPath,
+  pub asset: String,
+  pub amount: BigDecimal,
+  pub reference: String,
+}
+
+impl DisbursementRow {
+  /// Parse rows from CSV with the columns `destination,asset,amount,reference`
+  ///
+  /// A header line is skipped if present. The destination is either `PEER_TYPE:id`
+  /// (e.g. `VAULT_ACCOUNT:1`, `EXTERNAL_WALLET:<uuid>`) or a raw blockchain address,
+  /// optionally followed by `?tag` for assets that need a memo.
+  pub fn from_csv(reader: impl BufRead) -> Result<Vec<Self>, ParamError> {
+    let mut rows = Vec::new();
+    for (n, line) in reader.lines().enumerate() {
+      let line = line.map_err(|e| ParamError::InvalidParams { msg: format!("csv line {}: {e}", n + 1) })?;
+      if line.trim().is_empty() {
+        continue;
+      }
+      let fields = csv::parse_line(&line);
+      if n == 0 && fields.first().is_some_and(|f| f.eq_ignore_ascii_case("destination")) {
+        continue;
+      }
+      let [destination, asset, amount, reference] = fields.as_slice() else {
+        return Err(ParamError::InvalidParams { msg: format!("csv line {}: expected 4 columns", n + 1) });
+      };
+      let amount = BigDecimal::from_str(amount)
+        .map_err(|e| ParamError::InvalidParams { msg: format!("csv line {}: amount {amount}: {e}", n + 1) })?;
+      rows.push(Self {
+        destination: parse_destination(destination),
+        asset: String::from(asset),
+        amount,
+        reference: String::from(reference),
+      });
+    }
+    Ok(rows)
+  }
+}
+
+fn parse_destination(s: &str) -> DestinationTransferPeerPath {
+  if let Some((t, id)) = s.split_once(':') {
+    if let Ok(peer_type) = serde_json::from_value::<PeerType>(serde_json::Value::String(t.to_uppercase())) {
+      return DestinationTransferPeerPath { peer_type, id: String::from(id), ..Default::default() };
+    }
+  }
+  let (address, tag) = match s.split_once('?') {
+    Some((a, t)) => (a, Some(String::from(t))),
+    None => (s, None),
+  };
+  DestinationTransferPeerPath {
+    peer_type: PeerType::ONE_TIME_ADDRESS,
+    one_time_address: Some(OneTimeAddress { address: String::from(address), tag }),
+    ..Default::default()
+  }
+}
+
+fn destination_label(d: &DestinationTransferPeerPath) -> String {
+  d.one_time_address.as_ref().map_or_else(
+    || format!("{}:{}", peer_type_label(&d.peer_type), d.id),
+    |ota| ota.tag.as_ref().map_or_else(|| ota.address.clone(), |tag| format!("{}?{tag}", ota.address)),
+  )
+}
+
+fn peer_type_label(t: &PeerType) -> String {
+  serde_json::to_value(t).ok().and_then(|v| v.as_str().map(String::from)).unwrap_or_default()
+}
+
+/// How rows are grouped into API calls
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisbursementMode {
+  /// Group rows of the same asset into transactions of up to `max_destinations` destinations.
+  /// Multiple destinations are only supported for UTXO based assets, use 1 otherwise
+  Transactions { max_destinations: usize },
+  /// Group rows of the same asset into payout instruction sets of up to `max_instructions`
+  Payout { max_instructions: usize },
+}
+
+impl Default for DisbursementMode {
+  fn default() -> Self {
+    Self::Transactions { max_destinations: 1 }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DisbursementOutcome {
+  /// A transaction or payout was created. Rows grouped together share the same id
+  Submitted { id: String },
+  /// A previous run already created the transaction for this row
+  AlreadySubmitted { id: String },
+  Failed {
+    /// The payout, when it was created but could not be executed. Running the batch again executes it
+    id: Option<String>,
+    error: String,
+  },
+}
+
+#[derive(Debug, Clone)]
+pub struct DisbursementResult {
+  pub row: DisbursementRow,
+  pub outcome: DisbursementOutcome,
+}
+
+#[derive(Debug, Default)]
+pub struct DisbursementReport {
+  pub results: Vec<DisbursementResult>,
+}
+
+impl DisbursementReport {
+  pub fn failed(&self) -> impl Iterator<Item = &DisbursementResult> {
+    self.results.iter().filter(|r| matches!(r.outcome, DisbursementOutcome::Failed { .. }))
+  }
+
+  /// Render the report as CSV: `reference,destination,asset,amount,status,id,error`
+  pub fn to_csv(&self) -> String {
+    let mut out = csv::line(["reference", "destination", "asset", "amount", "status", "id", "error"]);
+    out.push('\n');
+    for r in &self.results {
+      let (status, id, error) = match &r.outcome {
+        DisbursementOutcome::Submitted { id } => ("SUBMITTED", id.as_str(), ""),
+        DisbursementOutcome::AlreadySubmitted { id } => ("ALREADY_SUBMITTED", id.as_str(), ""),
+        DisbursementOutcome::Failed { id, error } => ("FAILED", id.as_deref().unwrap_or_default(), error.as_str()),
+      };
+      let amount = r.row.amount.to_string();
+      let dest = destination_label(&r.row.destination);
+      out.push_str(&csv::line([r.row.reference.as_str(), &dest, &r.row.asset, &amount, status, id, error]));
+      out.push('\n');
+    }
+    out
+  }
+}
+
+/// Remembers the payouts a [`Disbursement`] created by their reference, as payouts cannot be looked up by one
+pub trait PayoutStore: Send + Sync {
+  fn payout_id(&self, reference: &str) -> impl Future<Output = Result<Option<String>, StoreError>> + Send;
+  fn insert(&self, reference: &str, payout_id: &str) -> impl Future<Output = Result<(), StoreError>> + Send;
+}
+
+/// Keeps payout ids in memory, which are lost on restart
+#[derive(Debug, Default)]
+pub struct MemoryPayoutStore {
+  payouts: Mutex<HashMap<String, String>>,
+}
+
+impl PayoutStore for MemoryPayoutStore {
+  async fn payout_id(&self, reference: &str) -> Result<Option<String>, StoreError> {
+    Ok(self.payouts.lock().unwrap_or_else(PoisonError::into_inner).get(reference).cloned())
+  }
+
+  async fn insert(&self, reference: &str, payout_id: &str) -> Result<(), StoreError> {
+    self
+      .payouts
+      .lock()
+      .unwrap_or_else(PoisonError::into_inner)
+      .insert(String::from(reference), String::from(payout_id));
+    Ok(())
+  }
+}
+
+/// Submit a batch of [`DisbursementRow`] from a source vault account
+pub struct Disbursement<S = MemoryPayoutStore> {
+  client: Client,
+  source_vault: i32,
+  mode: DisbursementMode,
+  interval: Duration,
+  note: String,
+  payouts: S,
+}
+
+impl Disbursement {
+  pub fn new(client: Client, source_vault: i32) -> Self {
+    Self {
+      client,
+      source_vault,
+      mode: DisbursementMode::default(),
+      interval: Duration::from_millis(250),
+      note: String::from("created by fireblocks-sdk for rust"),
+      payouts: MemoryPayoutStore::default(),
+    }
+  }
+}
+
+impl<S: PayoutStore> Disbursement<S> {
+  /// Keep the payouts created in [`DisbursementMode::Payout`] in `store`, e.g. a database, so a batch run again
+  /// after a restart does not pay twice
+  pub fn with_payout_store<T: PayoutStore>(self, store: T) -> Disbursement<T> {
+    let Self { client, source_vault, mode, interval, note, .. } = self;
+    Disbursement { client, source_vault, mode, interval, note, payouts: store }
+  }
+
+  #[allow(clippy::return_self_not_must_use)]
+  pub const fn with_mode(mut self, mode: DisbursementMode) -> Self {
+    self.mode = mode;
+    self
+  }
+
+  /// Pause between API calls, to stay under rate limits
+  #[allow(clippy::return_self_not_must_use)]
+  pub const fn with_interval(mut self, interval: Duration) -> Self {
+    self.interval = interval;
+    self
+  }
+
+  #[allow(clippy::return_self_not_must_use)]
+  pub fn with_note(mut self, note: &str) -> Self {
+    self.note = String::from(note);
+    self
+  }
+
+  /// Submit every row, returning a result for each of them in input order
+  ///
+  /// Each transaction or payout gets a reference derived from the references of its rows, so re-running the
+  /// same batch skips rows that were already submitted: in [`DisbursementMode::Transactions`] it is the
+  /// `externalTxId` of the transaction, in [`DisbursementMode::Payout`] the key of the payout in the
+  /// [`PayoutStore`]. A payout created but not executed is executed by the next run.
+  pub async fn submit(&self, rows: impl IntoIterator<Item = DisbursementRow>) -> DisbursementReport {
+    let groups = match self.mode {
+      DisbursementMode::Transactions { max_destinations } => group(rows, max_destinations),
+      DisbursementMode::Payout { max_instructions } => group(rows, max_instructions),
+    };
+    let mut results = Vec::new();
+    for (n, chunk) in groups.into_iter().enumerate() {
+      if n > 0 {
+        tokio::time::sleep(self.interval).await;
+      }
+      let (positions, rows): (Vec<usize>, Vec<DisbursementRow>) = chunk.into_iter().unzip();
+      let outcome = match self.mode {
+        DisbursementMode::Transactions { .. } => self
+          .submit_transaction(&rows)
+          .await
+          .unwrap_or_else(|e| DisbursementOutcome::Failed { id: None, error: e.to_string() }),
+        DisbursementMode::Payout { .. } => self.submit_payout(&rows).await,
+      };
+      results.extend(
+        positions.into_iter().zip(rows).map(|(n, row)| (n, DisbursementResult { row, outcome: outcome.clone() })),
+      );
+    }
+    results.sort_by_key(|(n, _)| *n);
+    DisbursementReport { results: results.into_iter().map(|(_, result)| result).collect() }
+  }
+
+  async fn submit_transaction(&self, rows: &[DisbursementRow]) -> Result<DisbursementOutcome, FireblocksError> {
+    let external_tx_id = idempotency_key(rows);
+    match self.client.get_transaction_external(&external_tx_id).await {
+      Ok((tx, _)) => return Ok(DisbursementOutcome::AlreadySubmitted { id: tx.id }),
+      Err(FireblocksError::NotFound { .. }) => {},
+      Err(e) => return Err(e),
+    }
+    let args = self.transaction_args(rows, external_tx_id);
+    let (tx, _) = self.client.create_transaction(&args).await?;
+    Ok(DisbursementOutcome::Submitted { id: tx.id })
+  }
+
+  fn transaction_args(&self, rows: &[DisbursementRow], external_tx_id: String) -> TransactionArguments {
+    let total: BigDecimal = rows.iter().map(|r| &r.amount).sum();
+    let (destination, destinations) = if let [row] = rows {
+      (Some(row.destination.clone()), None)
+    } else {
+      let d = rows
+        .iter()
+        .map(|r| TransactionRequestDestination { amount: r.amount.to_string(), destination: r.destination.clone() })
+        .collect();
+      (None, Some(d))
+    };
+    TransactionArguments {
+      asset_id: rows.first().map(|r| r.asset.clone()).unwrap_or_default(),
+      operation: TransactionOperation::TRANSFER,
+      source: TransferPeerPath { id: Some(self.source_vault.to_string()), ..Default::default() },
+      destination,
+      amount: total.to_string(),
+      gas_price: None,
+      gas_limit: None,
+      fee_level: None,
+      note: self.note.clone(),
+      extra_parameters: None,
+      destinations,
+      external_tx_id: Some(external_tx_id),
+    }
+  }
+
+  async fn submit_payout(&self, rows: &[DisbursementRow]) -> DisbursementOutcome {
+    let reference = idempotency_key(rows);
+    let payout_id = match self.payouts.payout_id(&reference).await {
+      Ok(Some(id)) => id,
+      Ok(None) => match self.create_payout(rows).await {
+        Ok(id) => id,
+        Err(e) => return DisbursementOutcome::Failed { id: None, error: e.to_string() },
+      },
+      Err(e) => return DisbursementOutcome::Failed { id: None, error: e.to_string() },
+    };
+    let failed =
+      |e: &dyn std::error::Error| DisbursementOutcome::Failed { id: Some(payout_id.clone()), error: e.to_string() };
+    if let Err(e) = self.payouts.insert(&reference, &payout_id).await {
+      return failed(&e);
+    }
+    // a payout from an earlier run may already be executing
+    match self.client.payout(&payout_id).await {
+      Ok((payout, _)) if payout.state != PayoutState::CREATED => {
+        return DisbursementOutcome::AlreadySubmitted { id: payout_id };
+      },
+      Ok(_) => {},
+      Err(e) => return failed(&e),
+    }
+    match self.client.payout_execute(&payout_id).await {
+      Ok(_) => DisbursementOutcome::Submitted { id: payout_id },
+      Err(e) => failed(&e),
+    }
+  }
+
+  async fn create_payout(&self, rows: &[DisbursementRow]) -> Result<String, FireblocksError> {
+    let mut instruction_set = Vec::with_capacity(rows.len());
+    for row in rows {
+      let account_type = serde_json::from_value::<PayoutAccountType>(serde_json::Value::String(peer_type_label(
+        &row.destination.peer_type,
+      )))
+      .map_err(|_| ParamError::InvalidParams {
+        msg: format!("{} cannot be a payout destination", destination_label(&row.destination)),
+      })?;
+      instruction_set.push(PayoutInstruction {
+        id: Some(row.reference.clone()),
+        payee_account: PayoutAccount { id: row.destination.id.clone(), account_type },
+        amount: PayoutAmount { amount: row.amount.clone(), asset_id: row.asset.clone() },
+      });
+    }
+    let request = CreatePayoutRequest {
+      payment_account: PayoutAccount {
+        id: self.source_vault.to_string(),
+        account_type: PayoutAccountType::VAULT_ACCOUNT,
+      },
+      instruction_set,
+    };
+    Ok(self.client.payout_create(&request).await?.0.payout_id)
+  }
+}
+
+/// Group rows by asset, preserving input order within each asset, in chunks of at most `size`. Rows keep their
+/// position in the input
+fn group(rows: impl IntoIterator<Item = DisbursementRow>, size: usize) -> Vec<Vec<(usize, DisbursementRow)>> {
+  let mut by_asset: BTreeMap<String, Vec<(usize, DisbursementRow)>> = BTreeMap::new();
+  for (n, row) in rows.into_iter().enumerate() {
+    by_asset.entry(row.asset.clone()).or_default().push((n, row));
+  }
+  let size = size.max(1);
+  by_asset
+    .into_values()
+    .flat_map(|rows| {
+      let mut chunks = Vec::new();
+      let mut rows = rows.into_iter().peekable();
+      while rows.peek().is_some() {
+        chunks.push(rows.by_ref().take(size).collect());
+      }
+      chunks
+    })
+    .collect()
+}
+
+#[allow(clippy::format_collect)]
+fn idempotency_key(rows: &[DisbursementRow]) -> String {
+  let mut digest = Sha256::new();
+  for r in rows {
+    digest.update(r.reference.as_bytes());
+    digest.update([0]);
+  }
+  let hash: String = digest.finalize().iter().take(16).map(|b| format!("{b:02x}")).collect();
+  format!("disbursement-{hash}")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::mock::{self, MockTransport};
+  use crate::transport::{HttpRequest, HttpResponse};
+  use http::{Method, StatusCode};
+  use std::sync::Arc;
+
+  const CSV: &str = "destination,asset,amount,reference
+VAULT_ACCOUNT:1,BTC_TEST,0.1,r1
+tb1qexampleaddress,BTC_TEST,0.2,r2
+rEXAMPLE?12345,XRP_TEST,5,r3
+EXTERNAL_WALLET:abc,BTC_TEST,0.3,\"r,4\"
+";
+
+  #[test]
+  fn parse_csv() -> color_eyre::Result<()> {
+    let rows = DisbursementRow::from_csv(CSV.as_bytes())?;
+    assert_eq!(4, rows.len());
+    assert_eq!(PeerType::VAULT_ACCOUNT, rows[0].destination.peer_type);
+    assert_eq!("1", rows[0].destination.id);
+    assert_eq!(PeerType::ONE_TIME_ADDRESS, rows[1].destination.peer_type);
+    let ota = rows[2].destination.one_time_address.as_ref().map(|o| o.tag.clone());
+    assert_eq!(Some(Some("12345".to_string())), ota);
+    assert_eq!("r,4", rows[3].reference);
+    assert!(DisbursementRow::from_csv(&b"a,b"[..]).is_err());
+    assert!(DisbursementRow::from_csv(&b"a,b,notanumber,c"[..]).is_err());
+    Ok(())
+  }
+
+  #[test]
+  fn grouping() -> color_eyre::Result<()> {
+    let rows = DisbursementRow::from_csv(CSV.as_bytes())?;
+    let groups = group(rows.clone(), 2);
+    let sizes: Vec<usize> = groups.iter().map(Vec::len).collect();
+    assert_eq!(vec![2, 1, 1], sizes);
+    assert_eq!("r1", groups[0][0].1.reference);
+    assert_eq!((2, "XRP_TEST"), (groups[2][0].0, groups[2][0].1.asset.as_str()));
+    assert_eq!(4, group(rows, 0).len());
+
+    let rows = |n: usize| groups[n].iter().map(|(_, r)| r.clone()).collect::<Vec<_>>();
+    let key = idempotency_key(&rows(0));
+    assert_eq!(key, idempotency_key(&rows(0)));
+    assert_ne!(key, idempotency_key(&rows(1)));
+    assert!(key.len() <= 64);
+    Ok(())
+  }
+
+  #[test]
+  fn report_csv() -> color_eyre::Result<()> {
+    let rows = DisbursementRow::from_csv(CSV.as_bytes())?;
+    let report = DisbursementReport {
+      results: vec![
+        DisbursementResult { row: rows[0].clone(), outcome: DisbursementOutcome::Submitted { id: "tx".into() } },
+        DisbursementResult {
+          row: rows[2].clone(),
+          outcome: DisbursementOutcome::Failed { id: Some("p1".into()), error: "no, funds".into() },
+        },
+      ],
+    };
+    let out = report.to_csv();
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!("r1,VAULT_ACCOUNT:1,BTC_TEST,0.1,SUBMITTED,tx,", lines[1]);
+    assert_eq!("r3,rEXAMPLE?12345,XRP_TEST,5,FAILED,p1,\"no, funds\"", lines[2]);
+    assert_eq!(1, report.failed().count());
+    Ok(())
+  }
+
+  /// Payouts API creating `payout-<n>`, whose first execution fails
+  fn payouts(created: &Mutex<Vec<PayoutState>>, executions: &Mutex<usize>, request: &HttpRequest) -> HttpResponse {
+    let path = request.url.path().trim_start_matches("/v1/payments/payout").trim_matches('/').to_string();
+    let mut created = created.lock().unwrap_or_else(PoisonError::into_inner);
+    let (status, body) = match (&request.method, path.split('/').collect::<Vec<_>>().as_slice()) {
+      (&Method::POST, [""]) => {
+        created.push(PayoutState::CREATED);
+        (StatusCode::OK, payout(&format!("payout-{}", created.len() - 1), &PayoutState::CREATED))
+      },
+      (&Method::POST, [id, "actions", "execute"]) => {
+        let mut executions = executions.lock().unwrap_or_else(PoisonError::into_inner);
+        *executions += 1;
+        if *executions == 1 {
+          (StatusCode::BAD_REQUEST, serde_json::json!({ "message": "insufficient balance" }))
+        } else {
+          let n: usize = id.trim_start_matches("payout-").parse().expect("payout id");
+          created[n] = PayoutState::SUBMITTED;
+          (StatusCode::OK, serde_json::json!({ "payoutId": id }))
+        }
+      },
+      (&Method::GET, [id]) => {
+        let n: usize = id.trim_start_matches("payout-").parse().expect("payout id");
+        (StatusCode::OK, payout(id, &created[n]))
+      },
+      _ => (StatusCode::NOT_FOUND, serde_json::json!({})),
+    };
+    drop(created);
+    mock::json(status, serde_json::to_vec(&body).expect("json"))
+  }
+
+  fn payout(id: &str, state: &PayoutState) -> serde_json::Value {
+    serde_json::json!({
+      "payoutId": id,
+      "paymentAccount": { "id": "0", "type": "VAULT_ACCOUNT" },
+      "createdAt": 0,
+      "state": state,
+      "status": "REGISTERED",
+      "instructionSet": [],
+    })
+  }
+
+  #[tokio::test]
+  async fn payout_retry() -> color_eyre::Result<()> {
+    let created = Arc::new(Mutex::new(Vec::new()));
+    let state = created.clone();
+    let executions = Mutex::new(0);
+    let client = mock::client(MockTransport::new(move |request| payouts(&state, &executions, request)))?;
+    let disbursement = Disbursement::new(client, 0)
+      .with_mode(DisbursementMode::Payout { max_instructions: 10 })
+      .with_interval(Duration::ZERO);
+    let rows = DisbursementRow::from_csv(
+      &b"destination,asset,amount,reference
+VAULT_ACCOUNT:1,XRP,1,a
+VAULT_ACCOUNT:2,BTC,1,b
+VAULT_ACCOUNT:3,XRP,1,c
+"[..],
+    )?;
+
+    let report = disbursement.submit(rows.clone()).await;
+    let references = report.results.iter().map(|r| r.row.reference.as_str()).collect::<Vec<_>>();
+    assert_eq!(references, ["a", "b", "c"]);
+    let failed = report.failed().map(|r| r.outcome.clone()).collect::<Vec<_>>();
+    assert!(matches!(&failed[..], [DisbursementOutcome::Failed { id: Some(id), .. }] if id == "payout-0"));
+
+    let report = disbursement.submit(rows).await;
+    assert_eq!(0, report.failed().count());
+    assert!(matches!(&report.results[0].outcome, DisbursementOutcome::AlreadySubmitted { id } if id == "payout-1"));
+    assert!(matches!(&report.results[1].outcome, DisbursementOutcome::Submitted { id } if id == "payout-0"));
+    assert_eq!(2, created.lock().unwrap_or_else(PoisonError::into_inner).len());
+    Ok(())
+  }
+}
@@ -1,3 +1,5 @@
+use crate::decode::{Checked, Strictness};
+use serde::de::DeserializeOwned;
 use thiserror::Error;
 use url::ParseError;
 
@@ -9,6 +11,33 @@ pub enum ParamError {
   InvalidParams { msg: String },
 }
 
+/// Thrown by pluggable storage backends (scheduler state, ledgers, ...)
+#[derive(Debug, Error)]
+#[error("storage error: {0}")]
+pub struct StoreError(pub String);
+
+/// Thrown when a webhook notification cannot be authenticated or parsed
+#[derive(Debug, Error)]
+pub enum WebhookError {
+  #[error("missing fireblocks-signature header")]
+  MissingSignature,
+
+  #[error("invalid webhook signature")]
+  InvalidSignature,
+
+  #[error(transparent)]
+  /// Thrown when the public key cannot be loaded
+  Key(#[from] jsonwebtoken::errors::Error),
+
+  #[error("invalid webhook payload: {0}")]
+  Payload(#[from] serde_json::Error),
+
+  #[cfg(feature = "reqwest")]
+  #[error(transparent)]
+  /// Thrown when the emitter fails to post a notification
+  Http(#[from] reqwest::Error),
+}
+
 #[derive(Debug, Error)]
 pub enum ClientError {
   #[error(transparent)]
@@ -19,9 +48,22 @@ pub enum ClientError {
   /// Thrown when Token fails
   TokenError(#[from] jsonwebtoken::errors::Error),
 
+  #[cfg(feature = "reqwest")]
   #[error(transparent)]
   /// Thrown when submitting a POST/GET request fails
   ReqwestError(#[from] reqwest::Error),
+
+  #[error("invalid client configuration: {0}")]
+  /// Thrown when an environment variable or config file value is missing or invalid
+  Config(String),
+
+  #[error(transparent)]
+  /// Thrown when the config or secret file cannot be read
+  Io(#[from] std::io::Error),
+
+  #[error(transparent)]
+  /// Thrown when the config file is not valid TOML
+  Toml(#[from] toml::de::Error),
 }
 
 #[derive(Debug, Error)]
@@ -30,10 +72,12 @@ pub enum FireblocksError {
   /// Thrown when JWT signing fails
   JwtError(#[from] jwt::JwtError),
 
-  #[error("Deserialization Error: {err}. Response: {text}")]
-  /// Serde JSON Error
-  SerdeJson { request_id: String, err: serde_json::Error, text: String },
+  #[error("Deserialization Error at {path}: {err}. Response: {snippet} request_id: {request_id}")]
+  /// Serde JSON Error. `path` is where in the body decoding failed, e.g. `accounts[3].assets[0].id`, and
+  /// `snippet` the raw body around that point
+  SerdeJson { request_id: String, path: String, err: serde_json::Error, snippet: String },
 
+  #[cfg(feature = "reqwest")]
   #[error(transparent)]
   /// Thrown when submitting a POST/GET request fails
   ReqwestError(#[from] reqwest::Error),
@@ -64,4 +108,127 @@ pub enum FireblocksError {
 
   #[error("Invalid Request Error: {text}. Code: {code} request_id: {request_id}")]
   InvalidRequest { request_id: String, code: u16, text: String },
+
+  #[error("transport error: {0}")]
+  Transport(Box<dyn std::error::Error + Send + Sync>),
+
+  #[error("client is shutting down, {path} was not sent")]
+  ShuttingDown { path: String },
+
+  #[error("{path} aborted at the shutdown deadline, it may have reached Fireblocks")]
+  Aborted { path: String },
+
+  #[error("{asset_id} contract call reverted in simulation, it was not submitted: {reason}")]
+  SimulationReverted { asset_id: String, reason: String },
+
+  #[error("{path} did not finish by the deadline, it may have reached Fireblocks")]
+  DeadlineExceeded { path: String },
+
+  #[error("no client for workspace {0}")]
+  UnknownWorkspace(String),
+}
+
+/// Bytes of the body kept in [`FireblocksError::SerdeJson`] on each side of the error
+const SNIPPET_CONTEXT: usize = 120;
+
+impl FireblocksError {
+  /// Deserialize a response body, reporting the JSON path and a snippet of the body on failure
+  pub(crate) fn decode<T: DeserializeOwned>(body: &[u8], request_id: &str, strictness: Strictness) -> Result<T, Self> {
+    let mut de = serde_json::Deserializer::from_slice(body);
+    let mut track = serde_path_to_error::Track::new();
+    let result = T::deserialize(Checked::new(serde_path_to_error::Deserializer::new(&mut de, &mut track), strictness));
+    match result {
+      Ok(t) => de.end().map(|()| t).map_err(|err| Self::serde_json(request_id, String::from("."), err, body)),
+      Err(err) => Err(Self::serde_json(request_id, track.path().to_string(), err, body)),
+    }
+  }
+
+  pub(crate) fn serde_json(request_id: &str, path: String, err: serde_json::Error, body: &[u8]) -> Self {
+    let snippet = snippet(body, err.line(), err.column());
+    Self::SerdeJson { request_id: request_id.to_string(), path, err, snippet }
+  }
+}
+
+/// The body around `line` and `column` (1-based, as reported by `serde_json`)
+fn snippet(body: &[u8], line: usize, column: usize) -> String {
+  let offset = body
+    .split(|b| *b == b'\n')
+    .take(line.saturating_sub(1))
+    .map(|l| l.len() + 1)
+    .sum::<usize>()
+    .saturating_add(column)
+    .min(body.len());
+  let start = offset.saturating_sub(SNIPPET_CONTEXT);
+  let end = offset.saturating_add(SNIPPET_CONTEXT).min(body.len());
+  let mut snippet = String::from_utf8_lossy(&body[start..end]).to_string();
+  if start > 0 {
+    snippet.insert_str(0, "...");
+  }
+  if end < body.len() {
+    snippet.push_str("...");
+  }
+  snippet
+}
+
+#[derive(Debug, Error)]
+pub enum SecretError {
+  #[cfg(feature = "reqwest")]
+  #[error(transparent)]
+  /// Thrown when the secret manager cannot be reached or rejects the request
+  Http(#[from] reqwest::Error),
+
+  #[error("unexpected secret manager response: {0}")]
+  Response(String),
+
+  #[error(transparent)]
+  /// Thrown when the fetched secret is not a valid RSA key
+  Client(#[from] ClientError),
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use serde_derive::Deserialize;
+
+  #[derive(Debug, Deserialize)]
+  #[allow(dead_code, clippy::upper_case_acronyms)]
+  enum Status {
+    COMPLETED,
+  }
+
+  #[derive(Debug, Deserialize)]
+  #[allow(dead_code)]
+  struct Tx {
+    id: String,
+    status: Status,
+  }
+
+  #[derive(Debug, Deserialize)]
+  #[allow(dead_code)]
+  struct Txs {
+    padding: String,
+    data: Vec<Tx>,
+  }
+
+  #[test]
+  fn test_decode_path() {
+    let body = format!(
+      r#"{{"padding": "{}", "data": [{{"id": "1", "status": "COMPLETED"}}, {{"id": "2", "status": "NEW_STATUS"}}]}}"#,
+      "x".repeat(500)
+    );
+    let err = FireblocksError::decode::<Txs>(body.as_bytes(), "req", Strictness::Lenient).expect_err("unknown status");
+    let FireblocksError::SerdeJson { path, snippet, request_id, .. } = &err else {
+      panic!("unexpected error {err}");
+    };
+    assert_eq!(path, "data[1].status");
+    assert_eq!(request_id, "req");
+    assert!(snippet.starts_with("...") && snippet.contains("NEW_STATUS"));
+    assert!(snippet.len() < 2 * SNIPPET_CONTEXT + 10);
+    assert!(err.to_string().contains("data[1].status"));
+
+    assert!(FireblocksError::decode::<Vec<Tx>>(b"[] x", "req", Strictness::Lenient).is_err());
+    assert!(
+      FireblocksError::decode::<Vec<Tx>>(br#"[{"id": "1", "status": "COMPLETED"}]"#, "req", Strictness::Strict).is_ok()
+    );
+  }
 }
@@ -0,0 +1,392 @@
+//! Fees paid, per asset, vault account and fee level over time windows
+//!
+//! [`FeeAnalytics`] consumes transactions, e.g. from
+//! [`PagedClient::transactions_from_source`](crate::PagedClient::transactions_from_source) or a
+//! [`Reconciler`](crate::reconcile::Reconciler), and sums the network and service fees of the final ones into a
+//! [`FeeReport`]. A fee is counted once per transaction, in the window of its last update, in the asset it was
+//! paid in (e.g. ETH for an ERC-20 transfer). [`FeeAnalytics::add_all_valued`] values each fee in fiat with a
+//! [`PriceOracle`] at the time it was paid, [`FeeReport::value`] values whole rows at the start of their window
+//! instead, which needs one price per row.
+//!
+//! ```
+//! use fireblocks_sdk::fees::{FeeAnalytics, FeeWindow};
+//! use fireblocks_sdk::price::PriceOracle;
+//! use fireblocks_sdk::types::Transaction;
+//! use futures::Stream;
+//!
+//! async fn monthly(
+//!   transactions: impl Stream<Item = Transaction>,
+//!   prices: &dyn PriceOracle,
+//! ) -> color_eyre::Result<()> {
+//!   let mut fees = FeeAnalytics::new(FeeWindow::Month);
+//!   fees.add_all_valued(transactions, prices).await?;
+//!   let report = fees.report();
+//!   for row in report.by_asset() {
+//!     println!("{} {} {} in {} transactions", row.window, row.asset_id, row.total(), row.transactions);
+//!   }
+//!   std::fs::write("fees.csv", report.to_csv())?;
+//!   Ok(())
+//! }
+//! ```
+use crate::csv;
+use crate::price::{self, PriceOracle};
+use crate::types::{FeeLevel, PeerType, Transaction};
+use crate::FireblocksError;
+use bigdecimal::{BigDecimal, Zero};
+use chrono::{DateTime, Datelike, Days, NaiveDate, Utc};
+use futures::{Stream, StreamExt};
+use std::collections::{BTreeMap, HashSet};
+
+/// Length of the windows fees are summed over
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FeeWindow {
+  Day,
+  /// ISO weeks, starting on Monday
+  Week,
+  #[default]
+  Month,
+}
+
+impl FeeWindow {
+  /// First day of the window holding `at`
+  pub fn start(self, at: DateTime<Utc>) -> NaiveDate {
+    let day = at.date_naive();
+    match self {
+      Self::Day => day,
+      Self::Week => day - Days::new(u64::from(day.weekday().num_days_from_monday())),
+      Self::Month => day.with_day(1).unwrap_or(day),
+    }
+  }
+}
+
+/// Fees of one window, asset, vault account and fee level, the last two are `None` in roll-ups or when
+/// unknown
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeRow {
+  pub window: NaiveDate,
+  pub asset_id: String,
+  pub vault: Option<String>,
+  pub fee_level: Option<FeeLevel>,
+  pub network_fee: BigDecimal,
+  pub service_fee: BigDecimal,
+  /// Both fees in fiat, `None` until valued or when a transaction or row rolled up is not
+  pub fiat_value: Option<BigDecimal>,
+  pub transactions: u64,
+}
+
+impl FeeRow {
+  pub fn total(&self) -> BigDecimal {
+    &self.network_fee + &self.service_fee
+  }
+}
+
+type FeeKey = (NaiveDate, String, Option<String>, Option<FeeLevel>);
+
+#[derive(Debug, Clone, Default)]
+struct Totals {
+  network_fee: BigDecimal,
+  service_fee: BigDecimal,
+  fiat_value: BigDecimal,
+  transactions: u64,
+  /// Transactions without a fiat value
+  unvalued: u64,
+}
+
+impl Totals {
+  fn add(&mut self, network_fee: &BigDecimal, service_fee: &BigDecimal, fiat_value: Option<&BigDecimal>, n: u64) {
+    self.network_fee += network_fee;
+    self.service_fee += service_fee;
+    self.transactions += n;
+    match fiat_value {
+      Some(v) => self.fiat_value += v,
+      None => self.unvalued += n,
+    }
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct FeeReport {
+  pub window: FeeWindow,
+  /// Ordered by window, asset, vault account and fee level
+  pub rows: Vec<FeeRow>,
+}
+
+impl FeeReport {
+  /// Fees per window and asset
+  pub fn by_asset(&self) -> Vec<FeeRow> {
+    self.roll_up(false, false)
+  }
+
+  /// Fees per window, asset and vault account
+  pub fn by_vault(&self) -> Vec<FeeRow> {
+    self.roll_up(true, false)
+  }
+
+  /// Fees per window, asset and fee level
+  pub fn by_fee_level(&self) -> Vec<FeeRow> {
+    self.roll_up(false, true)
+  }
+
+  /// Value the fees of each row in fiat at the start of its window, leaving `fiat_value` empty where `oracle`
+  /// has no price. Replaces values at transaction time from [`FeeAnalytics::add_valued`]
+  pub async fn value(&mut self, oracle: &dyn PriceOracle) -> Result<(), FireblocksError> {
+    for r in &mut self.rows {
+      let at = r.window.and_time(chrono::NaiveTime::MIN).and_utc();
+      r.fiat_value = price::fiat_value(oracle, &r.asset_id, &r.total(), at).await?;
+    }
+    Ok(())
+  }
+
+  /// Render the report as CSV: `window,asset,vault,fee_level,network_fee,service_fee,fiat_value,transactions`
+  pub fn to_csv(&self) -> String {
+    let header = ["window", "asset", "vault", "fee_level", "network_fee", "service_fee", "fiat_value", "transactions"];
+    let mut out = csv::line(header);
+    out.push('\n');
+    for r in &self.rows {
+      let level = r.fee_level.map(|l| l.to_string()).unwrap_or_default();
+      let fiat_value = r.fiat_value.as_ref().map(|v| v.normalized().to_plain_string()).unwrap_or_default();
+      out.push_str(&csv::line([
+        r.window.to_string().as_str(),
+        &r.asset_id,
+        r.vault.as_deref().unwrap_or_default(),
+        &level,
+        &r.network_fee.normalized().to_plain_string(),
+        &r.service_fee.normalized().to_plain_string(),
+        &fiat_value,
+        &r.transactions.to_string(),
+      ]));
+      out.push('\n');
+    }
+    out
+  }
+
+  fn roll_up(&self, vault: bool, fee_level: bool) -> Vec<FeeRow> {
+    let mut totals = BTreeMap::<FeeKey, Totals>::new();
+    for r in &self.rows {
+      let key = (r.window, r.asset_id.clone(), r.vault.clone().filter(|_| vault), r.fee_level.filter(|_| fee_level));
+      totals.entry(key).or_default().add(&r.network_fee, &r.service_fee, r.fiat_value.as_ref(), r.transactions);
+    }
+    rows(totals)
+  }
+}
+
+/// Sums the fees of transactions into a [`FeeReport`]
+#[derive(Debug, Clone, Default)]
+pub struct FeeAnalytics {
+  window: FeeWindow,
+  totals: BTreeMap<FeeKey, Totals>,
+  /// Transactions counted, a stream may repeat them
+  seen: HashSet<String>,
+}
+
+impl FeeAnalytics {
+  pub fn new(window: FeeWindow) -> Self {
+    Self { window, ..Default::default() }
+  }
+
+  /// Count the fees of `tx`, `false` when it is not final, has no fee or was counted before
+  pub fn add(&mut self, tx: &Transaction) -> bool {
+    let Some((key, network_fee, service_fee)) = self.fees(tx) else {
+      return false;
+    };
+    self.totals.entry(key).or_default().add(&network_fee, &service_fee, None, 1);
+    self.seen.insert(tx.id.clone());
+    true
+  }
+
+  /// [`add`](Self::add) `tx`, valuing its fees in fiat at its last update, the time they were paid
+  pub async fn add_valued(&mut self, tx: &Transaction, oracle: &dyn PriceOracle) -> Result<bool, FireblocksError> {
+    let Some((key, network_fee, service_fee)) = self.fees(tx) else {
+      return Ok(false);
+    };
+    let fiat_value = price::fiat_value(oracle, &key.1, &(&network_fee + &service_fee), tx.last_updated).await?;
+    self.totals.entry(key).or_default().add(&network_fee, &service_fee, fiat_value.as_ref(), 1);
+    self.seen.insert(tx.id.clone());
+    Ok(true)
+  }
+
+  /// Count the fees of every transaction of `transactions`, returns how many had fees counted
+  pub async fn add_all(&mut self, transactions: impl Stream<Item = Transaction>) -> usize {
+    let mut transactions = std::pin::pin!(transactions);
+    let mut counted = 0;
+    while let Some(tx) = transactions.next().await {
+      counted += usize::from(self.add(&tx));
+    }
+    counted
+  }
+
+  /// [`add_valued`](Self::add_valued) every transaction of `transactions`, returns how many had fees counted
+  pub async fn add_all_valued(
+    &mut self,
+    transactions: impl Stream<Item = Transaction>,
+    oracle: &dyn PriceOracle,
+  ) -> Result<usize, FireblocksError> {
+    let mut transactions = std::pin::pin!(transactions);
+    let mut counted = 0;
+    while let Some(tx) = transactions.next().await {
+      counted += usize::from(self.add_valued(&tx, oracle).await?);
+    }
+    Ok(counted)
+  }
+
+  pub fn report(&self) -> FeeReport {
+    FeeReport { window: self.window, rows: rows(self.totals.clone()) }
+  }
+
+  /// Key and network and service fees of `tx`, `None` when it does not count
+  fn fees(&self, tx: &Transaction) -> Option<(FeeKey, BigDecimal, BigDecimal)> {
+    if !tx.status.is_final() || self.seen.contains(&tx.id) {
+      return None;
+    }
+    let info = tx.fee_info.as_ref();
+    let network_fee = info.and_then(|f| f.network_fee.clone()).or_else(|| tx.network_fee.clone()).unwrap_or_default();
+    let service_fee = info.and_then(|f| f.service_fee.clone()).or_else(|| tx.service_fee.clone()).unwrap_or_default();
+    if network_fee.is_zero() && service_fee.is_zero() {
+      return None;
+    }
+    let vault = tx.source.as_ref().filter(|s| s.peer_type == PeerType::VAULT_ACCOUNT).and_then(|s| s.id.clone());
+    let key = (self.window.start(tx.last_updated), tx.fee_currency.to_string(), vault, tx.fee_level);
+    Some((key, network_fee, service_fee))
+  }
+}
+
+fn rows(totals: BTreeMap<FeeKey, Totals>) -> Vec<FeeRow> {
+  totals
+    .into_iter()
+    .map(|((window, asset_id, vault, fee_level), t)| FeeRow {
+      window,
+      asset_id,
+      vault,
+      fee_level,
+      network_fee: t.network_fee,
+      service_fee: t.service_fee,
+      fiat_value: (t.unvalued == 0).then_some(t.fiat_value),
+      transactions: t.transactions,
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::price::NoPrices;
+  use crate::types::{FeeInfo, TransactionStatus, TransferPeerPath};
+  use crate::Asset;
+  use chrono::TimeZone;
+  use futures::future::BoxFuture;
+  use std::str::FromStr;
+
+  fn tx(id: &str, vault: &str, level: FeeLevel, fee: &str, day: u32) -> color_eyre::Result<Transaction> {
+    Ok(Transaction {
+      id: String::from(id),
+      source: Some(TransferPeerPath { id: Some(String::from(vault)), ..Default::default() }),
+      fee_currency: Asset::new("ETH_TEST5"),
+      fee_level: Some(level),
+      fee_info: Some(FeeInfo { network_fee: Some(BigDecimal::from_str(fee)?), service_fee: None, gas_price: None }),
+      last_updated: Utc.with_ymd_and_hms(2026, 3, day, 12, 0, 0).single().unwrap_or_default(),
+      ..Default::default()
+    })
+  }
+
+  #[test]
+  fn test_window() {
+    let at = Utc.with_ymd_and_hms(2026, 10, 15, 23, 59, 0).single().unwrap_or_default();
+    assert_eq!(FeeWindow::Day.start(at).to_string(), "2026-10-15");
+    assert_eq!(FeeWindow::Week.start(at).to_string(), "2026-10-12");
+    assert_eq!(FeeWindow::Month.start(at).to_string(), "2026-10-01");
+  }
+
+  #[tokio::test]
+  async fn test_report() -> color_eyre::Result<()> {
+    let mut pending = tx("4", "0", FeeLevel::High, "1", 2)?;
+    pending.status = TransactionStatus::CONFIRMING;
+    let transactions = vec![
+      tx("1", "0", FeeLevel::Medium, "0.001", 2)?,
+      tx("2", "0", FeeLevel::High, "0.003", 20)?,
+      tx("3", "1", FeeLevel::Medium, "0.0005", 31)?,
+      tx("1", "0", FeeLevel::Medium, "0.001", 2)?,
+      tx("5", "1", FeeLevel::Low, "0", 3)?,
+      pending,
+    ];
+    let mut fees = FeeAnalytics::new(FeeWindow::Month);
+    assert_eq!(fees.add_all(futures::stream::iter(transactions)).await, 3);
+
+    let report = fees.report();
+    assert_eq!(report.rows.len(), 3);
+    let by_asset = report.by_asset();
+    assert_eq!(by_asset.len(), 1);
+    assert_eq!(by_asset[0].total(), BigDecimal::from_str("0.0045")?);
+    assert_eq!(by_asset[0].transactions, 3);
+    let by_vault = report.by_vault();
+    assert_eq!(by_vault.iter().map(|r| r.vault.as_deref()).collect::<Vec<_>>(), [Some("0"), Some("1")]);
+    assert_eq!(by_vault[0].network_fee, BigDecimal::from_str("0.004")?);
+    let by_level = report.by_fee_level();
+    assert_eq!(
+      by_level.iter().map(|r| r.fee_level).collect::<Vec<_>>(),
+      [Some(FeeLevel::Medium), Some(FeeLevel::High)]
+    );
+    assert_eq!(by_level[0].network_fee, BigDecimal::from_str("0.0015")?);
+    assert_eq!(report.to_csv().lines().nth(1), Some("2026-03-01,ETH_TEST5,0,MEDIUM,0.001,0,,1"));
+
+    let mut weekly = FeeAnalytics::new(FeeWindow::Week);
+    weekly.add(&tx("1", "0", FeeLevel::Medium, "0.001", 2)?);
+    weekly.add(&tx("2", "0", FeeLevel::Medium, "0.003", 20)?);
+    assert_eq!(weekly.report().by_asset().len(), 2);
+    Ok(())
+  }
+
+  /// ETH at 2000 until March 10, unknown afterwards
+  struct History;
+
+  impl PriceOracle for History {
+    fn price<'a>(
+      &'a self,
+      _asset_id: &'a str,
+      at: DateTime<Utc>,
+    ) -> BoxFuture<'a, Result<Option<BigDecimal>, FireblocksError>> {
+      let price = (at.day() < 10).then(|| BigDecimal::from(2000));
+      Box::pin(async move { Ok(price) })
+    }
+  }
+
+  #[tokio::test]
+  async fn test_value() -> color_eyre::Result<()> {
+    let mut fees = FeeAnalytics::new(FeeWindow::Week);
+    fees.add(&tx("1", "0", FeeLevel::Medium, "0.001", 2)?);
+    fees.add(&tx("2", "1", FeeLevel::Medium, "0.002", 3)?);
+    fees.add(&tx("3", "1", FeeLevel::High, "0.003", 20)?);
+    let mut report = fees.report();
+    report.value(&History).await?;
+    // valued on Monday March 2 and 16
+    assert_eq!(
+      report.rows.iter().map(|r| r.fiat_value.clone()).collect::<Vec<_>>(),
+      [Some(BigDecimal::from(2)), Some(BigDecimal::from(4)), None]
+    );
+    assert_eq!(report.by_fee_level()[0].fiat_value, Some(BigDecimal::from(6)));
+    assert_eq!(report.by_asset()[1].fiat_value, None);
+    assert_eq!(report.to_csv().lines().nth(1), Some("2026-03-02,ETH_TEST5,0,MEDIUM,0.001,0,2,1"));
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn test_add_valued() -> color_eyre::Result<()> {
+    let transactions = vec![
+      tx("1", "0", FeeLevel::Medium, "0.001", 2)?,
+      tx("2", "0", FeeLevel::Medium, "0.002", 9)?,
+      tx("1", "0", FeeLevel::Medium, "0.001", 2)?,
+      tx("3", "1", FeeLevel::Medium, "0.003", 12)?,
+    ];
+    let mut fees = FeeAnalytics::new(FeeWindow::Month);
+    assert_eq!(fees.add_all_valued(futures::stream::iter(transactions), &History).await?, 3);
+    // valued on March 2 and 9, March 12 has no price
+    let report = fees.report();
+    assert_eq!(report.rows.iter().map(|r| r.fiat_value.clone()).collect::<Vec<_>>(), [Some(BigDecimal::from(6)), None]);
+    assert_eq!(report.by_asset()[0].fiat_value, None);
+    assert_eq!(report.by_asset()[0].transactions, 3);
+
+    let mut fees = FeeAnalytics::new(FeeWindow::Month);
+    assert!(fees.add_valued(&tx("4", "0", FeeLevel::High, "0.004", 31)?, &NoPrices).await?);
+    assert_eq!(fees.report().rows[0].fiat_value, None);
+    Ok(())
+  }
+}
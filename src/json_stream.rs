@@ -0,0 +1,228 @@
+use crate::decode::Strictness;
+use crate::transport::Body;
+use crate::FireblocksError;
+use futures::{Stream, StreamExt};
+use serde::de::DeserializeOwned;
+use std::collections::VecDeque;
+use std::pin::Pin;
+
+/// Items of a JSON array deserialized one by one as the response body arrives, so only a single item
+/// is held in memory rather than the whole body
+pub type ItemStream<T> = Pin<Box<dyn Stream<Item = Result<T, FireblocksError>> + Send>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+  /// Looking for the start of the array
+  Seek,
+  Items,
+  Done,
+}
+
+/// Splits the raw bytes of one JSON array into its items without parsing them
+#[derive(Debug)]
+pub struct ArrayScanner {
+  /// Key of the array in the top level object, or `None` when the body is the array
+  field: Option<Vec<u8>>,
+  phase: Phase,
+  depth: usize,
+  items_depth: usize,
+  in_string: bool,
+  escaped: bool,
+  key: Vec<u8>,
+  matched: bool,
+  item: Vec<u8>,
+}
+
+impl ArrayScanner {
+  pub fn new(field: Option<&str>) -> Self {
+    Self {
+      field: field.map(|f| f.as_bytes().to_vec()),
+      phase: Phase::Seek,
+      depth: 0,
+      items_depth: 0,
+      in_string: false,
+      escaped: false,
+      key: vec![],
+      matched: false,
+      item: vec![],
+    }
+  }
+
+  /// Feed the next chunk of the body, complete items are appended to `items`
+  pub fn push(&mut self, chunk: &[u8], items: &mut VecDeque<Vec<u8>>) {
+    for &b in chunk {
+      match self.phase {
+        Phase::Seek => self.seek(b),
+        Phase::Items => self.scan_item(b, items),
+        Phase::Done => return,
+      }
+    }
+  }
+
+  /// True once the closing bracket of the array was seen
+  pub const fn is_done(&self) -> bool {
+    matches!(self.phase, Phase::Done)
+  }
+
+  const fn string_byte(&mut self, b: u8) {
+    if self.escaped {
+      self.escaped = false;
+    } else if b == b'\\' {
+      self.escaped = true;
+    } else if b == b'"' {
+      self.in_string = false;
+    }
+  }
+
+  fn seek(&mut self, b: u8) {
+    if self.in_string {
+      self.string_byte(b);
+      if self.in_string && self.depth == 1 {
+        self.key.push(b);
+      }
+      return;
+    }
+    match b {
+      b'"' => {
+        self.in_string = true;
+        self.key.clear();
+      },
+      b':' if self.depth == 1 => self.matched = self.field.as_ref() == Some(&self.key),
+      b'[' if (self.field.is_none() && self.depth == 0) || (self.depth == 1 && self.matched) => {
+        self.depth += 1;
+        self.items_depth = self.depth;
+        self.phase = Phase::Items;
+      },
+      b'{' | b'[' => {
+        self.depth += 1;
+        self.matched = false;
+      },
+      b'}' | b']' => self.depth = self.depth.saturating_sub(1),
+      b',' => self.matched = false,
+      _ => {},
+    }
+  }
+
+  fn scan_item(&mut self, b: u8, items: &mut VecDeque<Vec<u8>>) {
+    if self.in_string {
+      self.item.push(b);
+      self.string_byte(b);
+      return;
+    }
+    match b {
+      b'"' => {
+        self.in_string = true;
+        self.item.push(b);
+      },
+      b'{' | b'[' => {
+        self.depth += 1;
+        self.item.push(b);
+      },
+      b'}' | b']' if self.depth == self.items_depth => {
+        self.flush(items);
+        self.phase = Phase::Done;
+      },
+      b'}' | b']' => {
+        self.depth -= 1;
+        self.item.push(b);
+      },
+      b',' if self.depth == self.items_depth => self.flush(items),
+      b if b.is_ascii_whitespace() && self.item.is_empty() => {},
+      b => self.item.push(b),
+    }
+  }
+
+  fn flush(&mut self, items: &mut VecDeque<Vec<u8>>) {
+    let mut item = std::mem::take(&mut self.item);
+    while item.last().is_some_and(u8::is_ascii_whitespace) {
+      item.pop();
+    }
+    if !item.is_empty() {
+      items.push_back(item);
+    }
+  }
+}
+
+struct StreamState {
+  response: Option<Body>,
+  scanner: ArrayScanner,
+  items: VecDeque<Vec<u8>>,
+  request_id: String,
+  strictness: Strictness,
+}
+
+pub fn item_stream<T>(response: Body, field: Option<&str>, request_id: String, strictness: Strictness) -> ItemStream<T>
+where
+  T: DeserializeOwned + Send + 'static,
+{
+  let state = StreamState {
+    response: Some(response),
+    scanner: ArrayScanner::new(field),
+    items: VecDeque::new(),
+    request_id,
+    strictness,
+  };
+  Box::pin(futures::stream::unfold(state, |mut state| async move {
+    loop {
+      if let Some(item) = state.items.pop_front() {
+        let result = FireblocksError::decode::<T>(&item, &state.request_id, state.strictness);
+        return Some((result, state));
+      }
+      if state.scanner.is_done() {
+        return None;
+      }
+      let response = state.response.as_mut()?;
+      match response.next().await.transpose() {
+        Ok(Some(chunk)) => state.scanner.push(&chunk, &mut state.items),
+        Ok(None) => {
+          state.response = None;
+          let err = <serde_json::Error as serde::de::Error>::custom("response ended before the end of the array");
+          return Some((Err(FireblocksError::serde_json(&state.request_id, String::new(), err, &[])), state));
+        },
+        Err(e) => {
+          state.response = None;
+          return Some((Err(e), state));
+        },
+      }
+    }
+  }))
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn scan(field: Option<&str>, body: &str) -> (Vec<String>, bool) {
+    let mut scanner = ArrayScanner::new(field);
+    let mut items = VecDeque::new();
+    // one byte at a time to cross every possible chunk boundary
+    for b in body.as_bytes() {
+      scanner.push(&[*b], &mut items);
+    }
+    (items.into_iter().map(|i| String::from_utf8_lossy(&i).to_string()).collect(), scanner.is_done())
+  }
+
+  #[test]
+  fn test_top_level_array() {
+    let (items, done) = scan(None, r#" [ {"a": [1, 2], "b": "x]\"}"} , 3, "s,t" ,{}] trailing"#);
+    assert!(done);
+    assert_eq!(items, vec![r#"{"a": [1, 2], "b": "x]\"}"}"#, "3", r#""s,t""#, "{}"]);
+    let (items, done) = scan(None, "[]");
+    assert!(done && items.is_empty());
+  }
+
+  #[test]
+  fn test_field_array() -> color_eyre::Result<()> {
+    let body = r#"{"cursor": "data", "nested": {"data": [9]}, "data": [{"id": 1}, {"id": 2, "data": []}], "total": 2}"#;
+    let (items, done) = scan(Some("data"), body);
+    assert!(done);
+    assert_eq!(items.len(), 2);
+    let second: serde_json::Value = serde_json::from_str(&items[1])?;
+    assert_eq!(second["id"], 2);
+
+    let (items, done) = scan(Some("data"), r#"{"data": [{"id": 1}"#);
+    assert!(!done);
+    assert!(items.is_empty());
+    Ok(())
+  }
+}
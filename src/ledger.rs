@@ -0,0 +1,294 @@
+//! Per-customer balances of omnibus vault accounts
+//!
+//! An exchange holds the funds of its customers in shared (omnibus) vault accounts and keeps a
+//! sub-ledger of who owns what. The [`Ledger`] attributes completed transactions of these vaults to
+//! customers by their `customerRefId`:
+//! * a deposit by the customer of its deposit address (and tag), see [`Ledger::load_addresses`]
+//! * a withdrawal by the `customerRefId` it was created with
+//!
+//! Balances live in a [`LedgerStore`], a transaction is applied once however often it is recorded, and
+//! flows that cannot be attributed are reported as [`LedgerOutcome::Unattributed`] for review.
+//!
+//! ```
+//! use fireblocks_sdk::ledger::{Ledger, LedgerOutcome, LedgerStore, MemoryLedgerStore};
+//! use fireblocks_sdk::{Client, ASSET_BTC_TEST};
+//! use fireblocks_sdk::types::Transaction;
+//! use futures::{Stream, StreamExt};
+//!
+//! async fn bookkeeping(c: &Client, transactions: impl Stream<Item = Transaction>) -> color_eyre::Result<()> {
+//!   let mut ledger = Ledger::new(MemoryLedgerStore::default(), [0]);
+//!   ledger.load_addresses(c, 0, ASSET_BTC_TEST).await?;
+//!   let mut outcomes = std::pin::pin!(ledger.record_all(transactions));
+//!   while let Some(outcome) = outcomes.next().await {
+//!     if let LedgerOutcome::Unattributed(flow) = outcome? {
+//!       tracing::warn!("cannot attribute {} {:?}: {}", flow.transaction_id, flow.kind, flow.reason);
+//!     }
+//!   }
+//!   println!("{:?}", ledger.store().balances("customer-1").await?);
+//!   Ok(())
+//! }
+//! ```
+use crate::error::StoreError;
+use crate::types::{PeerType, Transaction, TransactionStatus, TransferPeerPath};
+use crate::{Client, FireblocksError};
+use bigdecimal::BigDecimal;
+use futures::{Stream, StreamExt};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{Debug, Display};
+use std::future::Future;
+use std::sync::{Mutex, PoisonError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlowKind {
+  Deposit,
+  Withdrawal,
+}
+
+/// A transaction attributed to a customer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LedgerEntry {
+  pub transaction_id: String,
+  pub customer_ref_id: String,
+  pub asset_id: String,
+  pub kind: FlowKind,
+  /// Positive for deposits, negative for withdrawals
+  pub amount: BigDecimal,
+}
+
+/// A flow of an omnibus vault without a customer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnattributedFlow {
+  pub transaction_id: String,
+  pub asset_id: String,
+  pub kind: FlowKind,
+  pub amount: BigDecimal,
+  pub reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LedgerOutcome {
+  Applied(LedgerEntry),
+  /// The transaction was applied before
+  Duplicate(LedgerEntry),
+  Unattributed(UnattributedFlow),
+  /// Not completed, or not moving funds in or out of the omnibus vaults
+  Ignored,
+}
+
+/// Keeps the customer balances and which transactions were applied to them
+pub trait LedgerStore: Send + Sync {
+  /// Add `entry` to the balance of its customer, returning `false` if its transaction was already applied
+  fn apply(&self, entry: &LedgerEntry) -> impl Future<Output = Result<bool, StoreError>> + Send;
+  fn balance(
+    &self,
+    customer_ref_id: &str,
+    asset_id: &str,
+  ) -> impl Future<Output = Result<BigDecimal, StoreError>> + Send;
+  /// Balances of a customer by asset id
+  fn balances(
+    &self,
+    customer_ref_id: &str,
+  ) -> impl Future<Output = Result<BTreeMap<String, BigDecimal>, StoreError>> + Send;
+}
+
+#[derive(Debug, Default)]
+struct Books {
+  applied: HashSet<String>,
+  /// customer -> asset -> balance
+  balances: HashMap<String, BTreeMap<String, BigDecimal>>,
+}
+
+/// In-memory [`LedgerStore`], for tests or rebuilding balances from the transaction history
+#[derive(Debug, Default)]
+pub struct MemoryLedgerStore {
+  books: Mutex<Books>,
+}
+
+impl LedgerStore for MemoryLedgerStore {
+  async fn apply(&self, entry: &LedgerEntry) -> Result<bool, StoreError> {
+    let mut books = self.books.lock().unwrap_or_else(PoisonError::into_inner);
+    if !books.applied.insert(entry.transaction_id.clone()) {
+      return Ok(false);
+    }
+    let customer = books.balances.entry(entry.customer_ref_id.clone()).or_default();
+    *customer.entry(entry.asset_id.clone()).or_default() += &entry.amount;
+    drop(books);
+    Ok(true)
+  }
+
+  async fn balance(&self, customer_ref_id: &str, asset_id: &str) -> Result<BigDecimal, StoreError> {
+    let books = self.books.lock().unwrap_or_else(PoisonError::into_inner);
+    Ok(books.balances.get(customer_ref_id).and_then(|b| b.get(asset_id)).cloned().unwrap_or_default())
+  }
+
+  async fn balances(&self, customer_ref_id: &str) -> Result<BTreeMap<String, BigDecimal>, StoreError> {
+    let books = self.books.lock().unwrap_or_else(PoisonError::into_inner);
+    Ok(books.balances.get(customer_ref_id).cloned().unwrap_or_default())
+  }
+}
+
+pub struct Ledger<S = MemoryLedgerStore> {
+  store: S,
+  /// Ids of the omnibus vault accounts
+  vaults: HashSet<String>,
+  /// (address, tag) -> customerRefId
+  addresses: HashMap<(String, Option<String>), String>,
+}
+
+impl<S: LedgerStore> Ledger<S> {
+  pub fn new(store: S, vaults: impl IntoIterator<Item = i32>) -> Self {
+    Self { store, vaults: vaults.into_iter().map(|v| v.to_string()).collect(), addresses: HashMap::new() }
+  }
+
+  pub const fn store(&self) -> &S {
+    &self.store
+  }
+
+  /// Attribute deposits to `address`, with `tag` on chains sharing one address between customers
+  pub fn insert_address(&mut self, address: &str, tag: Option<&str>, customer_ref_id: &str) {
+    let key = (String::from(address), tag.filter(|t| !t.is_empty()).map(String::from));
+    self.addresses.insert(key, String::from(customer_ref_id));
+  }
+
+  /// Attribute deposits to the addresses of `asset_id` in vault account `vault` with a `customerRefId`,
+  /// returning how many there are
+  pub async fn load_addresses<T>(&mut self, client: &Client, vault: i32, asset_id: T) -> Result<usize, FireblocksError>
+  where
+    T: AsRef<str> + Display + Debug,
+  {
+    let (addresses, _) = client.addresses(vault, asset_id).await?;
+    let mut loaded = 0;
+    for address in addresses {
+      if let Some(customer) = address.customer_ref_id.as_deref().filter(|c| !c.is_empty()) {
+        self.insert_address(&address.address, address.tag.as_deref(), customer);
+        loaded += 1;
+      }
+    }
+    Ok(loaded)
+  }
+
+  /// Apply a transaction to the balance of its customer
+  pub async fn record(&self, tx: &Transaction) -> Result<LedgerOutcome, StoreError> {
+    if tx.status != TransactionStatus::COMPLETED {
+      return Ok(LedgerOutcome::Ignored);
+    }
+    let kind = match (self.is_omnibus(tx.source.as_ref()), self.is_omnibus(tx.destination.as_ref())) {
+      (false, true) => FlowKind::Deposit,
+      (true, false) => FlowKind::Withdrawal,
+      _ => return Ok(LedgerOutcome::Ignored),
+    };
+    let amount = tx.amount.clone().unwrap_or_default();
+    let customer = match kind {
+      FlowKind::Deposit => self.depositor(tx),
+      FlowKind::Withdrawal => {
+        tx.customer_ref_id.clone().filter(|c| !c.is_empty()).ok_or_else(|| String::from("no customerRefId"))
+      },
+    };
+    let customer_ref_id = match customer {
+      Ok(customer) => customer,
+      Err(reason) => {
+        return Ok(LedgerOutcome::Unattributed(UnattributedFlow {
+          transaction_id: tx.id.clone(),
+          asset_id: tx.asset_id.to_string(),
+          kind,
+          amount,
+          reason,
+        }));
+      },
+    };
+    let entry = LedgerEntry {
+      transaction_id: tx.id.clone(),
+      customer_ref_id,
+      asset_id: tx.asset_id.to_string(),
+      kind,
+      amount: if kind == FlowKind::Withdrawal { -amount } else { amount },
+    };
+    Ok(if self.store.apply(&entry).await? { LedgerOutcome::Applied(entry) } else { LedgerOutcome::Duplicate(entry) })
+  }
+
+  /// [`Ledger::record`] every transaction of a stream, e.g. the updates of a
+  /// [`Reconciler`](crate::reconcile::Reconciler)
+  pub fn record_all<'a>(
+    &'a self,
+    transactions: impl Stream<Item = Transaction> + 'a,
+  ) -> impl Stream<Item = Result<LedgerOutcome, StoreError>> + 'a {
+    transactions.then(move |tx| async move { self.record(&tx).await })
+  }
+
+  fn is_omnibus(&self, peer: Option<&TransferPeerPath>) -> bool {
+    peer.is_some_and(|p| {
+      p.peer_type == PeerType::VAULT_ACCOUNT && p.id.as_ref().is_some_and(|id| self.vaults.contains(id))
+    })
+  }
+
+  fn depositor(&self, tx: &Transaction) -> Result<String, String> {
+    let Some(address) = tx.destination_address.clone().filter(|a| !a.is_empty()) else {
+      return Err(String::from("no destination address"));
+    };
+    let tag = tx.destination_tag.clone().filter(|t| !t.is_empty());
+    let tagged = tag.is_some();
+    self
+      .addresses
+      .get(&(address.clone(), tag))
+      // a deposit to a customer's own address may still carry a tag
+      .or_else(|| if tagged { self.addresses.get(&(address.clone(), None)) } else { None })
+      .cloned()
+      .ok_or_else(|| format!("no customer for address {address}"))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use std::str::FromStr;
+
+  fn transfer(id: &str, from: Option<&str>, to: Option<&str>, amount: &str) -> color_eyre::Result<Transaction> {
+    let peer = |vault: Option<&str>| TransferPeerPath {
+      id: Some(vault.unwrap_or("ext").to_string()),
+      peer_type: if vault.is_some() { PeerType::VAULT_ACCOUNT } else { PeerType::EXTERNAL_WALLET },
+      ..Default::default()
+    };
+    Ok(Transaction {
+      id: String::from(id),
+      asset_id: crate::Asset::new("XRP"),
+      status: TransactionStatus::COMPLETED,
+      source: Some(peer(from)),
+      destination: Some(peer(to)),
+      amount: Some(BigDecimal::from_str(amount)?),
+      destination_address: Some(String::from("rOmnibus")),
+      ..Default::default()
+    })
+  }
+
+  #[tokio::test]
+  async fn test_attribution() -> color_eyre::Result<()> {
+    let mut ledger = Ledger::new(MemoryLedgerStore::default(), [0, 1]);
+    ledger.insert_address("rOmnibus", Some("7"), "alice");
+
+    let mut deposit = transfer("d1", None, Some("0"), "10")?;
+    deposit.destination_tag = Some(String::from("7"));
+    assert!(matches!(ledger.record(&deposit).await?, LedgerOutcome::Applied(e) if e.customer_ref_id == "alice"));
+    assert!(matches!(ledger.record(&deposit).await?, LedgerOutcome::Duplicate(_)));
+
+    let mut withdrawal = transfer("w1", Some("1"), None, "4")?;
+    assert!(
+      matches!(ledger.record(&withdrawal).await?, LedgerOutcome::Unattributed(f) if f.kind == FlowKind::Withdrawal)
+    );
+    withdrawal.customer_ref_id = Some(String::from("alice"));
+    assert!(matches!(ledger.record(&withdrawal).await?, LedgerOutcome::Applied(e) if e.amount == -4));
+
+    let untagged = transfer("d2", None, Some("0"), "1")?;
+    assert!(matches!(ledger.record(&untagged).await?, LedgerOutcome::Unattributed(f) if f.kind == FlowKind::Deposit));
+    let internal = transfer("i1", Some("0"), Some("1"), "3")?;
+    assert_eq!(ledger.record(&internal).await?, LedgerOutcome::Ignored);
+    let pending = Transaction { status: TransactionStatus::SUBMITTED, ..transfer("d3", None, Some("0"), "1")? };
+    assert_eq!(ledger.record(&pending).await?, LedgerOutcome::Ignored);
+
+    assert_eq!(ledger.store().balance("alice", "XRP").await?, BigDecimal::from(6));
+    assert_eq!(ledger.store().balances("bob").await?.len(), 0);
+
+    let outcomes = ledger.record_all(futures::stream::iter([deposit, untagged])).collect::<Vec<_>>().await;
+    assert_eq!(outcomes.len(), 2);
+    Ok(())
+  }
+}
@@ -1,18 +1,50 @@
 #![doc = include_str!("../README.md")]
 use chrono::{DateTime, Utc};
+pub mod address_book;
 pub mod api;
 mod assets;
+pub mod cache;
+pub mod chain;
 mod client;
+pub mod config;
+pub mod cosigner;
+mod csv;
+pub mod deadline;
+pub mod decimals;
+pub mod decode;
+pub mod disbursement;
 pub mod error;
+pub mod fees;
+mod json_stream;
 pub(crate) mod jwt;
+pub mod ledger;
+pub mod meta;
+#[cfg(test)]
+mod mock;
+mod page;
 mod paged_client;
+pub mod price;
+pub mod queue;
+pub mod rate;
+pub mod reconcile;
+pub mod scheduler;
+pub mod secrets;
+mod shutdown;
+pub mod simulate;
+pub mod snapshot;
+pub mod transport;
 pub mod types;
+pub mod webhook;
+pub mod workspace;
 
 pub use crate::error::*;
 pub use crate::types::PagingVaultRequestBuilder;
 pub use assets::{Asset, ASSET_BTC, ASSET_BTC_TEST, ASSET_ETH, ASSET_ETH_TEST, ASSET_SOL, ASSET_SOL_TEST};
 pub use client::{Client, ClientBuilder};
-pub use paged_client::{PagedClient, VaultStream};
+pub use decode::Strictness;
+pub use json_stream::ItemStream;
+pub use page::Page;
+pub use paged_client::{PagedClient, SmartTransferStream, VaultStream};
 
 pub const FIREBLOCKS_API: &str = "https://api.fireblocks.io/v1";
 pub const FIREBLOCKS_SANDBOX_API: &str = "https://sandbox-api.fireblocks.io/v1";
@@ -43,7 +75,7 @@ macro_rules! impl_base_query_params {
   };
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "reqwest"))]
 mod tests {
   use std::str::FromStr;
   use std::sync::{Arc, Once, OnceLock};
@@ -141,23 +173,32 @@ mod tests {
     let params = PagingVaultRequestBuilder::new().build()?;
     let (results, id) = config.client().vaults(params).await?;
     assert!(!id.is_empty());
-    assert!(!results.accounts.is_empty());
+    assert!(!results.items.is_empty());
 
     let params = PagingVaultRequestBuilder::new().min_threshold(&BigDecimal::from_str("1000000.00")?).build()?;
     let (results, id) = config.client().vaults(params).await?;
     assert!(!id.is_empty());
-    assert!(results.accounts.is_empty());
+    assert!(results.items.is_empty());
 
     let params = PagingVaultRequestBuilder::new().limit(1).build()?;
     let (results, id) = config.client().vaults(params).await?;
     assert!(!id.is_empty());
-    assert_eq!(1, results.accounts.len());
+    assert_eq!(1, results.items.len());
+    if let Some(next) = results.next(&config.client()).await {
+      let (next, _) = next?;
+      assert_eq!(1, next.items.len());
+      assert!(next.has_prev());
+    }
 
     let (result, id) = config.client().vault(0).await?;
     assert!(!id.is_empty());
     assert_eq!(0, result.id);
     assert!(!result.assets.is_empty());
 
+    let (result, responses) = crate::meta::capture(config.client().vault(0)).await;
+    assert_eq!(responses.len(), 1);
+    assert_eq!(responses[0].request_id, result?.1);
+
     let _ = PagingVaultRequestBuilder::new().before("before").build(); // code coverage
     Ok(())
   }
@@ -170,13 +211,13 @@ mod tests {
     }
     let params = PagingVaultRequestBuilder::new().name_prefix("Default").build()?;
     let results = config.client().vaults(params).await?.0;
-    assert!(!results.accounts.is_empty());
-    assert_eq!(results.accounts[0].name, "Default");
+    assert!(!results.items.is_empty());
+    assert_eq!(results.items[0].name, "Default");
 
     let params = PagingVaultRequestBuilder::new().name_suffix("Default").build()?;
     let results = config.client().vaults(params).await?.0;
-    assert!(!results.accounts.is_empty());
-    assert_eq!(results.accounts[0].name, "Default");
+    assert!(!results.items.is_empty());
+    assert_eq!(results.items[0].name, "Default");
     Ok(())
   }
 
@@ -197,6 +238,9 @@ mod tests {
     assert!(!assets.is_empty());
     let found = assets.iter().find(|a| a.id == ASSET_BTC_TEST);
     assert!(found.is_some());
+
+    let decimals = c.asset_decimals().await?.0;
+    assert_eq!(decimals.decimals(ASSET_BTC_TEST.as_ref()), Some(8));
     Ok(())
   }
 
@@ -244,7 +288,7 @@ mod tests {
       return Ok(());
     }
     let c = config.client();
-    let transactions = c.transactions(options).await?.0;
+    let transactions = c.transactions(options).await?.0.items;
     assert!(!transactions.is_empty());
     let tx_id = &transactions[0].id;
     let resp = c.get_transaction(tx_id).await?.0;
@@ -260,7 +304,7 @@ mod tests {
     }
     let c = config.client();
     let results = c.assets(Vec::<(String, String)>::new()).await?.0;
-    assert!(!results.asset_wallets.is_empty());
+    assert!(!results.items.is_empty());
     Ok(())
   }
 
@@ -298,7 +342,7 @@ mod tests {
     let page = PagingAddressRequestBuilder::new().limit(10).build()?;
     let (container, id) = c.addresses_paginated(result.id, "SOL_TEST", page).await?;
     assert!(!id.is_empty());
-    assert_eq!(1, container.addresses.len());
+    assert_eq!(1, container.items.len());
 
     let rename = format!("{vault_name}-rename");
     c.rename_vault(result.id, &rename).await?;
@@ -460,7 +504,11 @@ mod tests {
       amount: "0.001".to_string(),
       gas_price: None,
       gas_limit: None,
+      fee_level: None,
       note: "created by fireblocks-sdk for rust".to_string(),
+      extra_parameters: None,
+      destinations: None,
+      external_tx_id: None,
     };
 
     c.estimate_fee_transaction(args).await?;
@@ -505,6 +553,8 @@ mod tests {
     }
     let c = config.client();
     c.wallet_connections().await?;
+    let params = connect::WalletConnectionListBuilder::new().vault(0).page_size(5).build()?;
+    c.wallet_connections_list(params).await?;
     if let Err(e) = c.wallet_connection_delete("wallet-connect-id").await {
       assert!(e.to_string().contains("wallet-connect-id not found"));
     }
@@ -526,10 +576,13 @@ mod tests {
     assert!(!chains.is_empty());
     c.staking_positions().await?;
     c.staking_positions_summary().await?;
+    c.staking_positions_summary_vaults().await?;
+    c.staking_positions_chain(ASSET_SOL_TEST).await?;
     let providers = c.staking_providers().await?.0;
     assert!(!providers.is_empty());
     for p in providers {
       c.staking_accept_terms(&p.id).await?;
+      assert!(c.staking_provider(&p.id).await?.0.is_some());
     }
 
     for chain in [ASSET_SOL, ASSET_SOL_TEST, ASSET_ETH, ASSET_ETH_TEST] {
@@ -538,6 +591,34 @@ mod tests {
     Ok(())
   }
 
+  #[rstest::rstest]
+  #[tokio::test]
+  async fn test_tokenization(config: Config) -> color_eyre::Result<()> {
+    if !config.is_ok() {
+      return Ok(());
+    }
+    let c = config.client();
+    let params = TokenLinkListBuilder::new().page_size(5).build()?;
+    c.token_links(params).await?;
+    if let Err(e) = c.token_link("token-link-id").await {
+      assert!(e.to_string().contains("token-link-id"), "{}", e.to_string());
+    }
+    Ok(())
+  }
+
+  #[rstest::rstest]
+  #[tokio::test]
+  async fn test_nfts(config: Config) -> color_eyre::Result<()> {
+    if !config.is_ok() {
+      return Ok(());
+    }
+    let c = config.client();
+    let params = NftOwnershipListBuilder::new().vault_account_ids(&[0]).page_size(5).build()?;
+    c.nfts_owned(&params).await?;
+    c.nfts_owned_collections(&params).await?;
+    Ok(())
+  }
+
   #[rstest::rstest]
   #[tokio::test]
   async fn test_internal_wallets(config: Config) -> color_eyre::Result<()> {
@@ -560,9 +641,34 @@ mod tests {
     let mut vs = pc.vaults(100);
 
     while let Ok(Some(result)) = vs.try_next().await {
-      tracing::info!("accounts {}", result.0.accounts.len());
+      tracing::info!("accounts {}", result.0.items.len());
       time::sleep(Duration::from_millis(200)).await;
     }
+
+    let (newest, _) = pc.vaults(2).order(false).try_next().await?.ok_or_else(|| format_err!("no vaults"))?;
+    if let Some(next) = newest.next_cursor() {
+      let (page, _) = pc.client.vaults(PagingVaultRequestBuilder::new().limit(2).after(next).build()?).await?;
+      let before = page.prev_cursor().ok_or_else(|| format_err!("no before cursor"))?;
+      let mut back = pc.vaults(2).before(before);
+      let (first, _) = back.try_next().await?.ok_or_else(|| format_err!("no page before"))?;
+      assert_eq!(first.items.first().map(|a| a.id), newest.items.first().map(|a| a.id));
+    }
+    Ok(())
+  }
+
+  #[rstest::rstest]
+  #[tokio::test]
+  async fn test_paged_smart_transfers(config: Config) -> color_eyre::Result<()> {
+    if !config.is_ok() {
+      return Ok(());
+    }
+    let c = config.client();
+    let pc = PagedClient::new(Arc::new(c));
+    let filter = SmartTransferListBuilder::new().statuses(&[SmartTransferStatus::OPEN]).build()?;
+    let mut ts = pc.smart_transfer_tickets(filter, 10);
+    while let Some(result) = ts.try_next().await? {
+      tracing::info!("tickets {}", result.0.items.len());
+    }
     Ok(())
   }
 
@@ -620,6 +726,99 @@ mod tests {
     transaction_stream(ts).await
   }
 
+  #[rstest::rstest]
+  #[tokio::test]
+  async fn test_users(config: Config) -> color_eyre::Result<()> {
+    if !config.is_ok() {
+      return Ok(());
+    }
+    let c = config.client();
+    let (users, _) = c.users().await?;
+    assert!(!users.is_empty());
+    Ok(())
+  }
+
+  #[rstest::rstest]
+  #[tokio::test]
+  async fn test_audit_logs(config: Config) -> color_eyre::Result<()> {
+    if !config.is_ok() {
+      return Ok(());
+    }
+    let c = config.client();
+    let params = AuditLogListBuilder::new().time_period(AuditTimePeriod::DAY).build()?;
+    c.audit_logs(params).await?;
+    Ok(())
+  }
+
+  #[rstest::rstest]
+  #[tokio::test]
+  async fn test_ota(config: Config) -> color_eyre::Result<()> {
+    if !config.is_ok() {
+      return Ok(());
+    }
+    let c = config.client();
+    c.ota_status().await?;
+    Ok(())
+  }
+
+  #[rstest::rstest]
+  #[tokio::test]
+  async fn test_jobs(config: Config) -> color_eyre::Result<()> {
+    if !config.is_ok() {
+      return Ok(());
+    }
+    let c = config.client();
+    let now = chrono::Utc::now();
+    let (jobs, _) = c.jobs(now - chrono::Duration::days(7), now).await?;
+    if let Some(job) = jobs.first() {
+      c.job_with_tasks(&job.id).await?;
+      let mut progress = c.job_progress(&job.id, Duration::from_secs(1));
+      if let Some(p) = progress.next().await {
+        assert_eq!(p?.job_id, job.id);
+      }
+    }
+    Ok(())
+  }
+
+  #[rstest::rstest]
+  #[tokio::test]
+  async fn test_blockchains(config: Config) -> color_eyre::Result<()> {
+    if !config.is_ok() {
+      return Ok(());
+    }
+    let c = config.client();
+    let (blockchains, _) = c.blockchains().await?;
+    if let Some(b) = blockchains.items.first() {
+      c.blockchain(&b.id).await?;
+    }
+    Ok(())
+  }
+
+  #[rstest::rstest]
+  #[tokio::test]
+  async fn test_balances_for_vaults(config: Config) -> color_eyre::Result<()> {
+    if !config.is_ok() {
+      return Ok(());
+    }
+    let balances = config.client().balances_for_vaults(&[0, 1, 2], ASSET_BTC_TEST, 2).await;
+    assert_eq!(balances.len(), 3);
+    Ok(())
+  }
+
+  #[rstest::rstest]
+  #[tokio::test]
+  async fn test_public_key_info(config: Config) -> color_eyre::Result<()> {
+    if !config.is_ok() {
+      return Ok(());
+    }
+    let (info, _) = config.client().vault_public_key_info(0, ASSET_BTC_TEST, 0, 0, true).await?;
+    assert_eq!(info.derivation_path.len(), 5);
+    let (by_path, _) =
+      config.client().public_key_info(&info.derivation_path, SigningAlgorithm::MPC_ECDSA_SECP256K1, true).await?;
+    assert_eq!(by_path.public_key, info.public_key);
+    Ok(())
+  }
+
   #[rstest::rstest]
   #[test]
   fn check_ci(config: Config) -> color_eyre::Result<()> {
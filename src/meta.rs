@@ -0,0 +1,114 @@
+//! Metadata of the responses behind an API call: request id, status and rate limit headers
+//!
+//! ```
+//! use fireblocks_sdk::{meta, Client};
+//!
+//! async fn vault(c: Client) -> color_eyre::Result<()> {
+//!   let (result, responses) = meta::capture(c.vault(0)).await;
+//!   let (account, _) = result?;
+//!   for m in responses {
+//!     println!("{} {} remaining {:?}", account.name, m.request_id, m.rate_limit.remaining);
+//!   }
+//!   Ok(())
+//! }
+//! ```
+use http::HeaderMap;
+use http::StatusCode;
+use std::cell::RefCell;
+use std::future::Future;
+use std::time::Duration;
+
+tokio::task_local! {
+  static CAPTURED: RefCell<Vec<ResponseMeta>>;
+}
+
+/// Rate limit headers of a response, unset when Fireblocks did not send them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimit {
+  /// Requests allowed in the current window
+  pub limit: Option<u64>,
+  pub remaining: Option<u64>,
+  /// Until the window resets
+  pub reset: Option<Duration>,
+}
+
+impl RateLimit {
+  pub fn from_headers(headers: &HeaderMap) -> Self {
+    let number = |suffix: &str| {
+      ["x-ratelimit-", "x-rate-limit-", "ratelimit-"]
+        .iter()
+        .find_map(|prefix| headers.get(format!("{prefix}{suffix}")))
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())
+    };
+    Self { limit: number("limit"), remaining: number("remaining"), reset: number("reset").map(Duration::from_secs) }
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct ResponseMeta {
+  /// `X-Request-ID`, quote it to Fireblocks support
+  pub request_id: String,
+  pub status: StatusCode,
+  pub rate_limit: RateLimit,
+  pub headers: HeaderMap,
+}
+
+impl ResponseMeta {
+  pub fn new(status: StatusCode, headers: &HeaderMap) -> Self {
+    let request_id = headers.get("x-request-id").and_then(|value| value.to_str().ok()).unwrap_or_default().to_string();
+    Self { request_id, status, rate_limit: RateLimit::from_headers(headers), headers: headers.clone() }
+  }
+}
+
+/// Run `call` and return the metadata of every response it received, in order. Calls running on other
+/// tasks are not captured.
+pub async fn capture<F: Future>(call: F) -> (F::Output, Vec<ResponseMeta>) {
+  CAPTURED
+    .scope(RefCell::new(vec![]), async move {
+      let output = call.await;
+      (output, CAPTURED.with(RefCell::take))
+    })
+    .await
+}
+
+/// Record a response when running inside [`capture`]
+pub(crate) fn record(status: StatusCode, headers: &HeaderMap) {
+  let _ = CAPTURED.try_with(|c| c.borrow_mut().push(ResponseMeta::new(status, headers)));
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_rate_limit() -> color_eyre::Result<()> {
+    let mut headers = HeaderMap::new();
+    headers.insert("x-request-id", "req-1".parse()?);
+    headers.insert("x-ratelimit-limit", "100".parse()?);
+    headers.insert("x-ratelimit-remaining", "7".parse()?);
+    headers.insert("x-ratelimit-reset", "30".parse()?);
+    let meta = ResponseMeta::new(StatusCode::OK, &headers);
+    assert_eq!(meta.request_id, "req-1");
+    assert_eq!(
+      meta.rate_limit,
+      RateLimit { limit: Some(100), remaining: Some(7), reset: Some(Duration::from_secs(30)) }
+    );
+    assert_eq!(RateLimit::from_headers(&HeaderMap::new()), RateLimit::default());
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn test_capture() {
+    let headers = HeaderMap::new();
+    record(StatusCode::OK, &headers);
+    let (value, captured) = capture(async {
+      record(StatusCode::OK, &headers);
+      record(StatusCode::CREATED, &headers);
+      1
+    })
+    .await;
+    assert_eq!(value, 1);
+    assert_eq!(captured.iter().map(|m| m.status).collect::<Vec<_>>(), vec![StatusCode::OK, StatusCode::CREATED]);
+  }
+}
@@ -0,0 +1,57 @@
+//! Test doubles shared by the unit tests
+use crate::transport::{HttpRequest, HttpResponse, HttpTransport};
+use crate::{Client, ClientBuilder, FireblocksError};
+use futures::future::BoxFuture;
+use http::{HeaderMap, StatusCode};
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Path of the RSA key the tests sign with
+pub const SECRET_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/webhook/testdata/webhook_private.pem");
+
+/// Answers every request with `handler` and records the requests
+pub struct MockTransport<F> {
+  handler: F,
+  requests: Mutex<Vec<HttpRequest>>,
+}
+
+impl<F> MockTransport<F>
+where
+  F: Fn(&HttpRequest) -> HttpResponse + Send + Sync + 'static,
+{
+  pub fn new(handler: F) -> Arc<Self> {
+    Arc::new(Self { handler, requests: Mutex::default() })
+  }
+
+  /// Requests sent so far, oldest first
+  pub fn requests(&self) -> Vec<HttpRequest> {
+    self.requests.lock().unwrap_or_else(PoisonError::into_inner).clone()
+  }
+}
+
+impl<F> HttpTransport for MockTransport<F>
+where
+  F: Fn(&HttpRequest) -> HttpResponse + Send + Sync + 'static,
+{
+  fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, FireblocksError>> {
+    let response = (self.handler)(&request);
+    self.requests.lock().unwrap_or_else(PoisonError::into_inner).push(request);
+    Box::pin(async move { Ok(response) })
+  }
+}
+
+/// A JSON response with `status`
+pub fn json(status: StatusCode, body: impl Into<Vec<u8>>) -> HttpResponse {
+  let mut headers = HeaderMap::new();
+  headers.insert("content-type", "application/json".parse().expect("header"));
+  HttpResponse::new(status, headers, body.into())
+}
+
+/// A builder for key `key` sending through `transport`
+pub fn builder(transport: Arc<dyn HttpTransport>) -> color_eyre::Result<ClientBuilder> {
+  Ok(ClientBuilder::new("key", &std::fs::read(SECRET_PATH)?).with_transport(transport))
+}
+
+/// A client sending through `transport`
+pub fn client(transport: Arc<dyn HttpTransport>) -> color_eyre::Result<Client> {
+  Ok(builder(transport)?.build()?)
+}
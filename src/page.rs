@@ -0,0 +1,336 @@
+use crate::queue::Priority;
+use crate::types::connect::{PagedWalletConnectResponse, WalletConnection};
+use crate::types::{
+  AddressContainer, AuditLogs, Blockchains, CosignerApiKeys, Cosigners, NcwAccounts, NcwAddresses, NcwAssets,
+  NcwWallets, NftCollections, NftOwnedTokens, NftPaging, NftTokens, Notifications, PaginatedAssetWallet, Paging,
+  SigningKeys, SmartTransferTickets, TokenLinks, Transaction, ValidationKeys, VaultAccounts, Webhooks,
+};
+use crate::{Client, FireblocksError};
+use futures::future::BoxFuture;
+use http::HeaderMap;
+use serde::de::DeserializeOwned;
+use url::Url;
+
+/// How a list endpoint is asked for another page
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cursors {
+  /// The cursor goes in the `next` (or `prev`) query parameter of the original request
+  Query { next: &'static str, prev: Option<&'static str> },
+  /// The cursor is the complete url of the page
+  Url,
+}
+
+/// A list response of the API, split by [`Page`] into its items and cursors
+pub trait Paginated: DeserializeOwned + Default + Send + 'static {
+  type Item: Send + 'static;
+  const CURSORS: Cursors;
+
+  /// The items and the next and previous cursors
+  fn into_page_parts(self, headers: &HeaderMap) -> (Vec<Self::Item>, Option<String>, Option<String>);
+}
+
+type FetchPage<T> = for<'a> fn(&'a Client, Url) -> BoxFuture<'a, crate::Result<Page<T>>>;
+
+/// One page of a list endpoint
+///
+/// ```
+/// use fireblocks_sdk::{Client, PagingVaultRequestBuilder};
+///
+/// async fn all_vaults(c: Client) -> color_eyre::Result<()> {
+///   let params = PagingVaultRequestBuilder::new().limit(100).build()?;
+///   let (mut page, _) = c.vaults(params).await?;
+///   loop {
+///     for account in &page.items {
+///       println!("{} {}", account.id, account.name);
+///     }
+///     match page.next(&c).await {
+///       Some(next) => page = next?.0,
+///       None => break,
+///     }
+///   }
+///   Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Page<T> {
+  pub items: Vec<T>,
+  next: Option<String>,
+  prev: Option<String>,
+  url: Url,
+  cursors: Cursors,
+  fetch: FetchPage<T>,
+}
+
+impl<T> std::fmt::Debug for Page<T>
+where
+  T: std::fmt::Debug,
+{
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("Page")
+      .field("items", &self.items)
+      .field("next", &self.next)
+      .field("prev", &self.prev)
+      .finish_non_exhaustive()
+  }
+}
+
+impl<T> Page<T> {
+  /// Cursor of the next page, `None` on the last page
+  pub fn next_cursor(&self) -> Option<&str> {
+    self.next.as_deref()
+  }
+
+  /// Cursor of the previous page, `None` on the first page or when the endpoint only pages forward
+  pub fn prev_cursor(&self) -> Option<&str> {
+    self.prev.as_deref()
+  }
+
+  pub const fn has_next(&self) -> bool {
+    self.next.is_some()
+  }
+
+  pub const fn has_prev(&self) -> bool {
+    self.prev.is_some()
+  }
+
+  pub fn into_items(self) -> Vec<T> {
+    self.items
+  }
+
+  /// Fetch the next page with the same filters, `None` on the last page
+  pub async fn next(&self, client: &Client) -> Option<crate::Result<Self>> {
+    let url = self.cursor_url(self.next.as_deref()?, false)?;
+    Some(match url {
+      Ok(url) => (self.fetch)(client, url).await,
+      Err(e) => Err(e),
+    })
+  }
+
+  /// Fetch the previous page with the same filters, `None` on the first page
+  pub async fn prev(&self, client: &Client) -> Option<crate::Result<Self>> {
+    let url = self.cursor_url(self.prev.as_deref()?, true)?;
+    Some(match url {
+      Ok(url) => (self.fetch)(client, url).await,
+      Err(e) => Err(e),
+    })
+  }
+
+  /// The url of the page at `cursor`, `None` when the endpoint cannot page that way
+  fn cursor_url(&self, cursor: &str, backward: bool) -> Option<Result<Url, FireblocksError>> {
+    let Cursors::Query { next, prev } = self.cursors else {
+      return Some(Url::parse(cursor).map_err(FireblocksError::from));
+    };
+    let param = if backward { prev? } else { next };
+    let mut url = self.url.clone();
+    let params: Vec<(String, String)> = url
+      .query_pairs()
+      .filter(|(k, _)| k != next && Some(k.as_ref()) != prev)
+      .map(|(k, v)| (k.into_owned(), v.into_owned()))
+      .collect();
+    url.query_pairs_mut().clear().extend_pairs(params).append_pair(param, cursor);
+    Some(Ok(url))
+  }
+}
+
+impl<T> IntoIterator for Page<T> {
+  type Item = T;
+  type IntoIter = std::vec::IntoIter<T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.items.into_iter()
+  }
+}
+
+fn fetch<R: Paginated>(client: &Client, url: Url) -> BoxFuture<'_, crate::Result<Page<R::Item>>> {
+  Box::pin(client.get_page::<R>(url))
+}
+
+impl Client {
+  /// GET one page of a list endpoint
+  pub(crate) async fn get_page<R: Paginated>(&self, url: Url) -> crate::Result<Page<R::Item>> {
+    let ((response, headers), request_id) =
+      crate::queue::default_priority(Priority::Bulk, self.get_with_headers::<R>(url.clone())).await?;
+    let (items, next, prev) = response.into_page_parts(&headers);
+    let cursor = |c: Option<String>| c.filter(|c| !c.is_empty());
+    let page = Page { items, next: cursor(next), prev: cursor(prev), url, cursors: R::CURSORS, fetch: fetch::<R> };
+    Ok((page, request_id))
+  }
+}
+
+/// `data` with a `next` cursor taken by `pageCursor`
+macro_rules! impl_data_next {
+  ($($response:ty => $item:ty),+ $(,)?) => {
+    $(
+      impl Paginated for $response {
+        type Item = $item;
+        const CURSORS: Cursors = Cursors::Query { next: "pageCursor", prev: None };
+
+        fn into_page_parts(self, _: &HeaderMap) -> (Vec<Self::Item>, Option<String>, Option<String>) {
+          (self.data, self.next, None)
+        }
+      }
+    )+
+  };
+}
+
+impl_data_next!(
+  Blockchains => crate::types::Blockchain,
+  Webhooks => crate::types::Webhook,
+  Notifications => crate::types::Notification,
+  SigningKeys => crate::types::SigningKey,
+  ValidationKeys => crate::types::ValidationKey,
+  NcwWallets => crate::types::NcwWallet,
+  NcwAccounts => crate::types::NcwAccount,
+  NcwAssets => crate::types::NcwAsset,
+  NcwAddresses => crate::types::NcwAddress,
+  TokenLinks => crate::types::TokenLink,
+  Cosigners => crate::types::Cosigner,
+  CosignerApiKeys => crate::types::CosignerApiKey,
+);
+
+/// `data` with a `paging.next` cursor taken by `pageCursor`
+macro_rules! impl_nft {
+  ($($response:ty => $item:ty),+ $(,)?) => {
+    $(
+      impl Paginated for $response {
+        type Item = $item;
+        const CURSORS: Cursors = Cursors::Query { next: "pageCursor", prev: None };
+
+        fn into_page_parts(self, _: &HeaderMap) -> (Vec<Self::Item>, Option<String>, Option<String>) {
+          (self.data, self.paging.and_then(|p: NftPaging| p.next), None)
+        }
+      }
+    )+
+  };
+}
+
+impl_nft!(
+  NftOwnedTokens => crate::types::NftOwnedToken,
+  NftTokens => crate::types::NftToken,
+  NftCollections => crate::types::NftCollection,
+);
+
+const AFTER_BEFORE: Cursors = Cursors::Query { next: "after", prev: Some("before") };
+
+fn paging(p: Paging) -> (Option<String>, Option<String>) {
+  (p.after, p.before)
+}
+
+impl Paginated for VaultAccounts {
+  type Item = crate::types::Account;
+  const CURSORS: Cursors = AFTER_BEFORE;
+
+  fn into_page_parts(self, _: &HeaderMap) -> (Vec<Self::Item>, Option<String>, Option<String>) {
+    let (next, prev) = paging(self.paging);
+    (self.accounts, next, prev)
+  }
+}
+
+impl Paginated for PaginatedAssetWallet {
+  type Item = crate::types::AssetResponse;
+  const CURSORS: Cursors = AFTER_BEFORE;
+
+  fn into_page_parts(self, _: &HeaderMap) -> (Vec<Self::Item>, Option<String>, Option<String>) {
+    let (next, prev) = paging(self.paging);
+    (self.asset_wallets, next, prev)
+  }
+}
+
+impl Paginated for AddressContainer {
+  type Item = crate::types::Address;
+  const CURSORS: Cursors = AFTER_BEFORE;
+
+  fn into_page_parts(self, _: &HeaderMap) -> (Vec<Self::Item>, Option<String>, Option<String>) {
+    let (next, prev) = self.paging.map(paging).unwrap_or_default();
+    (self.addresses, next, prev)
+  }
+}
+
+impl Paginated for SmartTransferTickets {
+  type Item = crate::types::SmartTransferTicket;
+  const CURSORS: Cursors = Cursors::Query { next: "after", prev: None };
+
+  fn into_page_parts(self, _: &HeaderMap) -> (Vec<Self::Item>, Option<String>, Option<String>) {
+    (self.data, self.after, None)
+  }
+}
+
+impl Paginated for PagedWalletConnectResponse {
+  type Item = WalletConnection;
+  const CURSORS: Cursors = Cursors::Query { next: "next", prev: None };
+
+  fn into_page_parts(self, _: &HeaderMap) -> (Vec<Self::Item>, Option<String>, Option<String>) {
+    (self.data, self.page.map(|p| p.next), None)
+  }
+}
+
+impl Paginated for AuditLogs {
+  type Item = crate::types::AuditLog;
+  const CURSORS: Cursors = Cursors::Query { next: "cursor", prev: None };
+
+  fn into_page_parts(self, _: &HeaderMap) -> (Vec<Self::Item>, Option<String>, Option<String>) {
+    (self.data, self.cursor, None)
+  }
+}
+
+/// Transactions page with the `next-page` and `prev-page` headers, each the url of the page
+impl Paginated for Vec<Transaction> {
+  type Item = Transaction;
+  const CURSORS: Cursors = Cursors::Url;
+
+  fn into_page_parts(self, headers: &HeaderMap) -> (Vec<Self::Item>, Option<String>, Option<String>) {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok()).map(String::from);
+    (self, header("next-page"), header("prev-page"))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn page(url: &str, cursors: Cursors) -> color_eyre::Result<Page<Transaction>> {
+    Ok(Page {
+      items: vec![],
+      next: Some("n".into()),
+      prev: Some("p".into()),
+      url: Url::parse(url)?,
+      cursors,
+      fetch: fetch::<Vec<Transaction>>,
+    })
+  }
+
+  #[test]
+  fn test_cursor_url() -> color_eyre::Result<()> {
+    let p = page("https://api.fireblocks.io/v1/vault/accounts_paged?limit=10&after=a", AFTER_BEFORE)?;
+    let next = p.cursor_url("n", false).expect("next")?;
+    assert_eq!(next.query(), Some("limit=10&after=n"));
+    let prev = p.cursor_url("p", true).expect("prev")?;
+    assert_eq!(prev.query(), Some("limit=10&before=p"));
+
+    let p = page("https://api.fireblocks.io/v1/webhooks", Cursors::Query { next: "pageCursor", prev: None })?;
+    assert_eq!(p.cursor_url("n", false).expect("next")?.query(), Some("pageCursor=n"));
+    assert!(p.cursor_url("p", true).is_none());
+
+    let p = page("https://api.fireblocks.io/v1/transactions", Cursors::Url)?;
+    let next = p.cursor_url("https://api.fireblocks.io/v1/transactions?next=abc", false).expect("next")?;
+    assert_eq!(next.query(), Some("next=abc"));
+    assert!(p.cursor_url("not a url", false).expect("next").is_err());
+    Ok(())
+  }
+
+  #[test]
+  fn test_into_page_parts() -> color_eyre::Result<()> {
+    let vaults: VaultAccounts = serde_json::from_str(
+      r#"{"accounts": [], "paging": {"after": "MTA=", "before": ""}, "previousUrl": null, "nextUrl": null}"#,
+    )?;
+    let (_, next, prev) = vaults.into_page_parts(&HeaderMap::new());
+    assert_eq!(next.as_deref(), Some("MTA="));
+    assert_eq!(prev.as_deref(), Some(""));
+
+    let mut headers = HeaderMap::new();
+    headers.insert("next-page", "https://api.fireblocks.io/v1/transactions?next=1".parse()?);
+    let (items, next, prev) = Vec::<Transaction>::new().into_page_parts(&headers);
+    assert!(items.is_empty() && next.is_some() && prev.is_none());
+    Ok(())
+  }
+}
@@ -1,5 +1,5 @@
-use crate::types::{Transaction, TransactionListBuilder, VaultAccounts};
-use crate::{Client, Epoch, FireblocksError, PagingVaultRequestBuilder, ParamError, QueryParams, Result};
+use crate::types::{Account, SmartTransferTicket, Transaction, TransactionListBuilder};
+use crate::{Client, Epoch, FireblocksError, Page, PagingVaultRequestBuilder, ParamError, QueryParams, Result};
 use chrono::{TimeZone, Utc};
 use futures::future::BoxFuture;
 use futures::stream::FuturesUnordered;
@@ -16,17 +16,72 @@ pub struct PagedClient {
 pub struct VaultStream {
   client: Arc<Client>,
   batch: u16,
-  after: Option<String>,
+  cursor: Option<String>,
+  backward: bool, // follow the before cursors instead of the after cursors
+  ascending: Option<bool>,
   init: bool,
-  fut: FuturesUnordered<BoxFuture<'static, Result<VaultAccounts>>>,
+  fut: FuturesUnordered<BoxFuture<'static, Result<Page<Account>>>>,
 }
 
 impl VaultStream {
   fn new(client: Arc<Client>, batch: u16) -> Self {
-    Self { client, batch, init: false, after: None, fut: FuturesUnordered::new() }
+    Self { client, batch, init: false, cursor: None, backward: false, ascending: None, fut: FuturesUnordered::new() }
+  }
+
+  /// Order accounts by creation time, by default the most recently created come first
+  #[allow(clippy::return_self_not_must_use)]
+  pub const fn order(mut self, ascending: bool) -> Self {
+    self.ascending = Some(ascending);
+    self
   }
+
+  /// Start at the `before` cursor of a page and walk backwards, following the `before` cursor of each page
+  #[allow(clippy::return_self_not_must_use)]
+  pub fn before(mut self, cursor: &str) -> Self {
+    self.cursor = Some(String::from(cursor));
+    self.backward = true;
+    self
+  }
+
   fn build_params(&self) -> std::result::Result<QueryParams, ParamError> {
-    PagingVaultRequestBuilder::new().limit(self.batch).after(self.after.as_ref().unwrap_or(&String::new())).build()
+    let mut builder = PagingVaultRequestBuilder::new();
+    builder.limit(self.batch);
+    if let Some(ascending) = self.ascending {
+      builder.order(ascending);
+    }
+    let cursor = self.cursor.as_deref().unwrap_or_default();
+    if self.backward {
+      builder.before(cursor);
+    } else {
+      builder.after(cursor);
+    }
+    builder.build()
+  }
+}
+
+pub struct SmartTransferStream {
+  client: Arc<Client>,
+  batch: u16,
+  filter: QueryParams,
+  after: Option<String>,
+  init: bool,
+  fut: FuturesUnordered<BoxFuture<'static, Result<Page<SmartTransferTicket>>>>,
+}
+
+impl SmartTransferStream {
+  fn new(client: Arc<Client>, filter: QueryParams, batch: u16) -> Self {
+    // paging is driven by the stream
+    let filter = filter.into_iter().filter(|(k, _)| k != "limit" && k != "after").collect();
+    Self { client, batch, filter, init: false, after: None, fut: FuturesUnordered::new() }
+  }
+
+  fn build_params(&self) -> QueryParams {
+    let mut params = Vec::clone(&self.filter);
+    params.push(("limit".to_owned(), format!("{}", self.batch)));
+    if let Some(after) = self.after.as_ref() {
+      params.push(("after".to_owned(), String::from(after)));
+    }
+    params
   }
 }
 
@@ -60,13 +115,8 @@ impl TransactionStream {
   }
 }
 
-pub trait AsyncIteratorAsyncNext {
-  type Item;
-  async fn next(&mut self) -> Result<Option<Self::Item>>;
-}
-
 impl Stream for VaultStream {
-  type Item = Result<VaultAccounts>;
+  type Item = Result<Page<Account>>;
 
   #[allow(clippy::cognitive_complexity)]
   fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
@@ -90,8 +140,9 @@ impl Stream for VaultStream {
       Poll::Ready(opt) => {
         if let Some(result) = opt {
           match result {
-            Ok((ref va, ref _id)) => {
-              self.after.clone_from(&va.paging.after);
+            Ok((ref page, ref _id)) => {
+              let cursor = if self.backward { page.prev_cursor() } else { page.next_cursor() };
+              self.cursor = cursor.map(String::from);
             },
             Err(e) => {
               return Poll::Ready(Some(Err(e)));
@@ -105,11 +156,11 @@ impl Stream for VaultStream {
         cx.waker().wake_by_ref();
         return Poll::Pending;
       },
-    };
+    }
 
-    tracing::trace!("checking after {:#?}", self.after);
+    tracing::trace!("checking cursor {:#?}", self.cursor);
     // If there are no more pages to fetch and no pending futures, end the stream
-    if self.after.is_none() {
+    if self.cursor.is_none() {
       return Poll::Ready(None);
     }
 
@@ -125,6 +176,54 @@ impl Stream for VaultStream {
   }
 }
 
+impl Stream for SmartTransferStream {
+  type Item = Result<Page<SmartTransferTicket>>;
+
+  fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+    if !self.init {
+      tracing::debug!("init smart transfer stream");
+      self.init = true;
+      let client = self.client.clone();
+      let params = self.build_params();
+      let fut = async move { client.smart_transfers(params).await }.boxed();
+      self.fut.push(fut);
+      cx.waker().wake_by_ref();
+      return Poll::Pending;
+    }
+
+    match self.fut.poll_next_unpin(cx) {
+      Poll::Ready(opt) => {
+        if let Some(result) = opt {
+          match result {
+            Ok((ref page, ref _id)) => {
+              self.after = page.next_cursor().map(String::from);
+            },
+            Err(e) => {
+              return Poll::Ready(Some(Err(e)));
+            },
+          }
+          return Poll::Ready(Some(result));
+        }
+      },
+      Poll::Pending => {
+        cx.waker().wake_by_ref();
+        return Poll::Pending;
+      },
+    }
+
+    if self.after.is_none() {
+      return Poll::Ready(None);
+    }
+
+    let client = self.client.clone();
+    let params = self.build_params();
+    let fut = async move { client.smart_transfers(params).await }.boxed();
+    self.fut.push(fut);
+    cx.waker().wake_by_ref();
+    Poll::Pending
+  }
+}
+
 impl Stream for TransactionStream {
   type Item = Result<Vec<Transaction>>;
 
@@ -137,7 +236,7 @@ impl Stream for TransactionStream {
         Ok(p) => p,
         Err(e) => return Poll::Ready(Some(Err(FireblocksError::from(e)))),
       };
-      let fut = async move { client.transactions(params).await }.boxed();
+      let fut = async move { client.transactions(params).await.map(|(page, id)| (page.items, id)) }.boxed();
       self.fut.push(fut);
       cx.waker().wake_by_ref();
       return Poll::Pending;
@@ -167,14 +266,14 @@ impl Stream for TransactionStream {
         cx.waker().wake_by_ref();
         return Poll::Pending;
       },
-    };
+    }
 
     let client = self.client.clone();
     let params = match self.build_params() {
       Ok(p) => p,
       Err(e) => return Poll::Ready(Some(Err(FireblocksError::from(e)))),
     };
-    let fut = async move { client.transactions(params).await }.boxed();
+    let fut = async move { client.transactions(params).await.map(|(page, id)| (page.items, id)) }.boxed();
     self.fut.push(fut);
     cx.waker().wake_by_ref();
     Poll::Pending
@@ -197,17 +296,45 @@ impl PagedClient {
   ///   let pc = PagedClient::new(Arc::new(c));
   ///   let mut vault_stream = pc.vaults(100);
   ///   while let Ok(Some(result)) = vault_stream.try_next().await {
-  ///     tracing::info!("accounts {}", result.0.accounts.len());
+  ///     tracing::info!("accounts {}", result.0.items.len());
   ///     tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
   ///    }
   ///   Ok(())
   /// }
   /// ```
+  /// Walk backwards from a `before` cursor with [`VaultStream::before`], or change the ordering with
+  /// [`VaultStream::order`]
+  ///
   /// see [`Client::vaults`]
   pub fn vaults(&self, batch_size: u16) -> VaultStream {
     VaultStream::new(self.client.clone(), batch_size)
   }
 
+  /// Stream smart transfer tickets matching `filter`, `batch_size` tickets at a time
+  ///
+  /// ```
+  /// use std::sync::Arc;
+  /// use futures::TryStreamExt;
+  /// use fireblocks_sdk::{Client, PagedClient};
+  /// use fireblocks_sdk::types::{SmartTransferListBuilder, SmartTransferStatus};
+  ///
+  /// async fn open_tickets(c: Client) -> color_eyre::Result<()> {
+  ///   let pc = PagedClient::new(Arc::new(c));
+  ///   let filter = SmartTransferListBuilder::new().statuses(&[SmartTransferStatus::OPEN]).build()?;
+  ///   let mut ts = pc.smart_transfer_tickets(filter, 50);
+  ///   while let Some(result) = ts.try_next().await? {
+  ///     tracing::info!("tickets {}", result.0.items.len());
+  ///   }
+  ///   Ok(())
+  /// }
+  /// ```
+  ///
+  /// see
+  /// * [`Client::smart_transfers`]
+  pub fn smart_transfer_tickets(&self, filter: QueryParams, batch_size: u16) -> SmartTransferStream {
+    SmartTransferStream::new(self.client.clone(), filter, batch_size)
+  }
+
   /// Stream all the transactions from source vault account id and after some date
   ///
   /// Default date is 2022-04-06 if None provided
@@ -246,3 +373,20 @@ impl PagedClient {
     TransactionStream::from_dest(self.client.clone(), batch_size, vault_id, after.unwrap_or(default_after))
   }
 }
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::mock::{self, MockTransport};
+  use http::StatusCode;
+
+  #[test]
+  fn test_vault_stream_params() -> color_eyre::Result<()> {
+    let pc = PagedClient::new(Arc::new(mock::client(MockTransport::new(|_| mock::json(StatusCode::OK, "{}")))?));
+    let params = pc.vaults(5).order(true).build_params()?;
+    assert_eq!(params, [("orderBy", "ASC"), ("limit", "5")].map(|(k, v)| (k.to_owned(), v.to_owned())));
+    let params = pc.vaults(5).before("MTA=").build_params()?;
+    assert_eq!(params, [("before", "MTA="), ("limit", "5")].map(|(k, v)| (k.to_owned(), v.to_owned())));
+    Ok(())
+  }
+}
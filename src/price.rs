@@ -0,0 +1,72 @@
+//! Fiat prices of assets, to value report rows
+//!
+//! Reports are valued with a [`PriceOracle`], e.g. an exchange rate API or the price history of your accounting
+//! system, at the time their amounts apply: [`FeeAnalytics::add_valued`](crate::fees::FeeAnalytics::add_valued)
+//! values the fees of each transaction at its last update, [`FeeReport::value`](crate::fees::FeeReport::value)
+//! whole rows at the start of their window, [`BalanceDiff::value`](crate::snapshot::BalanceDiff::value) the
+//! balance changes at the snapshot time given. The fee report is the only transaction report valued, transfer
+//! amounts are not. [`NoPrices`] leaves fiat values empty, a value is only given when every amount in it
+//! has a price, so a missing price never counts as zero.
+//!
+//! ```
+//! use bigdecimal::BigDecimal;
+//! use chrono::{DateTime, Utc};
+//! use fireblocks_sdk::price::PriceOracle;
+//! use fireblocks_sdk::FireblocksError;
+//! use futures::future::BoxFuture;
+//!
+//! /// One USD price per asset, whatever the time
+//! struct Fixed;
+//!
+//! impl PriceOracle for Fixed {
+//!   fn price<'a>(
+//!     &'a self,
+//!     asset_id: &'a str,
+//!     _at: DateTime<Utc>,
+//!   ) -> BoxFuture<'a, Result<Option<BigDecimal>, FireblocksError>> {
+//!     let price = match asset_id {
+//!       "ETH" => Some(BigDecimal::from(2500)),
+//!       "USDC" => Some(BigDecimal::from(1)),
+//!       _ => None,
+//!     };
+//!     Box::pin(async move { Ok(price) })
+//!   }
+//! }
+//! ```
+use crate::FireblocksError;
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+
+pub trait PriceOracle: Send + Sync {
+  /// Price of one unit of `asset_id` at `at`, `None` when it is not known
+  fn price<'a>(
+    &'a self,
+    asset_id: &'a str,
+    at: DateTime<Utc>,
+  ) -> BoxFuture<'a, Result<Option<BigDecimal>, FireblocksError>>;
+}
+
+/// Knows no price, reports are not valued
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoPrices;
+
+impl PriceOracle for NoPrices {
+  fn price<'a>(
+    &'a self,
+    _asset_id: &'a str,
+    _at: DateTime<Utc>,
+  ) -> BoxFuture<'a, Result<Option<BigDecimal>, FireblocksError>> {
+    Box::pin(async { Ok(None) })
+  }
+}
+
+/// `amount` of `asset_id` at `at` in fiat, `None` when the oracle knows no price
+pub async fn fiat_value(
+  oracle: &dyn PriceOracle,
+  asset_id: &str,
+  amount: &BigDecimal,
+  at: DateTime<Utc>,
+) -> Result<Option<BigDecimal>, FireblocksError> {
+  Ok(oracle.price(asset_id, at).await?.map(|price| price * amount))
+}
@@ -0,0 +1,234 @@
+//! Optional request queue with priority lanes, so creating and cancelling transactions is not starved by
+//! bulk paging sharing the same client
+//!
+//! Calls wait for one of `max_in_flight` slots. A free slot goes to the waiting call of the highest
+//! [`Priority`], and `reserved` slots are kept for [`Priority::High`] only. Transactions are created and
+//! cancelled with high priority, list pages and streams are fetched with bulk priority, anything else is
+//! normal. [`with_priority`] overrides the priority of the calls made by a future.
+//!
+//! ```
+//! use fireblocks_sdk::queue::{self, Priority, QueueConfig};
+//! use fireblocks_sdk::ClientBuilder;
+//!
+//! async fn vault(api_key: &str, secret: &[u8]) -> color_eyre::Result<()> {
+//!   let queue = QueueConfig::new(8).with_reserved(2);
+//!   let client = ClientBuilder::new(api_key, secret).with_request_queue(queue).build()?;
+//!   let (account, _) = queue::with_priority(Priority::High, client.vault(0)).await?;
+//!   println!("{}", account.name);
+//!   Ok(())
+//! }
+//! ```
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::{Arc, Mutex, PoisonError};
+use tokio::sync::oneshot;
+
+tokio::task_local! {
+  static PRIORITY: Priority;
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+  /// Latency sensitive, e.g. creating or cancelling a transaction
+  High,
+  #[default]
+  Normal,
+  /// Paging through lists
+  Bulk,
+}
+
+impl Priority {
+  const ALL: [Self; 3] = [Self::High, Self::Normal, Self::Bulk];
+
+  const fn lane(self) -> usize {
+    self as usize
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueueConfig {
+  pub max_in_flight: usize,
+  /// Slots only [`Priority::High`] calls may take
+  pub reserved: usize,
+}
+
+impl QueueConfig {
+  pub const fn new(max_in_flight: usize) -> Self {
+    Self { max_in_flight, reserved: 0 }
+  }
+
+  #[allow(clippy::return_self_not_must_use)]
+  pub const fn with_reserved(mut self, reserved: usize) -> Self {
+    self.reserved = reserved;
+    self
+  }
+}
+
+#[derive(Debug, Default)]
+struct State {
+  in_flight: usize,
+  waiting: [VecDeque<oneshot::Sender<()>>; 3],
+}
+
+#[derive(Debug)]
+pub(crate) struct RequestQueue {
+  config: QueueConfig,
+  state: Mutex<State>,
+}
+
+/// A slot of the queue, freed on drop
+#[derive(Debug)]
+pub(crate) struct Permit {
+  queue: Arc<RequestQueue>,
+}
+
+impl Drop for Permit {
+  fn drop(&mut self) {
+    self.queue.release();
+  }
+}
+
+/// Frees the slot handed to an acquire that was dropped before it could take it
+struct Waiter<'a> {
+  queue: &'a Arc<RequestQueue>,
+  rx: Option<oneshot::Receiver<()>>,
+}
+
+impl Drop for Waiter<'_> {
+  fn drop(&mut self) {
+    if let Some(mut rx) = self.rx.take() {
+      rx.close();
+      if rx.try_recv().is_ok() {
+        self.queue.release();
+      }
+    }
+  }
+}
+
+impl RequestQueue {
+  pub(crate) fn new(config: QueueConfig) -> Self {
+    let max_in_flight = config.max_in_flight.max(1);
+    let config = QueueConfig { max_in_flight, reserved: config.reserved.min(max_in_flight - 1) };
+    Self { config, state: Mutex::new(State::default()) }
+  }
+
+  const fn limit(&self, priority: Priority) -> usize {
+    match priority {
+      Priority::High => self.config.max_in_flight,
+      Priority::Normal | Priority::Bulk => self.config.max_in_flight - self.config.reserved,
+    }
+  }
+
+  pub(crate) async fn acquire(self: &Arc<Self>, priority: Priority) -> Permit {
+    let rx = {
+      let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+      let ahead = state.waiting[..=priority.lane()].iter().any(|lane| !lane.is_empty());
+      if !ahead && state.in_flight < self.limit(priority) {
+        state.in_flight += 1;
+        return Permit { queue: Arc::clone(self) };
+      }
+      let (tx, rx) = oneshot::channel();
+      state.waiting[priority.lane()].push_back(tx);
+      rx
+    };
+    let mut waiter = Waiter { queue: self, rx: Some(rx) };
+    if let Some(rx) = waiter.rx.as_mut() {
+      // the sender is only dropped after sending, or with the queue itself
+      let _ = rx.await;
+    }
+    waiter.rx = None;
+    Permit { queue: Arc::clone(self) }
+  }
+
+  fn release(&self) {
+    let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+    state.in_flight -= 1;
+    // hand free slots to the highest waiting lane, skipping acquires that were dropped
+    while let Some(priority) = Priority::ALL.into_iter().find(|p| !state.waiting[p.lane()].is_empty()) {
+      if state.in_flight >= self.limit(priority) {
+        break;
+      }
+      if let Some(tx) = state.waiting[priority.lane()].pop_front() {
+        if tx.send(()).is_ok() {
+          state.in_flight += 1;
+        }
+      }
+    }
+    drop(state);
+  }
+}
+
+/// Run `call` with its requests queued at `priority`, when the client has a request queue
+pub async fn with_priority<F: Future>(priority: Priority, call: F) -> F::Output {
+  PRIORITY.scope(priority, call).await
+}
+
+/// Run `call` at `priority` unless the caller chose one with [`with_priority`]
+pub(crate) async fn default_priority<F: Future>(priority: Priority, call: F) -> F::Output {
+  if PRIORITY.try_with(|_| ()).is_ok() {
+    call.await
+  } else {
+    PRIORITY.scope(priority, call).await
+  }
+}
+
+/// The priority chosen with [`with_priority`], or `default`
+pub(crate) fn current_priority(default: Priority) -> Priority {
+  PRIORITY.try_with(|p| *p).unwrap_or(default)
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use std::time::Duration;
+
+  #[tokio::test]
+  async fn test_priority_lanes() {
+    let queue = Arc::new(RequestQueue::new(QueueConfig::new(2).with_reserved(1)));
+    let first = queue.acquire(Priority::Bulk).await;
+    // the second slot is reserved for high priority
+    let bulk = tokio::spawn({
+      let queue = Arc::clone(&queue);
+      async move { queue.acquire(Priority::Bulk).await }
+    });
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    let high = queue.acquire(Priority::High).await;
+    assert!(!bulk.is_finished());
+
+    let normal = tokio::spawn({
+      let queue = Arc::clone(&queue);
+      async move { queue.acquire(Priority::Normal).await }
+    });
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    drop(first);
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    assert!(!normal.is_finished());
+    drop(high);
+    // the normal call overtakes the bulk call waiting before it
+    let normal = normal.await.expect("normal");
+    assert!(!bulk.is_finished());
+    drop(normal);
+    drop(bulk.await.expect("bulk"));
+    assert_eq!(queue.state.lock().expect("state").in_flight, 0);
+  }
+
+  #[tokio::test]
+  async fn test_dropped_acquire() {
+    let queue = Arc::new(RequestQueue::new(QueueConfig::new(1)));
+    let permit = queue.acquire(Priority::Normal).await;
+    let timed_out = tokio::time::timeout(Duration::from_millis(10), queue.acquire(Priority::High)).await;
+    assert!(timed_out.is_err());
+    drop(permit);
+    let _permit = queue.acquire(Priority::Bulk).await;
+    assert_eq!(queue.state.lock().expect("state").in_flight, 1);
+  }
+
+  #[tokio::test]
+  async fn test_default_priority() {
+    let current = || async { current_priority(Priority::Normal) };
+    assert_eq!(current().await, Priority::Normal);
+    assert_eq!(default_priority(Priority::Bulk, current()).await, Priority::Bulk);
+    let chosen = with_priority(Priority::High, default_priority(Priority::Bulk, current())).await;
+    assert_eq!(chosen, Priority::High);
+  }
+}
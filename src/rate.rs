@@ -0,0 +1,115 @@
+//! A token bucket rate budget that can be shared by several clients
+//!
+//! Fireblocks limits requests per API key, so clients using the same key (per workspace, per service, or
+//! built twice) should draw from one budget. Clones of a [`Client`](crate::Client) already share theirs.
+//!
+//! ```
+//! use fireblocks_sdk::rate::RateBudget;
+//! use fireblocks_sdk::ClientBuilder;
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! fn build(api_key: &str, secret: &[u8]) -> color_eyre::Result<()> {
+//!   let budget = Arc::new(RateBudget::new(20, Duration::from_secs(1)));
+//!   let payments = ClientBuilder::new(api_key, secret).with_rate_budget(Arc::clone(&budget)).build()?;
+//!   let reporting = ClientBuilder::new(api_key, secret).with_rate_budget(budget).build()?;
+//!   Ok(())
+//! }
+//! ```
+use crate::meta::RateLimit;
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+use tokio::time::Instant;
+
+#[derive(Debug)]
+struct Bucket {
+  /// Negative when calls are waiting for tokens they already took
+  tokens: f64,
+  updated: Instant,
+  paused_until: Option<Instant>,
+}
+
+#[derive(Debug)]
+pub struct RateBudget {
+  capacity: f64,
+  /// Tokens added per second
+  rate: f64,
+  bucket: Mutex<Bucket>,
+}
+
+impl RateBudget {
+  /// Allow `requests` per `per`, bursting up to `requests` at once
+  pub fn new(requests: u32, per: Duration) -> Self {
+    let capacity = f64::from(requests.max(1));
+    let rate = capacity / per.as_secs_f64().max(f64::EPSILON);
+    Self {
+      capacity,
+      rate,
+      bucket: Mutex::new(Bucket { tokens: capacity, updated: Instant::now(), paused_until: None }),
+    }
+  }
+
+  /// Take a token, waiting until one is available. Waiting calls are served in order
+  pub async fn acquire(&self) {
+    if let Some(wait) = self.reserve(Instant::now()) {
+      tokio::time::sleep(wait).await;
+    }
+  }
+
+  /// Take a token at `now`, returning how long to wait for it
+  fn reserve(&self, now: Instant) -> Option<Duration> {
+    let mut bucket = self.bucket.lock().unwrap_or_else(PoisonError::into_inner);
+    let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+    bucket.tokens = elapsed.mul_add(self.rate, bucket.tokens).min(self.capacity);
+    bucket.updated = now;
+    bucket.tokens -= 1.0;
+    let refill = (bucket.tokens < 0.0).then(|| Duration::from_secs_f64(-bucket.tokens / self.rate));
+    let paused = bucket.paused_until.and_then(|until| until.checked_duration_since(now));
+    drop(bucket);
+    refill.max(paused)
+  }
+
+  /// Hold back calls until the window resets when Fireblocks reports the limit exhausted
+  pub fn observe(&self, rate_limit: &RateLimit) {
+    if let (Some(0), Some(reset)) = (rate_limit.remaining, rate_limit.reset) {
+      let until = Instant::now() + reset;
+      let mut bucket = self.bucket.lock().unwrap_or_else(PoisonError::into_inner);
+      bucket.paused_until = bucket.paused_until.max(Some(until));
+    }
+  }
+
+  /// Tokens left right now, negative when calls are waiting
+  pub fn available(&self) -> f64 {
+    let bucket = self.bucket.lock().unwrap_or_else(PoisonError::into_inner);
+    let elapsed = Instant::now().saturating_duration_since(bucket.updated).as_secs_f64();
+    elapsed.mul_add(self.rate, bucket.tokens).min(self.capacity)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_reserve() {
+    let budget = RateBudget::new(2, Duration::from_secs(1));
+    let now = Instant::now();
+    assert_eq!(budget.reserve(now), None);
+    assert_eq!(budget.reserve(now), None);
+    // the third call waits for half a second, the fourth behind it
+    assert_eq!(budget.reserve(now), Some(Duration::from_millis(500)));
+    assert_eq!(budget.reserve(now), Some(Duration::from_secs(1)));
+    assert_eq!(budget.reserve(now + Duration::from_secs(5)), None);
+  }
+
+  #[tokio::test]
+  async fn test_acquire_and_observe() {
+    let budget = RateBudget::new(1, Duration::from_millis(20));
+    let start = Instant::now();
+    tokio::join!(budget.acquire(), budget.acquire());
+    assert!(start.elapsed() >= Duration::from_millis(20));
+
+    budget.observe(&RateLimit { limit: Some(1), remaining: Some(0), reset: Some(Duration::from_mins(1)) });
+    assert!(budget.reserve(Instant::now() + Duration::from_secs(1)) > Some(Duration::from_secs(50)));
+  }
+}
@@ -0,0 +1,356 @@
+//! Reconcile webhook notifications with transaction polling
+//!
+//! Webhooks can be dropped, delayed or delivered out of order. The [`Reconciler`] merges the webhook
+//! event stream with periodic polling of vault transactions, backfills anything it missed via the REST
+//! API and emits a single stream with one update per transaction state change.
+//!
+//! ```
+//! use fireblocks_sdk::Client;
+//! use fireblocks_sdk::reconcile::Reconciler;
+//! use fireblocks_sdk::webhook::WebhookStream;
+//! use futures::StreamExt;
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! async fn updates(c: Client, events: WebhookStream) {
+//!   let reconciler = Reconciler::new(Arc::new(c), vec![0, 1]).with_interval(Duration::from_secs(30));
+//!   let mut updates = std::pin::pin!(reconciler.stream(events));
+//!   while let Some(update) = updates.next().await {
+//!     match update {
+//!       Ok(u) => tracing::info!("{} {:?} ({:?})", u.transaction.id, u.transaction.status, u.source),
+//!       Err(e) => tracing::warn!("reconcile failed {e}"),
+//!     }
+//!   }
+//! }
+//! ```
+use crate::types::{Transaction, TransactionStatus};
+use crate::webhook::WebhookEvent;
+use crate::{Client, Epoch, FireblocksError, PagedClient};
+use chrono::Utc;
+use futures::{Stream, StreamExt};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Where a [`TransactionUpdate`] was observed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateSource {
+  Webhook,
+  Poll,
+  /// Fetched from the REST API after a gap was detected
+  Backfill,
+}
+
+#[derive(Debug, Clone)]
+pub struct TransactionUpdate {
+  pub transaction: Transaction,
+  pub source: UpdateSource,
+}
+
+#[derive(Debug, Clone)]
+struct KnownState {
+  status: TransactionStatus,
+  sub_status: Option<String>,
+  last_updated: Epoch,
+  /// When the transaction was last fetched because it was stale
+  backfilled_at: Option<Epoch>,
+}
+
+/// Last known state of each transaction
+///
+/// Only state changes are reported, stale updates are dropped and a final status never regresses
+#[derive(Debug, Default)]
+pub struct TransactionTracker {
+  known: HashMap<String, KnownState>,
+}
+
+impl TransactionTracker {
+  /// Record the transaction, returns an update if its state changed
+  pub fn apply(&mut self, transaction: Transaction, source: UpdateSource) -> Option<TransactionUpdate> {
+    if let Some(known) = self.known.get(&transaction.id) {
+      if known.status.is_final() && !transaction.status.is_final() {
+        return None;
+      }
+      if transaction.last_updated < known.last_updated {
+        return None;
+      }
+      if known.status == transaction.status && known.sub_status == transaction.sub_status {
+        return None;
+      }
+    }
+    self.known.insert(
+      transaction.id.clone(),
+      KnownState {
+        status: transaction.status.clone(),
+        sub_status: transaction.sub_status.clone(),
+        last_updated: transaction.last_updated,
+        backfilled_at: None,
+      },
+    );
+    Some(TransactionUpdate { transaction, source })
+  }
+
+  pub fn is_known(&self, id: &str) -> bool {
+    self.known.contains_key(id)
+  }
+
+  /// Transactions that are not final and have not been updated since `before`, skipping those
+  /// [backfilled](Self::backfilled) since `backfilled_before`
+  pub fn stale(&self, before: Epoch, backfilled_before: Epoch) -> Vec<String> {
+    self
+      .known
+      .iter()
+      .filter(|(_, k)| !k.status.is_final() && k.last_updated < before)
+      .filter(|(_, k)| k.backfilled_at.is_none_or(|at| at < backfilled_before))
+      .map(|(id, _)| id.clone())
+      .collect()
+  }
+
+  /// Record that the stale transaction `id` was fetched at `at`, whether its state changed or not
+  pub fn backfilled(&mut self, id: &str, at: Epoch) {
+    if let Some(known) = self.known.get_mut(id) {
+      known.backfilled_at = Some(at);
+    }
+  }
+
+  /// Forget final transactions last updated before `before`
+  pub fn prune(&mut self, before: Epoch) {
+    self.known.retain(|_, k| !k.status.is_final() || k.last_updated >= before);
+  }
+
+  /// Forget every transaction last updated before `before`, final or not
+  pub fn expire(&mut self, before: Epoch) {
+    self.known.retain(|_, k| k.last_updated >= before);
+  }
+
+  pub fn len(&self) -> usize {
+    self.known.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.known.is_empty()
+  }
+}
+
+pub struct Reconciler {
+  client: Arc<Client>,
+  vaults: Vec<i32>,
+  interval: Duration,
+  lookback: Duration,
+  stale_after: Duration,
+  backfill_backoff: Duration,
+  horizon: Duration,
+  batch_size: u16,
+}
+
+impl Reconciler {
+  /// Reconcile transactions of the given vault accounts
+  ///
+  /// Defaults to polling every minute, looking back one hour, backfilling transactions without an
+  /// update for five minutes at most every five minutes, and tracking transactions for a day
+  pub const fn new(client: Arc<Client>, vaults: Vec<i32>) -> Self {
+    Self {
+      client,
+      vaults,
+      interval: Duration::from_mins(1),
+      lookback: Duration::from_hours(1),
+      stale_after: Duration::from_mins(5),
+      backfill_backoff: Duration::from_mins(5),
+      horizon: Duration::from_hours(24),
+      batch_size: 100,
+    }
+  }
+
+  #[must_use]
+  pub const fn with_interval(mut self, interval: Duration) -> Self {
+    self.interval = interval;
+    self
+  }
+
+  /// How far back each poll looks for transactions
+  #[must_use]
+  pub const fn with_lookback(mut self, lookback: Duration) -> Self {
+    self.lookback = lookback;
+    self
+  }
+
+  /// Backfill non final transactions that have not been updated for this long
+  #[must_use]
+  pub const fn with_stale_after(mut self, stale_after: Duration) -> Self {
+    self.stale_after = stale_after;
+    self
+  }
+
+  /// Wait this long before backfilling the same stale transaction again
+  #[must_use]
+  pub const fn with_backfill_backoff(mut self, backoff: Duration) -> Self {
+    self.backfill_backoff = backoff;
+    self
+  }
+
+  /// Stop tracking, and backfilling, transactions not updated for this long, e.g. stuck ones
+  #[must_use]
+  pub const fn with_horizon(mut self, horizon: Duration) -> Self {
+    self.horizon = horizon;
+    self
+  }
+
+  #[must_use]
+  pub const fn with_batch_size(mut self, batch_size: u16) -> Self {
+    self.batch_size = batch_size;
+    self
+  }
+
+  /// Merge `events` with polling into a single update stream
+  ///
+  /// The first poll happens immediately. The stream ends when `events` ends
+  pub fn stream<S>(self, events: S) -> impl Stream<Item = Result<TransactionUpdate, FireblocksError>>
+  where
+    S: Stream<Item = WebhookEvent> + Unpin,
+  {
+    let state = ReconcileState {
+      ticker: tokio::time::interval(self.interval),
+      reconciler: self,
+      events,
+      tracker: TransactionTracker::default(),
+      pending: VecDeque::new(),
+    };
+    futures::stream::unfold(state, |mut state| async move {
+      loop {
+        if let Some(item) = state.pending.pop_front() {
+          return Some((item, state));
+        }
+        let step = tokio::select! {
+          event = state.events.next() => Step::Event(event),
+          _ = state.ticker.tick() => Step::Poll,
+        };
+        match step {
+          Step::Event(None) => return None,
+          Step::Event(Some(event)) => state.on_event(event).await,
+          Step::Poll => state.poll().await,
+        }
+      }
+    })
+  }
+}
+
+#[allow(clippy::large_enum_variant)]
+enum Step {
+  Event(Option<WebhookEvent>),
+  Poll,
+}
+
+struct ReconcileState<S> {
+  reconciler: Reconciler,
+  events: S,
+  ticker: tokio::time::Interval,
+  tracker: TransactionTracker,
+  pending: VecDeque<Result<TransactionUpdate, FireblocksError>>,
+}
+
+impl<S> ReconcileState<S> {
+  fn apply(&mut self, transaction: Transaction, source: UpdateSource) {
+    if let Some(update) = self.tracker.apply(transaction, source) {
+      self.pending.push_back(Ok(update));
+    }
+  }
+
+  async fn backfill(&mut self, id: &str) {
+    match self.reconciler.client.get_transaction(id).await {
+      Ok((tx, _)) => self.apply(tx, UpdateSource::Backfill),
+      Err(e) => self.pending.push_back(Err(e)),
+    }
+  }
+
+  async fn on_event(&mut self, event: WebhookEvent) {
+    let created = matches!(event, WebhookEvent::TRANSACTION_CREATED(_));
+    let Some(tx) = event.transaction() else {
+      return;
+    };
+    if created || self.tracker.is_known(&tx.id) {
+      self.apply(tx.clone(), UpdateSource::Webhook);
+    } else {
+      // a status update for a transaction we never saw, earlier events were missed
+      let id = tx.id.clone();
+      self.backfill(&id).await;
+    }
+  }
+
+  async fn poll(&mut self) {
+    let now = Utc::now();
+    let lookback = chrono::Duration::from_std(self.reconciler.lookback).unwrap_or_default();
+    let after = now - lookback;
+    let paged = PagedClient::new(self.reconciler.client.clone());
+    let batch_size = self.reconciler.batch_size;
+    for vault in self.reconciler.vaults.clone() {
+      let sources = paged.transactions_from_source(vault, batch_size, Some(after));
+      let destinations = paged.transactions_from_destination(vault, batch_size, Some(after));
+      for mut transactions in [sources, destinations] {
+        while let Some(page) = transactions.next().await {
+          match page {
+            Ok((txs, _)) => {
+              for tx in txs {
+                self.apply(tx, UpdateSource::Poll);
+              }
+            },
+            Err(e) => {
+              self.pending.push_back(Err(e));
+              break;
+            },
+          }
+        }
+      }
+    }
+    let horizon = chrono::Duration::from_std(self.reconciler.horizon).unwrap_or(chrono::Duration::MAX);
+    self.tracker.expire(now.checked_sub_signed(horizon).unwrap_or(Epoch::UNIX_EPOCH));
+    let stale_after = chrono::Duration::from_std(self.reconciler.stale_after).unwrap_or_default();
+    let backoff = chrono::Duration::from_std(self.reconciler.backfill_backoff).unwrap_or_default();
+    for id in self.tracker.stale(now - stale_after, now - backoff) {
+      self.backfill(&id).await;
+      self.tracker.backfilled(&id, now);
+    }
+    self.tracker.prune(after);
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn tx(status: TransactionStatus, minutes: i64) -> Transaction {
+    Transaction {
+      id: "tx-1".to_string(),
+      status,
+      last_updated: Epoch::UNIX_EPOCH + chrono::Duration::minutes(minutes),
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn test_tracker() {
+    let mut tracker = TransactionTracker::default();
+    assert!(tracker.apply(tx(TransactionStatus::SUBMITTED, 1), UpdateSource::Webhook).is_some());
+    assert!(tracker.apply(tx(TransactionStatus::SUBMITTED, 1), UpdateSource::Poll).is_none());
+    assert!(tracker.apply(tx(TransactionStatus::BROADCASTING, 3), UpdateSource::Poll).is_some());
+    // out of order webhook
+    assert!(tracker.apply(tx(TransactionStatus::PENDING_SIGNATURE, 2), UpdateSource::Webhook).is_none());
+    let at = |minutes| Epoch::UNIX_EPOCH + chrono::Duration::minutes(minutes);
+    assert_eq!(tracker.stale(at(10), at(10)), vec!["tx-1".to_string()]);
+    tracker.backfilled("tx-1", at(10));
+    assert!(tracker.stale(at(11), at(6)).is_empty());
+    assert_eq!(tracker.stale(at(15), at(11)), vec!["tx-1".to_string()]);
+    let update = tracker.apply(tx(TransactionStatus::COMPLETED, 4), UpdateSource::Backfill);
+    assert_eq!(update.map(|u| u.source), Some(UpdateSource::Backfill));
+    assert!(tracker.apply(tx(TransactionStatus::CONFIRMING, 5), UpdateSource::Webhook).is_none());
+    assert!(tracker.stale(at(20), at(20)).is_empty());
+    tracker.prune(at(10));
+    assert!(tracker.is_empty());
+
+    // a transaction stuck past the horizon is dropped
+    tracker.apply(tx(TransactionStatus::PENDING_SIGNATURE, 1), UpdateSource::Poll);
+    tracker.expire(at(1));
+    assert_eq!(tracker.len(), 1);
+    tracker.expire(at(2));
+    assert!(tracker.is_empty());
+  }
+}
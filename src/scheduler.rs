@@ -0,0 +1,303 @@
+//! Recurring transfers, e.g. vendor payments or treasury sweeps
+//!
+//! ```
+//! use fireblocks_sdk::Client;
+//! use fireblocks_sdk::scheduler::{MemoryStore, RecurringTransfer, Scheduler, TransferAmount};
+//! use fireblocks_sdk::types::DestinationTransferPeerPath;
+//! use std::time::Duration;
+//! use tokio_util::sync::CancellationToken;
+//!
+//! async fn sweep(c: Client, shutdown: CancellationToken) -> color_eyre::Result<()> {
+//!   let mut scheduler = Scheduler::new(c, MemoryStore::default());
+//!   scheduler.add(RecurringTransfer {
+//!     id: "nightly-sweep".to_string(),
+//!     interval: Duration::from_secs(24 * 60 * 60),
+//!     source_vault: 3,
+//!     destination: DestinationTransferPeerPath { id: "0".to_string(), ..Default::default() },
+//!     asset: "USDC".to_string(),
+//!     amount: TransferAmount::SweepAll,
+//!     note: None,
+//!   });
+//!   scheduler.run(Duration::from_secs(60), shutdown).await;
+//!   Ok(())
+//! }
+//! ```
+use crate::deadline;
+use crate::error::StoreError;
+use crate::types::{DestinationTransferPeerPath, TransactionArguments, TransactionOperation, TransferPeerPath};
+use crate::{Client, Epoch, FireblocksError};
+use bigdecimal::{BigDecimal, Zero};
+use chrono::Utc;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransferAmount {
+  Fixed(BigDecimal),
+  /// Transfer the maximum spendable amount (balance minus fees) of the source vault
+  SweepAll,
+}
+
+/// A transfer executed every `interval`
+#[derive(Debug, Clone)]
+pub struct RecurringTransfer {
+  /// Unique id, used as the key of the persisted state
+  pub id: String,
+  pub interval: Duration,
+  pub source_vault: i32,
+  pub destination: DestinationTransferPeerPath,
+  pub asset: String,
+  pub amount: TransferAmount,
+  pub note: Option<String>,
+}
+
+impl RecurringTransfer {
+  /// Is the transfer due at `now`, given the time of its last successful run
+  pub fn is_due(&self, last_success: Option<Epoch>, now: Epoch) -> bool {
+    last_success.is_none_or(|last| (now - last).to_std().is_ok_and(|elapsed| elapsed >= self.interval))
+  }
+
+  /// Id of the period `now` falls in, used to make each period's transaction idempotent
+  fn external_tx_id(&self, now: Epoch) -> String {
+    let secs = i64::try_from(self.interval.as_secs().max(1)).unwrap_or(i64::MAX);
+    format!("{}-{}", self.id, now.timestamp() / secs)
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunOutcome {
+  Submitted {
+    tx_id: String,
+  },
+  /// Nothing to do, e.g. an empty balance to sweep
+  Skipped {
+    reason: String,
+  },
+  Failed {
+    error: String,
+  },
+}
+
+#[derive(Debug, Clone)]
+pub struct ScheduledRun {
+  pub transfer_id: String,
+  pub at: Epoch,
+  pub attempts: u32,
+  pub outcome: RunOutcome,
+}
+
+/// Persists the state of the scheduler so restarts don't re-execute or skip transfers
+pub trait SchedulerStore: Send + Sync {
+  /// Time of the last run of `transfer_id` that did not fail
+  fn last_success(&self, transfer_id: &str) -> impl Future<Output = Result<Option<Epoch>, StoreError>> + Send;
+  fn record(&self, run: &ScheduledRun) -> impl Future<Output = Result<(), StoreError>> + Send;
+}
+
+/// Keeps state in memory, which is lost on restart
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+  runs: Mutex<HashMap<String, Vec<ScheduledRun>>>,
+}
+
+impl MemoryStore {
+  pub fn runs(&self, transfer_id: &str) -> Vec<ScheduledRun> {
+    self.runs.lock().map(|r| r.get(transfer_id).cloned().unwrap_or_default()).unwrap_or_default()
+  }
+}
+
+impl SchedulerStore for MemoryStore {
+  async fn last_success(&self, transfer_id: &str) -> Result<Option<Epoch>, StoreError> {
+    let runs = self.runs.lock().map_err(|e| StoreError(e.to_string()))?;
+    Ok(
+      runs
+        .get(transfer_id)
+        .and_then(|r| r.iter().filter(|r| !matches!(r.outcome, RunOutcome::Failed { .. })).map(|r| r.at).max()),
+    )
+  }
+
+  async fn record(&self, run: &ScheduledRun) -> Result<(), StoreError> {
+    self
+      .runs
+      .lock()
+      .map_err(|e| StoreError(e.to_string()))?
+      .entry(run.transfer_id.clone())
+      .or_default()
+      .push(run.clone());
+    Ok(())
+  }
+}
+
+pub struct Scheduler<S> {
+  client: Client,
+  store: S,
+  transfers: Vec<RecurringTransfer>,
+  max_attempts: u32,
+  backoff: Duration,
+}
+
+impl<S: SchedulerStore> Scheduler<S> {
+  pub const fn new(client: Client, store: S) -> Self {
+    Self { client, store, transfers: Vec::new(), max_attempts: 3, backoff: Duration::from_secs(5) }
+  }
+
+  /// Retry a failed transfer up to `max_attempts` times, sleeping `backoff` (doubling) in between. Retries
+  /// also stop at the [`Deadline`](crate::deadline::Deadline) of the caller
+  #[allow(clippy::return_self_not_must_use)]
+  pub fn with_retries(mut self, max_attempts: u32, backoff: Duration) -> Self {
+    self.max_attempts = max_attempts.max(1);
+    self.backoff = backoff;
+    self
+  }
+
+  /// Add a transfer, replacing any existing transfer with the same id
+  pub fn add(&mut self, transfer: RecurringTransfer) {
+    self.remove(&transfer.id);
+    self.transfers.push(transfer);
+  }
+
+  pub fn remove(&mut self, id: &str) -> Option<RecurringTransfer> {
+    let pos = self.transfers.iter().position(|t| t.id == id)?;
+    Some(self.transfers.remove(pos))
+  }
+
+  pub fn transfers(&self) -> &[RecurringTransfer] {
+    &self.transfers
+  }
+
+  pub const fn store(&self) -> &S {
+    &self.store
+  }
+
+  /// Execute every transfer which is due at `now`, once
+  pub async fn run_due(&self, now: Epoch) -> Result<Vec<ScheduledRun>, StoreError> {
+    let mut runs = Vec::new();
+    for t in &self.transfers {
+      if !t.is_due(self.store.last_success(&t.id).await?, now) {
+        continue;
+      }
+      let run = self.execute(t, now).await;
+      self.store.record(&run).await?;
+      runs.push(run);
+    }
+    Ok(runs)
+  }
+
+  /// Check for due transfers every `tick` until `shutdown` is cancelled
+  pub async fn run(&self, tick: Duration, shutdown: CancellationToken) {
+    loop {
+      match self.run_due(Utc::now()).await {
+        Ok(runs) => {
+          for run in runs {
+            tracing::info!("scheduled transfer {} {:?}", run.transfer_id, run.outcome);
+          }
+        },
+        Err(e) => tracing::error!("scheduler store failure {e}"),
+      }
+      tokio::select! {
+        () = shutdown.cancelled() => break,
+        () = tokio::time::sleep(tick) => {},
+      }
+    }
+  }
+
+  async fn execute(&self, t: &RecurringTransfer, now: Epoch) -> ScheduledRun {
+    let mut attempts = 0;
+    let mut backoff = self.backoff;
+    let outcome = loop {
+      attempts += 1;
+      match self.submit(t, now).await {
+        Ok(outcome) => break outcome,
+        Err(e) if attempts >= self.max_attempts || !deadline::retry(backoff) => {
+          break RunOutcome::Failed { error: e.to_string() }
+        },
+        Err(e) => {
+          tracing::warn!("transfer {} attempt {attempts} failed: {e}", t.id);
+          tokio::time::sleep(backoff).await;
+          backoff *= 2;
+        },
+      }
+    };
+    ScheduledRun { transfer_id: t.id.clone(), at: now, attempts, outcome }
+  }
+
+  async fn submit(&self, t: &RecurringTransfer, now: Epoch) -> Result<RunOutcome, FireblocksError> {
+    let external_tx_id = t.external_tx_id(now);
+    // the transaction of this period may have been submitted before the run was recorded
+    match self.client.get_transaction_external(&external_tx_id).await {
+      Ok((tx, _)) => return Ok(RunOutcome::Submitted { tx_id: tx.id }),
+      Err(FireblocksError::NotFound { .. }) => {},
+      Err(e) => return Err(e),
+    }
+    let amount = match &t.amount {
+      TransferAmount::Fixed(a) => a.clone(),
+      TransferAmount::SweepAll => {
+        self.client.vault_max_spendable(t.source_vault, &t.asset).await?.0.max_spendable_amount
+      },
+    };
+    if amount <= BigDecimal::zero() {
+      return Ok(RunOutcome::Skipped { reason: format!("nothing to transfer from vault {}", t.source_vault) });
+    }
+    let args = TransactionArguments {
+      asset_id: t.asset.clone(),
+      operation: TransactionOperation::TRANSFER,
+      source: TransferPeerPath { id: Some(t.source_vault.to_string()), ..Default::default() },
+      destination: Some(t.destination.clone()),
+      amount: amount.to_string(),
+      gas_price: None,
+      gas_limit: None,
+      fee_level: None,
+      note: t.note.clone().unwrap_or_else(|| format!("recurring transfer {}", t.id)),
+      extra_parameters: None,
+      destinations: None,
+      external_tx_id: Some(external_tx_id),
+    };
+    let (tx, _) = self.client.create_transaction(&args).await?;
+    Ok(RunOutcome::Submitted { tx_id: tx.id })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use chrono::TimeZone;
+
+  fn transfer() -> RecurringTransfer {
+    RecurringTransfer {
+      id: "rent".to_string(),
+      interval: Duration::from_hours(1),
+      source_vault: 0,
+      destination: DestinationTransferPeerPath::default(),
+      asset: "BTC_TEST".to_string(),
+      amount: TransferAmount::SweepAll,
+      note: None,
+    }
+  }
+
+  #[test]
+  fn due() {
+    let t = transfer();
+    let now = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+    assert!(t.is_due(None, now));
+    assert!(!t.is_due(Some(now - chrono::Duration::minutes(59)), now));
+    assert!(t.is_due(Some(now - chrono::Duration::minutes(60)), now));
+    assert!(!t.is_due(Some(now + chrono::Duration::minutes(1)), now));
+    assert_eq!(t.external_tx_id(now), t.external_tx_id(now + chrono::Duration::minutes(59)));
+    assert_ne!(t.external_tx_id(now), t.external_tx_id(now + chrono::Duration::minutes(60)));
+  }
+
+  #[tokio::test]
+  async fn memory_store() -> color_eyre::Result<()> {
+    let store = MemoryStore::default();
+    let at = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+    assert!(store.last_success("rent").await?.is_none());
+    let run = |at, outcome| ScheduledRun { transfer_id: "rent".to_string(), at, attempts: 1, outcome };
+    store.record(&run(at, RunOutcome::Submitted { tx_id: "1".to_string() })).await?;
+    store.record(&run(at + chrono::Duration::hours(1), RunOutcome::Failed { error: "oops".to_string() })).await?;
+    assert_eq!(Some(at), store.last_success("rent").await?);
+    assert_eq!(2, store.runs("rent").len());
+    Ok(())
+  }
+}
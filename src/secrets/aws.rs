@@ -0,0 +1,172 @@
+use crate::error::SecretError;
+use crate::secrets::SecretSource;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::fmt::Write;
+
+const SERVICE: &str = "secretsmanager";
+const TARGET: &str = "secretsmanager.GetSecretValue";
+const CONTENT_TYPE: &str = "application/x-amz-json-1.1";
+
+#[derive(Clone, Default)]
+pub struct AwsCredentials {
+  pub access_key_id: String,
+  pub secret_access_key: String,
+  pub session_token: Option<String>,
+}
+
+impl std::fmt::Debug for AwsCredentials {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("AwsCredentials").field("access_key_id", &self.access_key_id).finish_non_exhaustive()
+  }
+}
+
+impl AwsCredentials {
+  /// `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and the optional `AWS_SESSION_TOKEN`
+  pub fn from_env() -> Result<Self, SecretError> {
+    let var = |name: &str| std::env::var(name).map_err(|_| SecretError::Response(format!("{name} is not set")));
+    Ok(Self {
+      access_key_id: var("AWS_ACCESS_KEY_ID")?,
+      secret_access_key: var("AWS_SECRET_ACCESS_KEY")?,
+      session_token: std::env::var("AWS_SESSION_TOKEN").ok(),
+    })
+  }
+}
+
+/// An AWS Secrets Manager secret, either `SecretString` or `SecretBinary`
+///
+/// See
+/// * [GetSecretValue](https://docs.aws.amazon.com/secretsmanager/latest/apireference/API_GetSecretValue.html)
+#[derive(Debug, Clone)]
+pub struct AwsSecret {
+  client: reqwest::Client,
+  region: String,
+  secret_id: String,
+  credentials: AwsCredentials,
+}
+
+impl AwsSecret {
+  /// `secret_id` is the name or ARN of the secret
+  pub fn new(region: &str, secret_id: &str, credentials: AwsCredentials) -> Self {
+    Self { client: reqwest::Client::new(), region: region.to_string(), secret_id: secret_id.to_string(), credentials }
+  }
+
+  /// Region from `AWS_REGION` or `AWS_DEFAULT_REGION`, see [`AwsCredentials::from_env`]
+  pub fn from_env(secret_id: &str) -> Result<Self, SecretError> {
+    let region = std::env::var("AWS_REGION")
+      .or_else(|_| std::env::var("AWS_DEFAULT_REGION"))
+      .map_err(|_| SecretError::Response(String::from("AWS_REGION is not set")))?;
+    Ok(Self::new(&region, secret_id, AwsCredentials::from_env()?))
+  }
+
+  fn host(&self) -> String {
+    format!("{SERVICE}.{}.amazonaws.com", self.region)
+  }
+
+  /// Signature version 4 `Authorization` header
+  fn authorization(&self, body: &[u8], now: DateTime<Utc>) -> String {
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date = now.format("%Y%m%d").to_string();
+    let mut headers = vec![
+      ("content-type", CONTENT_TYPE.to_string()),
+      ("host", self.host()),
+      ("x-amz-date", amz_date.clone()),
+      ("x-amz-target", TARGET.to_string()),
+    ];
+    if let Some(token) = &self.credentials.session_token {
+      headers.push(("x-amz-security-token", token.clone()));
+    }
+    headers.sort_by_key(|(name, _)| *name);
+    let canonical_headers = headers.iter().fold(String::new(), |mut out, (name, value)| {
+      let _ = writeln!(out, "{name}:{value}");
+      out
+    });
+    let signed_headers = headers.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(";");
+    let canonical_request =
+      format!("POST\n/\n\n{canonical_headers}\n{signed_headers}\n{}", hex::encode(Sha256::digest(body)));
+    let scope = format!("{date}/{}/{SERVICE}/aws4_request", self.region);
+    let string_to_sign =
+      format!("AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}", hex::encode(Sha256::digest(canonical_request.as_bytes())));
+    let key = signing_key(&self.credentials.secret_access_key, &date, &self.region, SERVICE);
+    let signature = hex::encode(hmac_sha256(&key, string_to_sign.as_bytes()));
+    format!(
+      "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+      self.credentials.access_key_id
+    )
+  }
+}
+
+impl SecretSource for AwsSecret {
+  async fn fetch(&self) -> Result<Vec<u8>, SecretError> {
+    let body = serde_json::json!({ "SecretId": self.secret_id }).to_string().into_bytes();
+    let now = Utc::now();
+    let mut req = self
+      .client
+      .post(format!("https://{}/", self.host()))
+      .header("content-type", CONTENT_TYPE)
+      .header("x-amz-date", now.format("%Y%m%dT%H%M%SZ").to_string())
+      .header("x-amz-target", TARGET)
+      .header("authorization", self.authorization(&body, now));
+    if let Some(token) = &self.credentials.session_token {
+      req = req.header("x-amz-security-token", token);
+    }
+    let value: Value = req.body(body).send().await?.error_for_status()?.json().await?;
+    if let Some(s) = value["SecretString"].as_str() {
+      return Ok(s.as_bytes().to_vec());
+    }
+    let binary = value["SecretBinary"]
+      .as_str()
+      .ok_or_else(|| SecretError::Response(format!("no SecretString or SecretBinary for {}", self.secret_id)))?;
+    STANDARD.decode(binary).map_err(|e| SecretError::Response(format!("invalid SecretBinary: {e}")))
+  }
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+  // HMAC accepts keys of any length
+  let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("hmac key");
+  mac.update(data);
+  mac.finalize().into_bytes().into()
+}
+
+fn signing_key(secret: &str, date: &str, region: &str, service: &str) -> [u8; 32] {
+  let k_date = hmac_sha256(format!("AWS4{secret}").as_bytes(), date.as_bytes());
+  let k_region = hmac_sha256(&k_date, region.as_bytes());
+  let k_service = hmac_sha256(&k_region, service.as_bytes());
+  hmac_sha256(&k_service, b"aws4_request")
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_hmac() {
+    // RFC 4231 test case 2
+    assert_eq!(
+      hex::encode(hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+      "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+    );
+  }
+
+  #[test]
+  fn test_signing_key() {
+    // from the AWS signature version 4 documentation
+    let key = signing_key("wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY", "20120215", "us-east-1", "iam");
+    assert_eq!(hex::encode(key), "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d");
+  }
+
+  #[test]
+  fn test_authorization() -> color_eyre::Result<()> {
+    let credentials =
+      AwsCredentials { access_key_id: "AKID".into(), secret_access_key: "secret".into(), session_token: None };
+    let secret = AwsSecret::new("us-east-1", "fireblocks", credentials);
+    let auth = secret.authorization(b"{}", "2024-01-01T00:00:00Z".parse()?);
+    assert!(auth.starts_with("AWS4-HMAC-SHA256 Credential=AKID/20240101/us-east-1/secretsmanager/aws4_request, "));
+    assert!(auth.contains("SignedHeaders=content-type;host;x-amz-date;x-amz-target, "));
+    Ok(())
+  }
+}
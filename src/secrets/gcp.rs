@@ -0,0 +1,80 @@
+use crate::error::SecretError;
+use crate::secrets::SecretSource;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde_json::Value;
+
+const METADATA_TOKEN: &str =
+  "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
+
+/// A version of a GCP Secret Manager secret
+///
+/// Without [`GcpSecret::with_token`] the access token of the service account is taken from the
+/// metadata server, i.e. when running on GCE, GKE or Cloud Run
+///
+/// See
+/// * [accessSecretVersion](https://cloud.google.com/secret-manager/docs/reference/rest/v1/projects.secrets.versions/access)
+#[derive(Clone)]
+pub struct GcpSecret {
+  client: reqwest::Client,
+  name: String,
+  token: Option<String>,
+}
+
+impl std::fmt::Debug for GcpSecret {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("GcpSecret").field("name", &self.name).finish_non_exhaustive()
+  }
+}
+
+impl GcpSecret {
+  /// The latest version of `secret` in `project`
+  pub fn new(project: &str, secret: &str) -> Self {
+    Self::with_version(project, secret, "latest")
+  }
+
+  pub fn with_version(project: &str, secret: &str, version: &str) -> Self {
+    Self {
+      client: reqwest::Client::new(),
+      name: format!("projects/{project}/secrets/{secret}/versions/{version}"),
+      token: None,
+    }
+  }
+
+  /// Use an OAuth access token instead of the metadata server
+  #[must_use]
+  pub fn with_token(mut self, token: &str) -> Self {
+    self.token = Some(token.to_string());
+    self
+  }
+
+  async fn access_token(&self) -> Result<String, SecretError> {
+    if let Some(token) = &self.token {
+      return Ok(token.clone());
+    }
+    let body: Value = self
+      .client
+      .get(METADATA_TOKEN)
+      .header("Metadata-Flavor", "Google")
+      .send()
+      .await?
+      .error_for_status()?
+      .json()
+      .await?;
+    body["access_token"]
+      .as_str()
+      .map(ToString::to_string)
+      .ok_or_else(|| SecretError::Response(String::from("no access_token from the metadata server")))
+  }
+}
+
+impl SecretSource for GcpSecret {
+  async fn fetch(&self) -> Result<Vec<u8>, SecretError> {
+    let url = format!("https://secretmanager.googleapis.com/v1/{}:access", self.name);
+    let body: Value =
+      self.client.get(url).bearer_auth(self.access_token().await?).send().await?.error_for_status()?.json().await?;
+    let data =
+      body["payload"]["data"].as_str().ok_or_else(|| SecretError::Response(format!("no payload for {}", self.name)))?;
+    STANDARD.decode(data).map_err(|e| SecretError::Response(format!("invalid payload for {}: {e}", self.name)))
+  }
+}
@@ -0,0 +1,92 @@
+//! Load the API user's RSA secret from a secret manager, so it never touches disk
+//!
+//! Providers are behind the `aws-secrets`, `gcp-secrets` and `vault-secrets` features
+//!
+//! ```
+//! use fireblocks_sdk::secrets::SecretSource;
+//! use fireblocks_sdk::ClientBuilder;
+//! use std::time::Duration;
+//! use tokio_util::sync::CancellationToken;
+//!
+//! async fn client(source: impl SecretSource, shutdown: CancellationToken) -> color_eyre::Result<()> {
+//!   let client = ClientBuilder::new("api-key", b"").build_with_secret(&source).await?;
+//!   // pick up rotated keys every hour
+//!   client.refresh_secret(&source, Duration::from_secs(3600), shutdown).await;
+//!   Ok(())
+//! }
+//! ```
+#[cfg(feature = "aws-secrets")]
+mod aws;
+#[cfg(feature = "gcp-secrets")]
+mod gcp;
+#[cfg(feature = "vault-secrets")]
+mod vault;
+
+#[cfg(feature = "aws-secrets")]
+pub use aws::*;
+#[cfg(feature = "gcp-secrets")]
+pub use gcp::*;
+#[cfg(feature = "vault-secrets")]
+pub use vault::*;
+
+use crate::error::SecretError;
+use crate::{Client, ClientBuilder};
+use std::future::Future;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// Where the PEM encoded RSA secret is stored
+pub trait SecretSource {
+  fn fetch(&self) -> impl Future<Output = Result<Vec<u8>, SecretError>> + Send;
+}
+
+impl ClientBuilder {
+  /// Build with the secret fetched from `source` instead of the one given to [`ClientBuilder::new`]
+  pub async fn build_with_secret(self, source: &impl SecretSource) -> Result<Client, SecretError> {
+    let secret = source.fetch().await?;
+    Ok(self.with_secret(&secret).build()?)
+  }
+}
+
+impl Client {
+  /// Re-fetch the secret every `interval` until `shutdown` is cancelled. Failures are logged and the
+  /// current secret is kept
+  pub async fn refresh_secret(&self, source: &impl SecretSource, interval: Duration, shutdown: CancellationToken) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.tick().await;
+    loop {
+      tokio::select! {
+        () = shutdown.cancelled() => break,
+        _ = ticker.tick() => {},
+      }
+      let rotated = match source.fetch().await {
+        Ok(secret) => self.rotate_secret(&secret).map_err(SecretError::from),
+        Err(e) => Err(e),
+      };
+      if let Err(e) = rotated {
+        tracing::warn!("failed to refresh the API secret {e}");
+      }
+    }
+  }
+}
+
+#[cfg(all(test, feature = "reqwest"))]
+mod test {
+  use super::*;
+
+  struct TestSecret;
+
+  impl SecretSource for TestSecret {
+    async fn fetch(&self) -> Result<Vec<u8>, SecretError> {
+      Ok(include_bytes!("../webhook/testdata/webhook_private.pem").to_vec())
+    }
+  }
+
+  #[tokio::test]
+  async fn test_build_with_secret() -> color_eyre::Result<()> {
+    let client = ClientBuilder::new("key", b"not a key").build_with_secret(&TestSecret).await?;
+    assert!(client.rotate_secret(b"not a key").is_err());
+    client.rotate_secret(&TestSecret.fetch().await?)?;
+    Ok(())
+  }
+}
@@ -0,0 +1,66 @@
+use crate::error::SecretError;
+use crate::secrets::SecretSource;
+use serde_json::Value;
+use url::Url;
+
+/// A key of a `HashiCorp` Vault KV v2 secret
+///
+/// See
+/// * [KV v2](https://developer.hashicorp.com/vault/api-docs/secret/kv/kv-v2#read-secret-version)
+#[derive(Clone)]
+pub struct VaultSecret {
+  client: reqwest::Client,
+  addr: Url,
+  token: String,
+  mount: String,
+  path: String,
+  key: String,
+}
+
+impl std::fmt::Debug for VaultSecret {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("VaultSecret").field("addr", &self.addr).field("path", &self.path).finish_non_exhaustive()
+  }
+}
+
+impl VaultSecret {
+  /// `key` of the secret at `path` of the `secret` mount
+  pub fn new(addr: Url, token: &str, path: &str, key: &str) -> Self {
+    Self {
+      client: reqwest::Client::new(),
+      addr,
+      token: token.to_string(),
+      mount: String::from("secret"),
+      path: path.to_string(),
+      key: key.to_string(),
+    }
+  }
+
+  /// Address and token from `VAULT_ADDR` and `VAULT_TOKEN`
+  pub fn from_env(path: &str, key: &str) -> Result<Self, SecretError> {
+    let var = |name: &str| std::env::var(name).map_err(|_| SecretError::Response(format!("{name} is not set")));
+    let addr = var("VAULT_ADDR")?.parse().map_err(|e| SecretError::Response(format!("invalid VAULT_ADDR {e}")))?;
+    Ok(Self::new(addr, &var("VAULT_TOKEN")?, path, key))
+  }
+
+  #[must_use]
+  pub fn with_mount(mut self, mount: &str) -> Self {
+    self.mount = mount.to_string();
+    self
+  }
+}
+
+impl SecretSource for VaultSecret {
+  async fn fetch(&self) -> Result<Vec<u8>, SecretError> {
+    let url = self
+      .addr
+      .join(&format!("v1/{}/data/{}", self.mount, self.path))
+      .map_err(|e| SecretError::Response(e.to_string()))?;
+    let body: Value =
+      self.client.get(url).header("X-Vault-Token", &self.token).send().await?.error_for_status()?.json().await?;
+    body["data"]["data"][&self.key]
+      .as_str()
+      .map(|s| s.as_bytes().to_vec())
+      .ok_or_else(|| SecretError::Response(format!("key {} not found in {}", self.key, self.path)))
+  }
+}
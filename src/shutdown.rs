@@ -0,0 +1,178 @@
+use crate::deadline::{self, Deadline};
+use crate::json_stream::ItemStream;
+use crate::{Client, FireblocksError};
+use futures::StreamExt;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio_util::sync::CancellationToken;
+
+/// Calls in flight on a client and its clones, and whether it still accepts new ones
+#[derive(Debug, Default)]
+pub struct Lifecycle {
+  closed: AtomicBool,
+  in_flight: AtomicUsize,
+  idle: Notify,
+  abort: CancellationToken,
+}
+
+/// One call in flight, counted until dropped
+#[derive(Debug)]
+pub struct InFlight {
+  lifecycle: Arc<Lifecycle>,
+  path: String,
+}
+
+impl Drop for InFlight {
+  fn drop(&mut self) {
+    if self.lifecycle.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+      self.lifecycle.idle.notify_waiters();
+    }
+  }
+}
+
+impl Lifecycle {
+  /// Start a call, unless the client is shutting down
+  pub fn enter(self: &Arc<Self>, path: &str) -> Result<InFlight, FireblocksError> {
+    self.in_flight.fetch_add(1, Ordering::SeqCst);
+    let flight = InFlight { lifecycle: Arc::clone(self), path: String::from(path) };
+    if self.closed.load(Ordering::SeqCst) {
+      return Err(FireblocksError::ShuttingDown { path: flight.path.clone() });
+    }
+    Ok(flight)
+  }
+
+  async fn shutdown(&self, deadline: Duration) -> usize {
+    self.closed.store(true, Ordering::SeqCst);
+    let drained = tokio::time::timeout(deadline, async {
+      loop {
+        let idle = self.idle.notified();
+        if self.in_flight.load(Ordering::SeqCst) == 0 {
+          return;
+        }
+        idle.await;
+      }
+    })
+    .await;
+    if drained.is_ok() {
+      return 0;
+    }
+    let aborted = self.in_flight.load(Ordering::SeqCst);
+    self.abort.cancel();
+    aborted
+  }
+}
+
+impl InFlight {
+  /// Run `call`, failing with [`FireblocksError::Aborted`] when the shutdown deadline passes first, or
+  /// [`FireblocksError::DeadlineExceeded`] when the [`Deadline`] of the caller does
+  pub async fn run<T, F>(&self, call: F) -> crate::Result<T>
+  where
+    F: Future<Output = crate::Result<T>>,
+  {
+    let deadline = deadline::current();
+    if deadline.as_ref().is_some_and(Deadline::is_expired) {
+      return Err(FireblocksError::DeadlineExceeded { path: self.path.clone() });
+    }
+    tokio::select! {
+      result = call => result,
+      () = self.lifecycle.abort.cancelled() => Err(FireblocksError::Aborted { path: self.path.clone() }),
+      () = deadline::expired(deadline.as_ref()) => Err(FireblocksError::DeadlineExceeded { path: self.path.clone() }),
+    }
+  }
+
+  /// Keep counting the call while the body of `stream` is read, ending it with
+  /// [`FireblocksError::Aborted`] when the shutdown deadline passes first, or
+  /// [`FireblocksError::DeadlineExceeded`] when the [`Deadline`] of the caller does
+  pub fn guard<T: Send + 'static>(self, stream: ItemStream<T>) -> ItemStream<T> {
+    let deadline = deadline::current();
+    Box::pin(futures::stream::unfold(Some((stream, self)), move |state| {
+      let deadline = deadline.clone();
+      async move {
+        let (mut stream, flight) = state?;
+        tokio::select! {
+          item = stream.next() => item.map(|item| (item, Some((stream, flight)))),
+          () = flight.lifecycle.abort.cancelled() => {
+            let path = flight.path.clone();
+            Some((Err(FireblocksError::Aborted { path }), None))
+          },
+          () = deadline::expired(deadline.as_ref()) => {
+            let path = flight.path.clone();
+            Some((Err(FireblocksError::DeadlineExceeded { path }), None))
+          },
+        }
+      }
+    }))
+  }
+}
+
+impl Client {
+  /// Stop accepting new calls on this client and its clones, wait up to `deadline` for the calls and
+  /// stream page fetches in flight, then abort the rest with [`FireblocksError::Aborted`]
+  ///
+  /// Returns the number of calls aborted. An aborted call may still have reached Fireblocks, e.g. a
+  /// transaction can have been created, so look it up by its external tx id before submitting it again.
+  pub async fn shutdown(&self, deadline: Duration) -> usize {
+    self.lifecycle().shutdown(deadline).await
+  }
+
+  pub fn is_shutting_down(&self) -> bool {
+    self.lifecycle().closed.load(Ordering::SeqCst)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[tokio::test]
+  async fn test_drain() -> color_eyre::Result<()> {
+    let lifecycle = Arc::new(Lifecycle::default());
+    let flight = lifecycle.enter("/vault/accounts/0")?;
+    let call = tokio::spawn(async move {
+      let result = flight.run(async { Ok(((), String::from("request"))) }).await;
+      tokio::time::sleep(Duration::from_millis(20)).await;
+      drop(flight);
+      result
+    });
+    assert_eq!(lifecycle.shutdown(Duration::from_secs(5)).await, 0);
+    assert!(call.await?.is_ok());
+    assert!(matches!(lifecycle.enter("/transactions"), Err(FireblocksError::ShuttingDown { .. })));
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn test_abort() -> color_eyre::Result<()> {
+    let lifecycle = Arc::new(Lifecycle::default());
+    let flight = lifecycle.enter("/transactions")?;
+    let call = tokio::spawn(async move { flight.run(std::future::pending::<crate::Result<()>>()).await });
+    let stream = lifecycle.enter("/audits")?.guard::<u8>(Box::pin(futures::stream::pending()));
+    assert_eq!(lifecycle.shutdown(Duration::from_millis(10)).await, 2);
+    assert!(matches!(call.await?, Err(FireblocksError::Aborted { path }) if path == "/transactions"));
+    let items = stream.collect::<Vec<_>>().await;
+    assert!(matches!(items.as_slice(), [Err(FireblocksError::Aborted { .. })]));
+    assert_eq!(lifecycle.in_flight.load(Ordering::SeqCst), 0);
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn test_deadline() -> color_eyre::Result<()> {
+    let lifecycle = Arc::new(Lifecycle::default());
+    let flight = lifecycle.enter("/transactions")?;
+    let deadline = Deadline::after(Duration::from_millis(10));
+    let result =
+      deadline::with_deadline(deadline.clone(), flight.run(std::future::pending::<crate::Result<()>>())).await;
+    assert!(matches!(result, Err(FireblocksError::DeadlineExceeded { path }) if path == "/transactions"));
+    let result = deadline::with_deadline(deadline, flight.run(async { Ok(((), String::new())) })).await;
+    assert!(matches!(result, Err(FireblocksError::DeadlineExceeded { .. })));
+    let stream = deadline::with_deadline(Deadline::after(Duration::from_millis(10)), async {
+      lifecycle.enter("/audits").map(|f| f.guard::<u8>(Box::pin(futures::stream::pending())))
+    })
+    .await?;
+    let items = stream.collect::<Vec<_>>().await;
+    assert!(matches!(items.as_slice(), [Err(FireblocksError::DeadlineExceeded { .. })]));
+    Ok(())
+  }
+}
@@ -0,0 +1,176 @@
+//! Simulating contract calls before they are submitted
+//!
+//! A [`Simulator`] set with [`ClientBuilder::with_simulator`](crate::ClientBuilder::with_simulator) runs every
+//! `CONTRACT_CALL` through a backend of your choice, e.g. `eth_call` or `debug_traceCall` on an RPC node, in
+//! [`Client::preflight`]. [`Client::create_transaction`] runs the preflight first and does not submit a call that
+//! reverts, so it never reaches signing, or the chain, where a revert still costs gas.
+//!
+//! ```
+//! use fireblocks_sdk::simulate::{ContractCall, Simulation, Simulator};
+//! use fireblocks_sdk::{ClientBuilder, FireblocksError};
+//! use futures::future::BoxFuture;
+//! use std::sync::Arc;
+//!
+//! /// Rejects calls to a selector known to revert
+//! struct Blocklist;
+//!
+//! impl Simulator for Blocklist {
+//!   fn simulate<'a>(&'a self, call: &'a ContractCall) -> BoxFuture<'a, Result<Simulation, FireblocksError>> {
+//!     let outcome = if call.data.starts_with("0xdeadbeef") {
+//!       Simulation::Reverted { reason: String::from("blocked selector") }
+//!     } else {
+//!       Simulation::Success { gas_used: None }
+//!     };
+//!     Box::pin(async move { Ok(outcome) })
+//!   }
+//! }
+//!
+//! fn client(api_key: &str, secret: &[u8]) -> color_eyre::Result<()> {
+//!   let _client = ClientBuilder::new(api_key, secret).with_simulator(Arc::new(Blocklist)).build()?;
+//!   Ok(())
+//! }
+//! ```
+use crate::types::{DestinationTransferPeerPath, ExtraParameters, TransactionArguments, TransactionOperation};
+use crate::{Client, FireblocksError};
+use futures::future::BoxFuture;
+
+/// A contract call pending submission
+#[derive(Debug, Clone)]
+pub struct ContractCall {
+  pub asset_id: String,
+  /// Vault account the call is sent from
+  pub source_vault: Option<String>,
+  /// Address of the contract when the destination is a one-time address, for other peers look it up
+  /// from `destination`
+  pub to: Option<String>,
+  pub destination: Option<DestinationTransferPeerPath>,
+  /// Value sent with the call, in the native asset
+  pub amount: String,
+  /// Hex calldata, as passed in `contractCallData`
+  pub data: String,
+}
+
+impl ContractCall {
+  /// The contract call of `args`, `None` for other operations or without calldata
+  pub fn from_args(args: &TransactionArguments) -> Option<Self> {
+    if args.operation != TransactionOperation::CONTRACT_CALL {
+      return None;
+    }
+    let Some(ExtraParameters::ContractCallData(data)) = &args.extra_parameters else {
+      return None;
+    };
+    Some(Self {
+      asset_id: args.asset_id.clone(),
+      source_vault: args.source.id.clone(),
+      to: args.destination.as_ref().and_then(|d| d.one_time_address.as_ref()).map(|ota| ota.address.clone()),
+      destination: args.destination.clone(),
+      amount: args.amount.clone(),
+      data: data.clone(),
+    })
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Simulation {
+  Success {
+    gas_used: Option<u64>,
+  },
+  /// The call would revert, it is not submitted
+  Reverted {
+    reason: String,
+  },
+}
+
+pub trait Simulator: Send + Sync {
+  /// Evaluate `call`, an error (e.g. the RPC node is down) blocks submission too
+  fn simulate<'a>(&'a self, call: &'a ContractCall) -> BoxFuture<'a, Result<Simulation, FireblocksError>>;
+}
+
+impl Client {
+  /// Check `args` the way [`Client::create_transaction`] does before submitting, without submitting: destination
+  /// tags (see [`crate::types::tag`]) and, with a [`Simulator`], contract calls. Returns the simulation of a
+  /// contract call, a reverting call is [`FireblocksError::SimulationReverted`]
+  #[tracing::instrument(level = "debug", skip(self))]
+  pub async fn preflight(&self, args: &TransactionArguments) -> Result<Option<Simulation>, FireblocksError> {
+    if self.tag_validation() {
+      args.validate_tags(self.required_tags())?;
+    }
+    let (Some(simulator), Some(call)) = (self.simulator(), ContractCall::from_args(args)) else {
+      return Ok(None);
+    };
+    match simulator.simulate(&call).await? {
+      Simulation::Reverted { reason } => Err(FireblocksError::SimulationReverted { asset_id: call.asset_id, reason }),
+      success @ Simulation::Success { .. } => Ok(Some(success)),
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::mock::{self, MockTransport};
+  use crate::types::{OneTimeAddress, PeerType, TransferPeerPath};
+  use http::StatusCode;
+  use std::sync::Arc;
+
+  /// Reverts calldata starting with `0xdead`
+  struct Node;
+
+  impl Simulator for Node {
+    fn simulate<'a>(&'a self, call: &'a ContractCall) -> BoxFuture<'a, Result<Simulation, FireblocksError>> {
+      let outcome = if call.data.starts_with("0xdead") {
+        Simulation::Reverted { reason: String::from("execution reverted: paused") }
+      } else {
+        Simulation::Success { gas_used: Some(21_000) }
+      };
+      Box::pin(async move { Ok(outcome) })
+    }
+  }
+
+  fn call(data: &str) -> TransactionArguments {
+    TransactionArguments {
+      asset_id: String::from("ETH_TEST5"),
+      operation: TransactionOperation::CONTRACT_CALL,
+      source: TransferPeerPath { id: Some(String::from("0")), ..Default::default() },
+      destination: Some(DestinationTransferPeerPath {
+        peer_type: PeerType::ONE_TIME_ADDRESS,
+        one_time_address: Some(OneTimeAddress { address: String::from("0xcontract"), tag: None }),
+        ..Default::default()
+      }),
+      amount: String::from("0"),
+      gas_price: None,
+      gas_limit: None,
+      fee_level: None,
+      extra_parameters: Some(ExtraParameters::ContractCallData(String::from(data))),
+      destinations: None,
+      external_tx_id: None,
+      note: String::new(),
+    }
+  }
+
+  #[test]
+  fn test_contract_call() {
+    let contract = ContractCall::from_args(&call("0xa9059cbb")).expect("contract call");
+    assert_eq!(contract.to.as_deref(), Some("0xcontract"));
+    assert_eq!(contract.source_vault.as_deref(), Some("0"));
+    let mut transfer = call("0x");
+    transfer.operation = TransactionOperation::TRANSFER;
+    assert!(ContractCall::from_args(&transfer).is_none());
+  }
+
+  #[tokio::test]
+  async fn test_blocks_reverting_calls() -> color_eyre::Result<()> {
+    let submitted = MockTransport::new(|_| mock::json(StatusCode::OK, r#"{"id": "tx1", "status": "SUBMITTED"}"#));
+    let client = mock::builder(submitted.clone())?.with_simulator(Arc::new(Node)).build()?;
+
+    assert_eq!(client.preflight(&call("0xa9059cbb")).await?, Some(Simulation::Success { gas_used: Some(21_000) }));
+    let reverted = client.create_transaction(&call("0xdeadbeef")).await;
+    assert!(
+      matches!(reverted, Err(FireblocksError::SimulationReverted { ref reason, .. }) if reason.contains("paused"))
+    );
+    assert!(submitted.requests().is_empty());
+    assert_eq!(client.create_transaction(&call("0xa9059cbb")).await?.0.id, "tx1");
+    assert_eq!(submitted.requests().len(), 1);
+    Ok(())
+  }
+}
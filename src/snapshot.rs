@@ -0,0 +1,214 @@
+//! Differences between two balance snapshots
+//!
+//! A snapshot is the list of vault accounts with their asset balances at one time, e.g. collected from
+//! [`PagedClient::vaults`](crate::PagedClient::vaults). [`BalanceDiff::new`] compares the total balances of
+//! two of them, reporting new and disappeared vault accounts, and per asset wallet the ones added, changed or
+//! disappeared, for reconciliation jobs to alert on. [`BalanceDiff::value`] values the changes in fiat with a
+//! [`PriceOracle`].
+//!
+//! ```
+//! use chrono::Utc;
+//! use fireblocks_sdk::price::PriceOracle;
+//! use fireblocks_sdk::snapshot::BalanceDiff;
+//! use fireblocks_sdk::types::Account;
+//!
+//! async fn alert(yesterday: &[Account], today: &[Account], prices: &dyn PriceOracle) -> color_eyre::Result<()> {
+//!   let mut diff = BalanceDiff::new(yesterday, today);
+//!   diff.value(prices, Utc::now()).await?;
+//!   for change in &diff.changes {
+//!     tracing::warn!("vault {} {} {:?} by {}", change.vault_id, change.asset_id, change.kind, change.delta());
+//!   }
+//!   print!("{}", diff.to_csv());
+//!   Ok(())
+//! }
+//! ```
+use crate::csv;
+use crate::price::{self, PriceOracle};
+use crate::types::Account;
+use crate::FireblocksError;
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+  /// The asset wallet is new, or its vault account is
+  Added,
+  Changed,
+  /// The asset wallet is gone, or its vault account is
+  Disappeared,
+}
+
+/// The total balance of one asset wallet in both snapshots, `None` where it does not exist
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalanceChange {
+  pub vault_id: i32,
+  pub asset_id: String,
+  pub kind: ChangeKind,
+  pub before: Option<BigDecimal>,
+  pub after: Option<BigDecimal>,
+  /// [`BalanceChange::delta`] in fiat, `None` until [valued](BalanceDiff::value) or without a price
+  pub fiat_delta: Option<BigDecimal>,
+}
+
+impl BalanceChange {
+  /// `after - before`, a missing balance counting as zero
+  pub fn delta(&self) -> BigDecimal {
+    self.after.clone().unwrap_or_default() - self.before.clone().unwrap_or_default()
+  }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BalanceDiff {
+  pub new_vaults: Vec<i32>,
+  pub disappeared_vaults: Vec<i32>,
+  /// Ordered by vault account and asset
+  pub changes: Vec<BalanceChange>,
+}
+
+impl BalanceDiff {
+  pub fn new(before: &[Account], after: &[Account]) -> Self {
+    let ids = |accounts: &[Account]| accounts.iter().map(|a| a.id).collect::<BTreeSet<_>>();
+    let (old_vaults, new_vaults) = (ids(before), ids(after));
+    let (before, after) = (totals(before), totals(after));
+    let keys = before.keys().chain(after.keys()).collect::<BTreeSet<_>>();
+    let changes = keys
+      .into_iter()
+      .filter_map(|key| {
+        let (old, new) = (before.get(key), after.get(key));
+        let kind = match (old, new) {
+          (None, Some(_)) => ChangeKind::Added,
+          (Some(_), None) => ChangeKind::Disappeared,
+          (Some(old), Some(new)) if old != new => ChangeKind::Changed,
+          _ => return None,
+        };
+        Some(BalanceChange {
+          vault_id: key.0,
+          asset_id: key.1.clone(),
+          kind,
+          before: old.cloned(),
+          after: new.cloned(),
+          fiat_delta: None,
+        })
+      })
+      .collect();
+    Self {
+      new_vaults: new_vaults.difference(&old_vaults).copied().collect(),
+      disappeared_vaults: old_vaults.difference(&new_vaults).copied().collect(),
+      changes,
+    }
+  }
+
+  pub const fn is_empty(&self) -> bool {
+    self.new_vaults.is_empty() && self.disappeared_vaults.is_empty() && self.changes.is_empty()
+  }
+
+  /// Value the delta of each change in fiat at `at`, e.g. the time of the later snapshot, leaving `fiat_delta`
+  /// empty where `oracle` has no price
+  pub async fn value(&mut self, oracle: &dyn PriceOracle, at: DateTime<Utc>) -> Result<(), FireblocksError> {
+    for c in &mut self.changes {
+      c.fiat_delta = price::fiat_value(oracle, &c.asset_id, &c.delta(), at).await?;
+    }
+    Ok(())
+  }
+
+  /// Render the changes as CSV: `vault,asset,change,before,after,delta,fiat_delta`
+  pub fn to_csv(&self) -> String {
+    let mut out = csv::line(["vault", "asset", "change", "before", "after", "delta", "fiat_delta"]);
+    out.push('\n');
+    for c in &self.changes {
+      let kind = match c.kind {
+        ChangeKind::Added => "ADDED",
+        ChangeKind::Changed => "CHANGED",
+        ChangeKind::Disappeared => "DISAPPEARED",
+      };
+      let amount = |a: Option<&BigDecimal>| a.map(|a| a.normalized().to_plain_string()).unwrap_or_default();
+      out.push_str(&csv::line([
+        c.vault_id.to_string().as_str(),
+        &c.asset_id,
+        kind,
+        &amount(c.before.as_ref()),
+        &amount(c.after.as_ref()),
+        &amount(Some(&c.delta())),
+        &amount(c.fiat_delta.as_ref()),
+      ]));
+      out.push('\n');
+    }
+    out
+  }
+}
+
+/// Total balance by vault account and asset
+fn totals(accounts: &[Account]) -> BTreeMap<(i32, String), BigDecimal> {
+  accounts
+    .iter()
+    .flat_map(|account| account.assets.iter().map(|asset| ((account.id, asset.id.clone()), asset.total.clone())))
+    .collect()
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::types::AccountAsset;
+  use futures::future::BoxFuture;
+  use std::str::FromStr;
+
+  fn account(id: i32, assets: &[(&str, &str)]) -> color_eyre::Result<Account> {
+    let assets = assets
+      .iter()
+      .map(|(id, total)| {
+        Ok(AccountAsset { id: String::from(*id), total: BigDecimal::from_str(total)?, ..Default::default() })
+      })
+      .collect::<color_eyre::Result<_>>()?;
+    Ok(Account { id, assets, ..Default::default() })
+  }
+
+  #[test]
+  fn test_diff() -> color_eyre::Result<()> {
+    let before = [account(0, &[("BTC", "1.5"), ("ETH", "2")])?, account(1, &[("SOL", "10")])?, account(2, &[])?];
+    let after = [account(0, &[("BTC", "1.50"), ("ETH", "1.25"), ("USDC", "5")])?, account(3, &[("BTC", "0")])?];
+    let diff = BalanceDiff::new(&before, &after);
+    assert_eq!(diff.new_vaults, [3]);
+    assert_eq!(diff.disappeared_vaults, [1, 2]);
+    let changes = diff.changes.iter().map(|c| (c.vault_id, c.asset_id.as_str(), c.kind)).collect::<Vec<_>>();
+    assert_eq!(
+      changes,
+      [
+        (0, "ETH", ChangeKind::Changed),
+        (0, "USDC", ChangeKind::Added),
+        (1, "SOL", ChangeKind::Disappeared),
+        (3, "BTC", ChangeKind::Added),
+      ]
+    );
+    assert_eq!(diff.changes[0].delta(), BigDecimal::from_str("-0.75")?);
+    assert_eq!(diff.to_csv().lines().nth(3), Some("1,SOL,DISAPPEARED,10,,-10,"));
+    assert!(BalanceDiff::new(&after, &after).is_empty());
+    Ok(())
+  }
+
+  /// USDC at par, nothing else
+  struct Dollars;
+
+  impl PriceOracle for Dollars {
+    fn price<'a>(
+      &'a self,
+      asset_id: &'a str,
+      _at: DateTime<Utc>,
+    ) -> BoxFuture<'a, Result<Option<BigDecimal>, FireblocksError>> {
+      let price = (asset_id == "USDC").then(|| BigDecimal::from(1));
+      Box::pin(async move { Ok(price) })
+    }
+  }
+
+  #[tokio::test]
+  async fn test_value() -> color_eyre::Result<()> {
+    let before = [account(0, &[("USDC", "100"), ("ETH", "1")])?];
+    let after = [account(0, &[("USDC", "75.5"), ("ETH", "2")])?];
+    let mut diff = BalanceDiff::new(&before, &after);
+    diff.value(&Dollars, Utc::now()).await?;
+    let values = diff.changes.iter().map(|c| (c.asset_id.as_str(), c.fiat_delta.clone())).collect::<Vec<_>>();
+    assert_eq!(values, [("ETH", None), ("USDC", Some(BigDecimal::from_str("-24.5")?))]);
+    assert_eq!(diff.to_csv().lines().nth(2), Some("0,USDC,CHANGED,100,75.5,-24.5,-24.5"));
+    Ok(())
+  }
+}
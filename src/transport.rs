@@ -0,0 +1,167 @@
+//! The HTTP stack the client sends its requests with
+//!
+//! [`ReqwestTransport`], behind the default `reqwest` feature, is used unless
+//! [`ClientBuilder::with_transport`](crate::ClientBuilder::with_transport)
+//! plugs in another [`HttpTransport`], e.g. hyper, a corporate HTTP stack or a test double. Requests reach the
+//! transport signed and with their body serialized, the transport only moves bytes.
+//!
+//! ```
+//! use fireblocks_sdk::transport::{HttpRequest, HttpResponse, HttpTransport};
+//! use fireblocks_sdk::{ClientBuilder, FireblocksError};
+//! use futures::future::BoxFuture;
+//! use http::{HeaderMap, StatusCode};
+//! use std::sync::Arc;
+//!
+//! /// Answers every request with the same vault account
+//! struct Canned;
+//!
+//! impl HttpTransport for Canned {
+//!   fn send(&self, _request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, FireblocksError>> {
+//!     let mut headers = HeaderMap::new();
+//!     headers.insert("content-type", "application/json".parse().unwrap());
+//!     let body = br#"{"id": "0", "name": "Default", "hiddenOnUI": false, "assets": [], "autoFuel": false}"#;
+//!     let body = body.to_vec();
+//!     Box::pin(async move { Ok(HttpResponse::new(StatusCode::OK, headers, body)) })
+//!   }
+//! }
+//!
+//! async fn vault(api_key: &str, secret: &[u8]) -> color_eyre::Result<()> {
+//!   let client = ClientBuilder::new(api_key, secret).with_transport(Arc::new(Canned)).build()?;
+//!   assert_eq!(client.vault(0).await?.0.name, "Default");
+//!   Ok(())
+//! }
+//! ```
+use crate::FireblocksError;
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
+use http::{HeaderMap, Method, StatusCode};
+use url::Url;
+
+/// Chunks of a response body as they arrive
+pub type Body = BoxStream<'static, Result<Bytes, FireblocksError>>;
+
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+  pub method: Method,
+  pub url: Url,
+  /// Authentication and content type headers
+  pub headers: HeaderMap,
+  /// JSON body
+  pub body: Option<Vec<u8>>,
+}
+
+pub struct HttpResponse {
+  pub status: StatusCode,
+  pub headers: HeaderMap,
+  pub body: Body,
+}
+
+impl std::fmt::Debug for HttpResponse {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("HttpResponse").field("status", &self.status).field("headers", &self.headers).finish_non_exhaustive()
+  }
+}
+
+impl HttpResponse {
+  /// A response with the whole body at once
+  pub fn new(status: StatusCode, headers: HeaderMap, body: Vec<u8>) -> Self {
+    Self { status, headers, body: futures::stream::once(async move { Ok(Bytes::from(body)) }).boxed() }
+  }
+
+  /// Read the rest of the body
+  pub async fn bytes(self) -> Result<Vec<u8>, FireblocksError> {
+    self
+      .body
+      .try_fold(Vec::new(), |mut body, chunk| async move {
+        body.extend_from_slice(&chunk);
+        Ok(body)
+      })
+      .await
+  }
+}
+
+/// Sends a request and returns the response once its headers arrived, leaving the body to be streamed
+///
+/// Report failures to reach the server as [`FireblocksError::Transport`], HTTP error statuses are
+/// handled by the client.
+pub trait HttpTransport: Send + Sync {
+  fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, FireblocksError>>;
+}
+
+/// The default transport, see the [`ClientBuilder`](crate::ClientBuilder) settings for its pool and timeouts
+#[cfg(feature = "reqwest")]
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+  client: reqwest::Client,
+}
+
+#[cfg(feature = "reqwest")]
+impl ReqwestTransport {
+  pub const fn new(client: reqwest::Client) -> Self {
+    Self { client }
+  }
+}
+
+#[cfg(feature = "reqwest")]
+impl HttpTransport for ReqwestTransport {
+  fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, FireblocksError>> {
+    Box::pin(async move {
+      let mut req = self.client.request(request.method, request.url).headers(request.headers);
+      if let Some(body) = request.body {
+        req = req.body(body);
+      }
+      let resp = req.send().await?;
+      let status = resp.status();
+      let headers = resp.headers().clone();
+      let body = futures::stream::unfold(resp, |mut resp| async move {
+        resp.chunk().await.map_err(FireblocksError::from).transpose().map(|chunk| (chunk, resp))
+      });
+      Ok(HttpResponse { status, headers, body: body.boxed() })
+    })
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::mock::{self, MockTransport};
+  use crate::PagingVaultRequestBuilder;
+
+  #[tokio::test]
+  async fn test_transport() -> color_eyre::Result<()> {
+    let body = r#"{"accounts": [
+      {"id": "1", "name": "a", "hiddenOnUI": false, "assets": [], "autoFuel": false},
+      {"id": "2", "name": "b", "hiddenOnUI": true, "assets": [], "autoFuel": false}
+    ], "paging": {"after": "Mg=="}}"#;
+    // a JSON body streamed in small chunks
+    let recorder = MockTransport::new(move |_| {
+      let mut headers = HeaderMap::new();
+      headers.insert("content-type", "application/json".parse().expect("header"));
+      headers.insert("x-request-id", "req-1".parse().expect("header"));
+      let chunks = body.as_bytes().chunks(3).map(|c| Ok(Bytes::copy_from_slice(c))).collect::<Vec<_>>();
+      HttpResponse { status: StatusCode::OK, headers, body: futures::stream::iter(chunks).boxed() }
+    });
+    let client = mock::client(recorder.clone())?;
+
+    let (page, request_id) = client.vaults(PagingVaultRequestBuilder::new().limit(2).build()?).await?;
+    assert_eq!(request_id, "req-1");
+    assert_eq!(page.items.len(), 2);
+    assert_eq!(page.next_cursor(), Some("Mg=="));
+
+    let request = recorder.requests().remove(0);
+    assert_eq!(request.method, Method::GET);
+    assert_eq!(request.url.path(), "/v1/vault/accounts_paged");
+    assert_eq!(request.headers.get("x-api-key").map(|v| v.to_str()).transpose()?, Some("key"));
+    assert!(request.headers.contains_key("authorization"));
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn test_bytes() -> color_eyre::Result<()> {
+    let response = HttpResponse::new(StatusCode::OK, HeaderMap::new(), b"body".to_vec());
+    assert_eq!(response.bytes().await?, b"body");
+    Ok(())
+  }
+}
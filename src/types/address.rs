@@ -54,6 +54,9 @@ pub struct Address {
   pub tag: Option<String>,
   #[serde(deserialize_with = "deserialize_option_empty_object", default)]
   pub description: Option<String>,
+  /// The customer an omnibus deposit address belongs to
+  #[serde(deserialize_with = "deserialize_option_empty_object", default)]
+  pub customer_ref_id: Option<String>,
   #[serde(rename = "bip44AddressIndex")]
   pub bip44address_index: i32,
   pub user_defined: bool,
@@ -54,3 +54,9 @@ pub struct AccountAsset {
   pub block_height: Option<String>,
   pub block_hash: Option<String>,
 }
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MaxSpendableAmount {
+  pub max_spendable_amount: BigDecimal,
+}
@@ -0,0 +1,156 @@
+use crate::QueryParams;
+use chrono::{DateTime, TimeZone, Utc};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer};
+use serde_derive::Serialize;
+use serde_json::Value;
+
+/// How far back to fetch, Fireblocks offers no other periods
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AuditTimePeriod {
+  #[default]
+  DAY,
+  WEEK,
+}
+
+impl AuditTimePeriod {
+  pub const fn as_str(&self) -> &'static str {
+    match self {
+      Self::DAY => "DAY",
+      Self::WEEK => "WEEK",
+    }
+  }
+}
+
+/// A workspace audit record
+///
+/// See
+/// * [getAuditLogs](https://developers.fireblocks.com/reference/getauditlogs)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLog {
+  pub id: String,
+  #[serde(default, deserialize_with = "deserialize_time")]
+  pub timestamp: Option<DateTime<Utc>>,
+  #[serde(default, deserialize_with = "deserialize_time")]
+  pub created_at: Option<DateTime<Utc>>,
+  /// Display name of the user who performed the action
+  pub user: Option<String>,
+  pub user_id: Option<String>,
+  pub subject: Option<String>,
+  pub event: Option<AuditEvent>,
+  pub tenant_id: Option<String>,
+}
+
+/// What happened, as described by Fireblocks. Descriptions this crate does not know are kept as
+/// [`AuditEvent::Other`]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub enum AuditEvent {
+  #[serde(rename = "Vault account created")]
+  VaultAccountCreated,
+  #[serde(rename = "API user added")]
+  ApiUserAdded,
+  #[serde(rename = "User added")]
+  UserAdded,
+  #[serde(rename = "User removed")]
+  UserRemoved,
+  #[serde(untagged)]
+  Other(String),
+}
+
+/// RFC 3339, or milliseconds since epoch as a number or a string
+fn deserialize_time<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let millis = |ms: i64| Utc.timestamp_millis_opt(ms).single();
+  let time = match Option::<Value>::deserialize(deserializer)? {
+    None | Some(Value::Null) => return Ok(None),
+    Some(Value::String(s)) if s.is_empty() => return Ok(None),
+    Some(Value::String(s)) => {
+      s.parse().ok().and_then(millis).or_else(|| DateTime::parse_from_rfc3339(&s).ok().map(|t| t.with_timezone(&Utc)))
+    },
+    Some(Value::Number(n)) => n.as_i64().and_then(millis),
+    Some(_) => None,
+  };
+  time.map(Some).ok_or_else(|| D::Error::custom("invalid audit log time"))
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogs {
+  pub data: Vec<AuditLog>,
+  /// Pass to [`AuditLogListBuilder::cursor`] to get the next page, unset on the last page
+  pub cursor: Option<String>,
+  pub total: Option<u64>,
+}
+
+/// [getAuditLogs](https://developers.fireblocks.com/reference/getauditlogs)
+#[derive(Debug, Default)]
+pub struct AuditLogListBuilder {
+  params: QueryParams,
+}
+
+impl AuditLogListBuilder {
+  #[allow(clippy::missing_const_for_fn)]
+  pub fn new() -> Self {
+    Self { params: Vec::new() }
+  }
+
+  pub fn time_period(&mut self, period: AuditTimePeriod) -> &mut Self {
+    self.params.push(("timePeriod".to_owned(), period.as_str().to_owned()));
+    self
+  }
+
+  pub fn cursor(&mut self, cursor: &str) -> &mut Self {
+    if !cursor.is_empty() {
+      self.params.push(("cursor".to_owned(), String::from(cursor)));
+    }
+    self
+  }
+
+  #[allow(clippy::unnecessary_wraps)]
+  pub fn build(&self) -> std::result::Result<QueryParams, crate::error::ParamError> {
+    Ok(Vec::clone(&self.params))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::types::audit::{AuditEvent, AuditLogs};
+  use chrono::{TimeZone, Utc};
+
+  #[test]
+  fn test_json_audit_logs() -> color_eyre::Result<()> {
+    let data = r#"
+    {
+      "data": [
+        {
+          "id": "1",
+          "timestamp": "2024-03-01T10:00:00.000Z",
+          "createdAt": "2024-03-01T10:00:00.000Z",
+          "user": "Jane Doe",
+          "userId": "u1",
+          "subject": "Vault account 3",
+          "event": "Vault account created"
+        },
+        {"id": "2", "userId": "u2", "timestamp": "1709287200000", "event": "API user added"},
+        {"id": "3", "createdAt": 1709287200000, "event": "Something new"}
+      ],
+      "cursor": "MjAyNC0wMy0wMQ==",
+      "total": 3
+    }
+    "#;
+    let logs: AuditLogs = serde_json::from_str(data)?;
+    let at = Utc.with_ymd_and_hms(2024, 3, 1, 10, 0, 0).single();
+    assert_eq!([at, at, None], logs.data.iter().map(|l| l.timestamp).collect::<Vec<_>>()[..]);
+    assert_eq!(at, logs.data[2].created_at);
+    assert_eq!(Some(AuditEvent::VaultAccountCreated), logs.data[0].event);
+    assert_eq!(Some(AuditEvent::Other(String::from("Something new"))), logs.data[2].event);
+    assert!(logs.cursor.is_some());
+    assert!(serde_json::from_str::<AuditLogs>(r#"{"data": [{"id": "1", "timestamp": "yesterday"}]}"#).is_err());
+    Ok(())
+  }
+}
@@ -0,0 +1,53 @@
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockchainOnchain {
+  pub protocol: String,
+  pub chain_id: Option<String>,
+  #[serde(default)]
+  pub test: bool,
+  pub signing_algo: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockchainMetadata {
+  pub scope: Option<String>,
+  #[serde(default)]
+  pub deprecated: bool,
+}
+
+/// See
+/// * [getBlockchain](https://developers.fireblocks.com/reference/getblockchain)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Blockchain {
+  pub id: String,
+  pub legacy_id: String,
+  pub display_name: String,
+  pub native_asset_id: String,
+  pub onchain: BlockchainOnchain,
+  pub metadata: BlockchainMetadata,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Blockchains {
+  pub data: Vec<Blockchain>,
+  pub next: Option<String>,
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_json_blockchain() -> color_eyre::Result<()> {
+    let json = r#"{"id": "9f9f7062-df90-4fc0-8697-96685184357b", "legacyId": "ETH", "displayName": "Ethereum", "nativeAssetId": "ETH", "onchain": {"protocol": "ETH", "chainId": "1", "test": false, "signingAlgo": "ECDSA_SECP256K1"}, "metadata": {"scope": "GLOBAL", "deprecated": false}}"#;
+    let b: Blockchain = serde_json::from_str(json)?;
+    assert_eq!(b.legacy_id, "ETH");
+    assert_eq!(b.onchain.chain_id.as_deref(), Some("1"));
+    Ok(())
+  }
+}
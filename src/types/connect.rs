@@ -1,3 +1,5 @@
+pub use crate::types::fee::FeeLevel;
+use crate::QueryParams;
 use chrono::{DateTime, Utc};
 use serde_derive::{Deserialize, Serialize};
 
@@ -21,7 +23,7 @@ pub struct WalletConnection {
   pub metadata: Metadata,
   #[serde(rename = "vaultAccountId")]
   pub vault: i32,
-  pub fee_level: String,
+  pub fee_level: FeeLevel,
   #[serde(rename = "chainIds")]
   pub chains: Vec<String>,
   pub connection_type: String,
@@ -42,11 +44,71 @@ pub struct PagedWalletConnectResponse {
   pub page: Option<NextPage>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Default)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum FeeLevel {
-  #[default]
-  Medium,
+/// Filter and page through dApp connections
+///
+/// [get](https://developers.fireblocks.com/reference/get)
+#[derive(Debug, Default)]
+pub struct WalletConnectionListBuilder {
+  params: QueryParams,
+  filters: Vec<String>,
+}
+
+impl WalletConnectionListBuilder {
+  #[allow(clippy::missing_const_for_fn)]
+  pub fn new() -> Self {
+    Self { params: Vec::new(), filters: Vec::new() }
+  }
+
+  pub fn page_size(&mut self, size: u16) -> &mut Self {
+    self.params.push(("pageSize".to_owned(), format!("{size}")));
+    self
+  }
+
+  pub fn next(&mut self, cursor: &str) -> &mut Self {
+    if !cursor.is_empty() {
+      self.params.push(("next".to_owned(), String::from(cursor)));
+    }
+    self
+  }
+
+  pub fn sort_created_at(&mut self) -> &mut Self {
+    self.params.push(("sort".to_owned(), String::from("createdAt")));
+    self
+  }
+
+  pub fn order_asc(&mut self) -> &mut Self {
+    self.params.push(("order".to_owned(), String::from("ASC")));
+    self
+  }
+
+  pub fn order_desc(&mut self) -> &mut Self {
+    self.params.push(("order".to_owned(), String::from("DESC")));
+    self
+  }
+
+  pub fn vault(&mut self, vault_id: i32) -> &mut Self {
+    self.filters.push(format!("vaultAccountId:{vault_id}"));
+    self
+  }
+
+  pub fn user(&mut self, user_id: &str) -> &mut Self {
+    self.filters.push(format!("userId:{user_id}"));
+    self
+  }
+
+  pub fn app_url(&mut self, url: &str) -> &mut Self {
+    self.filters.push(format!("appUrl:{url}"));
+    self
+  }
+
+  #[allow(clippy::unnecessary_wraps)]
+  pub fn build(&self) -> std::result::Result<QueryParams, crate::error::ParamError> {
+    let mut p = Vec::clone(&self.params);
+    if !self.filters.is_empty() {
+      p.push(("filter".to_owned(), self.filters.join(",")));
+    }
+    Ok(p)
+  }
 }
 
 #[derive(Debug, Deserialize, Serialize, Default)]
@@ -62,6 +124,8 @@ pub struct WalletConnectRequest {
 #[serde(rename_all = "camelCase")]
 pub struct WalletConnectResponse {
   pub id: String,
+  #[serde(rename = "sessionMetadata")]
+  pub metadata: Option<Metadata>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Default)]
@@ -72,9 +136,18 @@ pub struct WalletApprove {
 
 #[cfg(test)]
 mod test {
-  use crate::types::connect::WalletConnection;
+  use crate::types::connect::{FeeLevel, WalletConnection, WalletConnectionListBuilder};
   use chrono::Datelike;
 
+  #[test]
+  fn test_connection_list_builder() -> color_eyre::Result<()> {
+    let params =
+      WalletConnectionListBuilder::new().vault(1).app_url("https://app").page_size(10).order_desc().build()?;
+    assert!(params.iter().any(|(k, v)| k == "filter" && v == "vaultAccountId:1,appUrl:https://app"));
+    assert_eq!("\"HIGH\"", serde_json::to_string(&FeeLevel::High)?);
+    Ok(())
+  }
+
   #[test]
   fn test_json_wallet_connections() {
     let data = r#"
@@ -103,5 +176,9 @@ mod test {
     let r: WalletConnection = serde_json::from_str(data).expect("oh no");
     assert_eq!(22, r.creation_date.day());
     assert_eq!("url", r.metadata.app_url);
+    assert_eq!(FeeLevel::Medium, r.fee_level);
+
+    let r: WalletConnection = serde_json::from_str(&data.replace("MEDIUM", "TURBO")).expect("oh no");
+    assert_eq!(FeeLevel::Unknown, r.fee_level);
   }
 }
@@ -1,7 +1,34 @@
 use bigdecimal::BigDecimal;
 use serde::Deserialize;
+use serde_derive::Serialize;
+use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Deserialize, Default)]
+/// Speed of a transaction, the fee is taken from the matching level of [`EstimateFee`]
+#[derive(Debug, Deserialize, Serialize, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FeeLevel {
+  Low,
+  #[default]
+  Medium,
+  High,
+  /// A level this version does not know
+  #[serde(other)]
+  Unknown,
+}
+
+impl Display for FeeLevel {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    let level = match self {
+      Self::Low => "LOW",
+      Self::Medium => "MEDIUM",
+      Self::High => "HIGH",
+      Self::Unknown => "UNKNOWN",
+    };
+    f.write_str(level)
+  }
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Fee {
   pub network_fee: Option<BigDecimal>,
@@ -11,10 +38,50 @@ pub struct Fee {
   pub priority_fee: Option<BigDecimal>,
 }
 
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Deserialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct EstimateFee {
   pub low: Fee,
   pub medium: Fee,
   pub high: Fee,
 }
+
+impl EstimateFee {
+  /// The fee of `level`, the medium fee for an unknown level as Fireblocks defaults to it
+  pub const fn level(&self, level: FeeLevel) -> &Fee {
+    match level {
+      FeeLevel::Low => &self.low,
+      FeeLevel::Medium | FeeLevel::Unknown => &self.medium,
+      FeeLevel::High => &self.high,
+    }
+  }
+}
+
+/// Fees charged for a transaction, in [`crate::types::Transaction::fee_currency`]
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeInfo {
+  pub network_fee: Option<BigDecimal>,
+  pub service_fee: Option<BigDecimal>,
+  pub gas_price: Option<BigDecimal>,
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use std::str::FromStr;
+
+  #[test]
+  fn test_fee_level() -> color_eyre::Result<()> {
+    let estimate: EstimateFee = serde_json::from_str(
+      r#"{"low": {"gasPrice": "1"}, "medium": {"gasPrice": "2", "networkFee": "0.0004"}, "high": {"gasPrice": "3"}}"#,
+    )?;
+    assert_eq!(estimate.level(FeeLevel::Medium).gas_price, Some(BigDecimal::from(2)));
+    assert_eq!(estimate.level(FeeLevel::High).gas_price, Some(BigDecimal::from(3)));
+    assert_eq!(estimate.medium.network_fee, Some(BigDecimal::from_str("0.0004")?));
+    assert_eq!(serde_json::from_str::<FeeLevel>("\"LOW\"")?, FeeLevel::Low);
+    assert_eq!(serde_json::from_str::<FeeLevel>("\"ULTRA\"")?, FeeLevel::Unknown);
+    assert_eq!(FeeLevel::High.to_string(), serde_json::to_value(FeeLevel::High)?.as_str().unwrap_or_default());
+    Ok(())
+  }
+}
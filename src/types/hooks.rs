@@ -1,7 +1,241 @@
-use serde_derive::Deserialize;
+pub use crate::webhook::WebhookEventType;
+use crate::QueryParams;
+use serde_derive::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct HookResponse {
   pub messages_count: u32,
 }
+
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WebhookStatus {
+  #[default]
+  ENABLED,
+  DISABLED,
+}
+
+/// See
+/// * [getWebhook](https://developers.fireblocks.com/reference/getwebhook)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Webhook {
+  pub id: String,
+  pub url: String,
+  pub description: Option<String>,
+  #[serde(default)]
+  pub events: Vec<WebhookEventType>,
+  pub status: WebhookStatus,
+  pub created_at: Option<i64>,
+  pub updated_at: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Webhooks {
+  pub data: Vec<Webhook>,
+  pub next: Option<String>,
+}
+
+/// See
+/// * [createWebhook](https://developers.fireblocks.com/reference/createwebhook)
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWebhook {
+  pub url: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub description: Option<String>,
+  pub events: Vec<WebhookEventType>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub enabled: Option<bool>,
+}
+
+/// Fields left unset are not changed
+///
+/// See
+/// * [updateWebhook](https://developers.fireblocks.com/reference/updatewebhook)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateWebhook {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub url: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub description: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub events: Option<Vec<WebhookEventType>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub enabled: Option<bool>,
+}
+
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NotificationStatus {
+  COMPLETED,
+  FAILED,
+  #[default]
+  IN_PROGRESS,
+  ON_HOLD,
+}
+
+impl NotificationStatus {
+  pub const fn as_str(&self) -> &'static str {
+    match self {
+      Self::COMPLETED => "COMPLETED",
+      Self::FAILED => "FAILED",
+      Self::IN_PROGRESS => "IN_PROGRESS",
+      Self::ON_HOLD => "ON_HOLD",
+    }
+  }
+}
+
+/// A delivery attempt of a notification
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationAttempt {
+  pub sent_time: i64,
+  /// Milliseconds until the endpoint responded
+  pub duration: u64,
+  pub response_code: Option<u16>,
+  pub failure_reason: Option<String>,
+}
+
+/// See
+/// * [getNotification](https://developers.fireblocks.com/reference/getnotification)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Notification {
+  pub id: String,
+  pub created_at: i64,
+  pub updated_at: i64,
+  pub status: NotificationStatus,
+  pub event_type: Option<WebhookEventType>,
+  pub resource_id: Option<String>,
+  #[serde(default)]
+  pub attempts: Vec<NotificationAttempt>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Notifications {
+  pub data: Vec<Notification>,
+  pub next: Option<String>,
+}
+
+/// Paging and filters of [`crate::Client::webhooks`] and [`crate::Client::webhook_notifications`]
+///
+/// [getNotifications](https://developers.fireblocks.com/reference/getnotifications)
+#[derive(Debug, Default)]
+pub struct NotificationListBuilder {
+  params: QueryParams,
+}
+
+impl NotificationListBuilder {
+  #[allow(clippy::missing_const_for_fn)]
+  pub fn new() -> Self {
+    Self { params: Vec::new() }
+  }
+
+  pub fn page_size(&mut self, size: u16) -> &mut Self {
+    self.params.push(("pageSize".to_owned(), format!("{size}")));
+    self
+  }
+
+  pub fn page_cursor(&mut self, cursor: &str) -> &mut Self {
+    if !cursor.is_empty() {
+      self.params.push(("pageCursor".to_owned(), String::from(cursor)));
+    }
+    self
+  }
+
+  pub fn order(&mut self, ascending: bool) -> &mut Self {
+    let order = if ascending { "ASC" } else { "DESC" };
+    self.params.push(("order".to_owned(), order.to_owned()));
+    self
+  }
+
+  pub fn statuses(&mut self, statuses: &[NotificationStatus]) -> &mut Self {
+    let s: Vec<&str> = statuses.iter().map(NotificationStatus::as_str).collect();
+    self.params.push(("statuses".to_owned(), s.join(",")));
+    self
+  }
+
+  pub fn resource_id(&mut self, id: &str) -> &mut Self {
+    self.params.push(("resourceId".to_owned(), String::from(id)));
+    self
+  }
+
+  /// Milliseconds since epoch
+  pub fn created_after(&mut self, t: i64) -> &mut Self {
+    self.params.push(("createdStartDate".to_owned(), t.to_string()));
+    self
+  }
+
+  /// Milliseconds since epoch
+  pub fn created_before(&mut self, t: i64) -> &mut Self {
+    self.params.push(("createdEndDate".to_owned(), t.to_string()));
+    self
+  }
+
+  #[allow(clippy::unnecessary_wraps)]
+  pub fn build(&self) -> std::result::Result<QueryParams, crate::error::ParamError> {
+    Ok(Vec::clone(&self.params))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::types::hooks::{HookResponse, NotificationStatus, Notifications, WebhookEventType, Webhooks};
+
+  #[test]
+  fn test_json_resend() -> color_eyre::Result<()> {
+    let r: HookResponse = serde_json::from_str(r#"{"messagesCount": 12}"#)?;
+    assert_eq!(12, r.messages_count);
+    Ok(())
+  }
+
+  #[test]
+  fn test_json_webhooks() -> color_eyre::Result<()> {
+    let data = r#"
+    {
+      "data": [
+        {
+          "id": "44fcead0-7053-4831-a53a-df7fb90d440f",
+          "url": "https://example.com/webhook",
+          "description": "ops",
+          "events": ["TRANSACTION_CREATED", "VAULT_BALANCE_UPDATE", "SOMETHING_NEW"],
+          "status": "ENABLED",
+          "createdAt": 1625126400000,
+          "updatedAt": 1625126400000
+        }
+      ],
+      "next": null
+    }
+    "#;
+    let hooks: Webhooks = serde_json::from_str(data)?;
+    assert_eq!(WebhookEventType::UNKNOWN, hooks.data[0].events[2]);
+
+    let data = r#"
+    {
+      "data": [
+        {
+          "id": "0f9a7a4c-5c1a-4f0e-9d0a-1b2c3d4e5f60",
+          "createdAt": 1625126400000,
+          "updatedAt": 1625126460000,
+          "status": "FAILED",
+          "eventType": "TRANSACTION_STATUS_UPDATED",
+          "resourceId": "e01b1c68-2d26-45dc-bb02-4cc9152295e1",
+          "attempts": [{"sentTime": 1625126400000, "duration": 10000, "responseCode": 503, "failureReason": "timeout"}]
+        }
+      ],
+      "next": "abc"
+    }
+    "#;
+    let notifications: Notifications = serde_json::from_str(data)?;
+    assert_eq!(NotificationStatus::FAILED, notifications.data[0].status);
+    assert_eq!(Some(503), notifications.data[0].attempts[0].response_code);
+    Ok(())
+  }
+}
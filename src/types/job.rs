@@ -0,0 +1,124 @@
+use serde_derive::{Deserialize, Serialize};
+
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum JobState {
+  #[default]
+  INACTIVE,
+  ACTIVE,
+  PAUSED,
+  CANCELLED,
+  DONE,
+  FAILED,
+  #[serde(other)]
+  UNKNOWN,
+}
+
+impl JobState {
+  pub const fn is_final(&self) -> bool {
+    matches!(self, Self::CANCELLED | Self::DONE | Self::FAILED)
+  }
+}
+
+/// A batch job, e.g. bulk vault or wallet creation
+///
+/// See
+/// * [getJob](https://developers.fireblocks.com/reference/getjob)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Job {
+  pub id: String,
+  pub tenant_id: Option<String>,
+  #[serde(rename = "type")]
+  pub job_type: String,
+  pub user_id: Option<String>,
+  pub created: i64,
+  pub updated: Option<i64>,
+  pub state: JobState,
+  pub data: Option<serde_json::Value>,
+}
+
+/// See
+/// * [getJobTasks](https://developers.fireblocks.com/reference/getjobtasks)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct JobTask {
+  pub id: String,
+  pub job_id: String,
+  #[serde(rename = "type")]
+  pub task_type: String,
+  pub tenant_id: Option<String>,
+  pub created: i64,
+  pub updated: Option<i64>,
+  pub state: JobState,
+  pub data: Option<serde_json::Value>,
+  pub result: Option<serde_json::Value>,
+}
+
+/// Progress of a [`Job`], counted from its tasks
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JobProgress {
+  pub job_id: String,
+  pub state: JobState,
+  pub total: usize,
+  pub done: usize,
+  /// Failed or cancelled
+  pub failed: usize,
+}
+
+impl JobProgress {
+  pub fn new(job: &Job, tasks: &[JobTask]) -> Self {
+    let count = |f: fn(&JobState) -> bool| tasks.iter().filter(|t| f(&t.state)).count();
+    Self {
+      job_id: job.id.clone(),
+      state: job.state,
+      total: tasks.len(),
+      done: count(|s| *s == JobState::DONE),
+      failed: count(|s| matches!(s, JobState::FAILED | JobState::CANCELLED)),
+    }
+  }
+
+  pub const fn pending(&self) -> usize {
+    self.total.saturating_sub(self.done + self.failed)
+  }
+
+  /// The job terminated, no more tasks will run
+  pub const fn is_final(&self) -> bool {
+    self.state.is_final()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::types::job::{Job, JobProgress, JobState, JobTask};
+
+  #[test]
+  fn test_json_jobs() -> color_eyre::Result<()> {
+    let data = r#"
+    [
+      {
+        "id": "c7f4a2e8-1b3d-4e5f-9a0b-2c4d6e8f0a1b",
+        "tenantId": "t1",
+        "type": "MULTI_SIG_CREATE_VAULT_ACCOUNTS",
+        "userId": "u1",
+        "created": 1700000000000,
+        "updated": 1700000060000,
+        "state": "PAUSED"
+      }
+    ]
+    "#;
+    let jobs: Vec<Job> = serde_json::from_str(data)?;
+    assert_eq!(JobState::PAUSED, jobs[0].state);
+    assert!(!jobs[0].state.is_final());
+
+    let data = r#"[{"id": "1", "jobId": "c7f4a2e8-1b3d-4e5f-9a0b-2c4d6e8f0a1b", "type": "CREATE_VAULT_ACCOUNT", "created": 1700000000000, "state": "DONE", "result": {"id": "12"}}]"#;
+    let tasks: Vec<JobTask> = serde_json::from_str(data)?;
+    assert!(tasks[0].state.is_final());
+
+    let progress =
+      JobProgress::new(&jobs[0], &[tasks[0].clone(), JobTask { state: JobState::ACTIVE, ..tasks[0].clone() }]);
+    assert_eq!((progress.total, progress.done, progress.failed, progress.pending()), (2, 1, 0, 1));
+    Ok(())
+  }
+}
@@ -0,0 +1,162 @@
+use crate::types::SigningAlgorithm;
+use crate::QueryParams;
+use serde_derive::{Deserialize, Serialize};
+
+/// An externally held (e.g. HSM) key used to sign transactions of a vault account
+///
+/// See
+/// * [getSigningKey](https://developers.fireblocks.com/reference/getsigningkey)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SigningKey {
+  pub key_id: String,
+  /// Id of the key in the external signing device
+  pub signing_device_key_id: String,
+  pub public_key_pem: String,
+  pub algorithm: SigningAlgorithm,
+  pub enabled: bool,
+  pub vault_account_id: Option<i32>,
+  pub agent_user_id: String,
+  pub created_at: i64,
+}
+
+/// A key used to validate the certificates of signing keys
+///
+/// See
+/// * [getValidationKey](https://developers.fireblocks.com/reference/getvalidationkey)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationKey {
+  pub key_id: String,
+  pub public_key_pem: String,
+  pub days_till_expired: u32,
+  pub enabled: bool,
+  pub created_at: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SigningKeys {
+  pub data: Vec<SigningKey>,
+  pub next: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationKeys {
+  pub data: Vec<ValidationKey>,
+  pub next: Option<String>,
+}
+
+/// Register an external signing key
+///
+/// See
+/// * [createSigningKey](https://developers.fireblocks.com/reference/createsigningkey)
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSigningKey {
+  pub signing_device_key_id: String,
+  /// Certificate of the signing key, signed by a registered validation key
+  pub signed_cert_pem: String,
+  /// The API user which acts as the agent of the external signing device
+  pub agent_user_id: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub proof_of_ownership: Option<ProofOfOwnership>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProofOfOwnership {
+  pub message: String,
+  pub signature: String,
+}
+
+/// Register a validation key
+///
+/// See
+/// * [createValidationKey](https://developers.fireblocks.com/reference/createvalidationkey)
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateValidationKey {
+  pub validation_key_pem: String,
+  pub days_till_expired: u32,
+}
+
+/// Search signing keys
+///
+/// [getSigningKeysList](https://developers.fireblocks.com/reference/getsigningkeyslist)
+#[derive(Debug, Default)]
+pub struct SigningKeyListBuilder {
+  params: QueryParams,
+}
+
+impl SigningKeyListBuilder {
+  #[allow(clippy::missing_const_for_fn)]
+  pub fn new() -> Self {
+    Self { params: Vec::new() }
+  }
+
+  pub fn vault(&mut self, vault_id: i32) -> &mut Self {
+    self.params.push(("vaultAccountId".to_owned(), vault_id.to_string()));
+    self
+  }
+
+  pub fn agent_user_id(&mut self, id: &str) -> &mut Self {
+    self.params.push(("agentUserId".to_owned(), String::from(id)));
+    self
+  }
+
+  pub fn assigned(&mut self, assigned: bool) -> &mut Self {
+    self.params.push(("isAssigned".to_owned(), assigned.to_string()));
+    self
+  }
+
+  pub fn page_size(&mut self, size: u16) -> &mut Self {
+    self.params.push(("pageSize".to_owned(), format!("{size}")));
+    self
+  }
+
+  pub fn page_cursor(&mut self, cursor: &str) -> &mut Self {
+    if !cursor.is_empty() {
+      self.params.push(("pageCursor".to_owned(), String::from(cursor)));
+    }
+    self
+  }
+
+  #[allow(clippy::unnecessary_wraps)]
+  pub fn build(&self) -> std::result::Result<QueryParams, crate::error::ParamError> {
+    Ok(Vec::clone(&self.params))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::types::key_link::SigningKeys;
+  use crate::types::SigningAlgorithm;
+
+  #[test]
+  fn test_json_signing_keys() -> color_eyre::Result<()> {
+    let data = r#"
+    {
+      "data": [
+        {
+          "keyId": "46a92767-5f93-4a46-9eed-f012196bb4fc",
+          "signingDeviceKeyId": "MyKey1",
+          "publicKeyPem": "-----BEGIN PUBLIC KEY-----",
+          "algorithm": "MPC_EDDSA_ED25519",
+          "enabled": true,
+          "vaultAccountId": 10,
+          "agentUserId": "d2d0a3b5-3c4e-4d4b-8a6b-6e1b7a7e2c3f",
+          "createdAt": 1706745600000
+        }
+      ],
+      "next": null
+    }
+    "#;
+    let keys: SigningKeys = serde_json::from_str(data)?;
+    assert_eq!(SigningAlgorithm::MPC_EDDSA_ED25519, keys.data[0].algorithm);
+    assert_eq!(Some(10), keys.data[0].vault_account_id);
+    assert!(keys.next.is_none());
+    Ok(())
+  }
+}
@@ -7,23 +7,50 @@ use serde_json::Value;
 
 pub mod address;
 pub mod asset;
+pub mod audit;
+pub mod blockchain;
 pub mod connect;
 pub mod fee;
 pub mod hooks;
+pub mod job;
+pub mod key_link;
+pub mod ncw;
+pub mod nft;
 mod page;
+pub mod payout;
+pub mod policy;
+pub mod smart_transfer;
 pub mod staking;
+pub mod tag;
+pub mod tokenization;
 pub mod transaction;
+pub mod user;
 pub mod vault;
 pub mod wallet;
+pub mod workspace;
 
 pub use address::*;
 pub use asset::*;
+pub use audit::*;
+pub use blockchain::*;
 pub use fee::*;
+pub use hooks::*;
+pub use job::*;
+pub use key_link::*;
+pub use ncw::*;
+pub use nft::*;
 pub use page::*;
+pub use payout::*;
+pub use policy::*;
+pub use smart_transfer::*;
 pub use staking::*;
+pub use tag::*;
+pub use tokenization::*;
 pub use transaction::*;
+pub use user::*;
 pub use vault::*;
 pub use wallet::*;
+pub use workspace::*;
 
 fn deserialize_option_empty_object<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
 where
@@ -50,13 +77,13 @@ where
     _ => Err(SerdeError::custom(format!("invalid timestamp {millis}"))),
   }
 }
-fn deserialize_str_i32<'de, D>(deserializer: D) -> Result<i32, D::Error>
+pub(crate) fn deserialize_str_i32<'de, D>(deserializer: D) -> Result<i32, D::Error>
 where
   D: Deserializer<'de>,
 {
   struct Visitor;
 
-  impl<'de> serde::de::Visitor<'de> for Visitor {
+  impl serde::de::Visitor<'_> for Visitor {
     type Value = i32;
 
     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
@@ -146,6 +173,6 @@ mod test {
       Ok(a) => {
         assert_eq!(a.id, 483);
       },
-    };
+    }
   }
 }
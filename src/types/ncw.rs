@@ -0,0 +1,291 @@
+use crate::types::SigningAlgorithm;
+use crate::QueryParams;
+use bigdecimal::BigDecimal;
+use serde_derive::{Deserialize, Serialize};
+
+/// An embedded (non-custodial) wallet
+///
+/// See
+/// * [getEmbeddedWallet](https://developers.fireblocks.com/reference/getembeddedwallet)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NcwWallet {
+  pub wallet_id: String,
+  pub enabled: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NcwWallets {
+  pub data: Vec<NcwWallet>,
+  pub next: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NcwAccount {
+  pub wallet_id: String,
+  pub account_id: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NcwAccounts {
+  pub data: Vec<NcwAccount>,
+  pub next: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NcwAsset {
+  pub id: String,
+  pub symbol: String,
+  pub name: String,
+  pub decimals: Option<u8>,
+  pub network_protocol: Option<String>,
+  #[serde(default)]
+  pub testnet: bool,
+  #[serde(default)]
+  pub has_footprint: bool,
+  pub native_asset: Option<String>,
+  #[serde(rename = "type")]
+  pub asset_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NcwAssets {
+  pub data: Vec<NcwAsset>,
+  pub next: Option<String>,
+}
+
+/// See
+/// * [getEmbeddedWalletAssetBalance](https://developers.fireblocks.com/reference/getembeddedwalletassetbalance)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NcwAssetBalance {
+  pub id: String,
+  pub total: BigDecimal,
+  pub available: BigDecimal,
+  #[serde(default)]
+  pub pending: BigDecimal,
+  #[serde(default)]
+  pub frozen: BigDecimal,
+  #[serde(default)]
+  pub locked_amount: BigDecimal,
+  pub block_height: Option<String>,
+  pub block_hash: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NcwAddress {
+  pub account_name: Option<String>,
+  pub account_id: String,
+  pub asset: String,
+  pub address: String,
+  pub address_type: Option<String>,
+  pub address_description: Option<String>,
+  pub tag: Option<String>,
+  pub address_index: Option<u32>,
+  pub legacy_address: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NcwAddresses {
+  pub data: Vec<NcwAddress>,
+  pub next: Option<String>,
+}
+
+/// A device of an end user holding a key share of an embedded wallet
+///
+/// See
+/// * [getEmbeddedWalletDevice](https://developers.fireblocks.com/reference/getembeddedwalletdevice)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NcwDevice {
+  pub device_id: String,
+  pub enabled: bool,
+  pub physical_device_id: Option<String>,
+}
+
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NcwSetupStatus {
+  COMPLETE,
+  #[default]
+  INCOMPLETE,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NcwKeySetupStatus {
+  pub algorithm_name: SigningAlgorithm,
+  pub status: NcwSetupStatus,
+  pub confirmed: Option<bool>,
+  pub backed_up: Option<bool>,
+}
+
+/// Progress of the MPC key generation of a device
+///
+/// See
+/// * [getEmbeddedWalletDeviceSetupState](https://developers.fireblocks.com/reference/getembeddedwalletdevicesetupstate)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NcwDeviceSetupStatus {
+  pub status: NcwSetupStatus,
+  pub device_id: String,
+  #[serde(default)]
+  pub setup_status: Vec<NcwKeySetupStatus>,
+}
+
+impl NcwDeviceSetupStatus {
+  pub fn is_complete(&self) -> bool {
+    self.status == NcwSetupStatus::COMPLETE
+  }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NcwRpcError {
+  pub message: String,
+  pub code: Option<i64>,
+}
+
+/// Response of the MPC RPC relayed from a device, passed back to the device SDK as is
+///
+/// See
+/// * [invokeWalletRpc](https://developers.fireblocks.com/reference/invokewalletrpc)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NcwRpcResponse {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub result: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub error: Option<NcwRpcError>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NcwBackupKey {
+  pub device_id: String,
+  pub public_key: String,
+  pub key_id: String,
+  pub algorithm: SigningAlgorithm,
+}
+
+/// See
+/// * [getEmbeddedWalletLatestBackup](https://developers.fireblocks.com/reference/getembeddedwalletlatestbackup)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NcwBackup {
+  pub passphrase_id: String,
+  pub created_at: i64,
+  pub keys: Vec<NcwBackupKey>,
+}
+
+/// Paging parameters shared by the embedded wallet list endpoints
+///
+/// [getEmbeddedWallets](https://developers.fireblocks.com/reference/getembeddedwallets)
+#[derive(Debug, Default)]
+pub struct NcwListBuilder {
+  params: QueryParams,
+}
+
+impl NcwListBuilder {
+  #[allow(clippy::missing_const_for_fn)]
+  pub fn new() -> Self {
+    Self { params: Vec::new() }
+  }
+
+  pub fn page_size(&mut self, size: u16) -> &mut Self {
+    self.params.push(("pageSize".to_owned(), format!("{size}")));
+    self
+  }
+
+  pub fn page_cursor(&mut self, cursor: &str) -> &mut Self {
+    if !cursor.is_empty() {
+      self.params.push(("pageCursor".to_owned(), String::from(cursor)));
+    }
+    self
+  }
+
+  pub fn sort(&mut self, field: &str) -> &mut Self {
+    self.params.push(("sort".to_owned(), String::from(field)));
+    self
+  }
+
+  pub fn order(&mut self, ascending: bool) -> &mut Self {
+    let order = if ascending { "ASC" } else { "DESC" };
+    self.params.push(("order".to_owned(), order.to_owned()));
+    self
+  }
+
+  /// Only applies to [`crate::Client::ncw_wallets`]
+  pub fn enabled(&mut self, enabled: bool) -> &mut Self {
+    self.params.push(("enabled".to_owned(), enabled.to_string()));
+    self
+  }
+
+  #[allow(clippy::unnecessary_wraps)]
+  pub fn build(&self) -> std::result::Result<QueryParams, crate::error::ParamError> {
+    Ok(Vec::clone(&self.params))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::types::ncw::{NcwAddresses, NcwAssetBalance, NcwDeviceSetupStatus, NcwRpcResponse};
+  use bigdecimal::BigDecimal;
+  use std::str::FromStr;
+
+  #[test]
+  fn test_json_ncw_balance() -> color_eyre::Result<()> {
+    let data = r#"{"id": "ETH_TEST5", "total": "0.5", "available": "0.4", "pending": "0.1", "lockedAmount": "0"}"#;
+    let balance: NcwAssetBalance = serde_json::from_str(data)?;
+    assert_eq!(BigDecimal::from_str("0.4")?, balance.available);
+    assert_eq!(BigDecimal::from(0), balance.frozen);
+
+    let data = r#"
+    {
+      "data": [
+        {
+          "accountId": "0",
+          "asset": "ETH_TEST5",
+          "address": "0x3f6b8d2a1c9e0f4b5a7d6c8e9f0a1b2c3d4e5f60",
+          "addressType": "PERMANENT",
+          "addressIndex": 0
+        }
+      ],
+      "next": null
+    }
+    "#;
+    let addresses: NcwAddresses = serde_json::from_str(data)?;
+    assert_eq!(Some(0), addresses.data[0].address_index);
+    Ok(())
+  }
+
+  #[test]
+  fn test_json_ncw_device() -> color_eyre::Result<()> {
+    let data = r#"
+    {
+      "status": "INCOMPLETE",
+      "deviceId": "a4a3b1d0-6d5a-4a7b-9f65-6f5c0e2b3e11",
+      "setupStatus": [
+        {"algorithmName": "MPC_ECDSA_SECP256K1", "status": "COMPLETE", "confirmed": true, "backedUp": false},
+        {"algorithmName": "MPC_EDDSA_ED25519", "status": "INCOMPLETE"}
+      ]
+    }
+    "#;
+    let setup: NcwDeviceSetupStatus = serde_json::from_str(data)?;
+    assert!(!setup.is_complete());
+    assert_eq!(2, setup.setup_status.len());
+
+    let rpc: NcwRpcResponse = serde_json::from_str(r#"{"error": {"message": "bad payload", "code": -1}}"#)?;
+    assert!(rpc.result.is_none());
+    assert_eq!(Some(-1), rpc.error.and_then(|e| e.code));
+    Ok(())
+  }
+}
@@ -0,0 +1,310 @@
+use crate::error::ParamError;
+use crate::types::{DestinationTransferPeerPath, TransactionArguments, TransactionOperation, TransferPeerPath};
+use crate::QueryParams;
+use serde_derive::{Deserialize, Serialize};
+use std::borrow::Borrow;
+
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, Eq)]
+pub enum NftStandard {
+  #[default]
+  ERC721,
+  ERC1155,
+  METAPLEX,
+  #[serde(other)]
+  UNKNOWN,
+}
+
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, Eq)]
+pub enum NftOwnershipStatus {
+  #[default]
+  LISTED,
+  ARCHIVED,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NftMedia {
+  pub url: String,
+  pub content_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NftSpam {
+  pub result: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NftCollection {
+  pub id: String,
+  pub name: Option<String>,
+  pub symbol: Option<String>,
+  pub standard: Option<NftStandard>,
+  pub blockchain_descriptor: Option<String>,
+  pub contract_address: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NftPaging {
+  pub next: Option<String>,
+}
+
+/// Token metadata, as returned by
+/// * [getNFT](https://developers.fireblocks.com/reference/getnft)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NftToken {
+  pub id: String,
+  pub token_id: String,
+  pub standard: NftStandard,
+  pub blockchain_descriptor: String,
+  pub name: Option<String>,
+  pub description: Option<String>,
+  #[serde(rename = "metadataURI")]
+  pub metadata_uri: Option<String>,
+  #[serde(rename = "cachedMetadataURI")]
+  pub cached_metadata_uri: Option<String>,
+  #[serde(default)]
+  pub media: Vec<NftMedia>,
+  pub spam: Option<NftSpam>,
+  pub collection: Option<NftCollection>,
+}
+
+impl NftToken {
+  /// Build the arguments to transfer `amount` of this token out of `source_vault`
+  ///
+  /// ERC-721 (and Metaplex) tokens are unique, so `amount` must be 1 for those standards.
+  /// ERC-1155 tokens can move any positive amount.
+  pub fn transfer_args(
+    &self,
+    source_vault: i32,
+    destination: DestinationTransferPeerPath,
+    amount: u64,
+    note: Option<&str>,
+  ) -> Result<TransactionArguments, ParamError> {
+    if amount == 0 {
+      return Err(ParamError::InvalidParams { msg: format!("nft {} transfer amount must be positive", self.id) });
+    }
+    if amount > 1 && self.standard != NftStandard::ERC1155 {
+      return Err(ParamError::InvalidParams {
+        msg: format!("nft {} is {:?} and can only be transferred with amount 1", self.id, self.standard),
+      });
+    }
+    Ok(TransactionArguments {
+      asset_id: self.id.clone(),
+      operation: TransactionOperation::TRANSFER,
+      source: TransferPeerPath { id: Some(source_vault.to_string()), ..Default::default() },
+      destination: Some(destination),
+      amount: amount.to_string(),
+      gas_price: None,
+      gas_limit: None,
+      fee_level: None,
+      note: note.unwrap_or("created by fireblocks-sdk for rust").to_string(),
+      extra_parameters: None,
+      destinations: None,
+      external_tx_id: None,
+    })
+  }
+}
+
+/// A token owned by a vault account or an embedded wallet
+///
+/// See
+/// * [getOwnershipTokens](https://developers.fireblocks.com/reference/getownershiptokens)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NftOwnedToken {
+  #[serde(flatten)]
+  pub token: NftToken,
+  pub vault_account_id: Option<String>,
+  pub ncw_id: Option<String>,
+  pub ncw_account_id: Option<String>,
+  pub balance: String,
+  pub status: Option<NftOwnershipStatus>,
+  pub ownership_start_time: Option<i64>,
+  pub ownership_last_update_time: Option<i64>,
+}
+
+/// Bulk ownership status update item
+///
+/// [updateTokensOwnershipStatus](https://developers.fireblocks.com/reference/updatetokensownershipstatus)
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NftStatusUpdate {
+  pub asset_id: String,
+  pub status: NftOwnershipStatus,
+}
+
+/// Bulk spam flag update item
+///
+/// [updateTokensOwnershipSpam](https://developers.fireblocks.com/reference/updatetokensownershipspam)
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NftSpamUpdate {
+  pub asset_id: String,
+  pub spam: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NftOwnedTokens {
+  pub data: Vec<NftOwnedToken>,
+  pub paging: Option<NftPaging>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NftTokens {
+  pub data: Vec<NftToken>,
+  pub paging: Option<NftPaging>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NftCollections {
+  pub data: Vec<NftCollection>,
+  pub paging: Option<NftPaging>,
+}
+
+/// Search for owned NFTs, collections and unique assets
+///
+/// [getOwnershipTokens](https://developers.fireblocks.com/reference/getownershiptokens)
+#[derive(Debug, Default)]
+pub struct NftOwnershipListBuilder {
+  params: QueryParams,
+}
+
+impl NftOwnershipListBuilder {
+  #[allow(clippy::missing_const_for_fn)]
+  pub fn new() -> Self {
+    Self { params: Vec::new() }
+  }
+
+  pub fn blockchain_descriptor(&mut self, chain: &str) -> &mut Self {
+    self.params.push(("blockchainDescriptor".to_owned(), String::from(chain)));
+    self
+  }
+
+  pub fn vault_account_ids(&mut self, ids: &[i32]) -> &mut Self {
+    let ids: Vec<String> = ids.iter().map(ToString::to_string).collect();
+    self.params.push(("vaultAccountIds".to_owned(), ids.join(",")));
+    self
+  }
+
+  pub fn ncw_id(&mut self, id: &str) -> &mut Self {
+    self.params.push(("ncwId".to_owned(), String::from(id)));
+    self.params.push(("walletType".to_owned(), String::from("END_USER_WALLET")));
+    self
+  }
+
+  pub fn ids<T: Borrow<str>>(&mut self, ids: &[T]) -> &mut Self {
+    self.params.push(("ids".to_owned(), ids.join(",")));
+    self
+  }
+
+  pub fn collection_ids<T: Borrow<str>>(&mut self, ids: &[T]) -> &mut Self {
+    self.params.push(("collectionIds".to_owned(), ids.join(",")));
+    self
+  }
+
+  pub fn status(&mut self, status: &NftOwnershipStatus) -> &mut Self {
+    let s = match status {
+      NftOwnershipStatus::LISTED => "LISTED",
+      NftOwnershipStatus::ARCHIVED => "ARCHIVED",
+    };
+    self.params.push(("status".to_owned(), String::from(s)));
+    self
+  }
+
+  pub fn spam(&mut self, spam: bool) -> &mut Self {
+    self.params.push(("spam".to_owned(), spam.to_string()));
+    self
+  }
+
+  pub fn search(&mut self, s: &str) -> &mut Self {
+    self.params.push(("search".to_owned(), String::from(s)));
+    self
+  }
+
+  pub fn page_size(&mut self, size: u16) -> &mut Self {
+    self.params.push(("pageSize".to_owned(), format!("{size}")));
+    self
+  }
+
+  pub fn page_cursor(&mut self, cursor: &str) -> &mut Self {
+    if !cursor.is_empty() {
+      self.params.push(("pageCursor".to_owned(), String::from(cursor)));
+    }
+    self
+  }
+
+  #[allow(clippy::unnecessary_wraps)]
+  pub fn build(&self) -> std::result::Result<QueryParams, crate::error::ParamError> {
+    Ok(Vec::clone(&self.params))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::types::nft::{NftOwnedTokens, NftOwnershipListBuilder, NftStandard, NftToken};
+  use crate::types::DestinationTransferPeerPath;
+
+  #[test]
+  fn test_json_owned_tokens() -> color_eyre::Result<()> {
+    let data = r#"
+    {
+      "paging": { "next": "MjAyMy0wNi0xMyAxMzo0NToxMy4xMTQ=" },
+      "data": [
+        {
+          "id": "NFT-abcdefabcdefabcdefabcdefabcdefabcdefabcd",
+          "tokenId": "7",
+          "standard": "ERC1155",
+          "metadataURI": "ipfs://QmSomething/7.json",
+          "cachedMetadataURI": "https://cache.example/7.json",
+          "media": [{ "url": "https://cache.example/7.png", "contentType": "IMAGE" }],
+          "spam": { "result": false },
+          "collection": { "id": "0x9bb4d44e6963260a1850926e8f6beb8d5803836f", "name": "Test", "symbol": "TST" },
+          "blockchainDescriptor": "ETH_TEST5",
+          "vaultAccountId": "0",
+          "balance": "2",
+          "status": "LISTED",
+          "ownershipStartTime": 1686663913,
+          "ownershipLastUpdateTime": 1686663913
+        }
+      ]
+    }
+    "#;
+    let t: NftOwnedTokens = serde_json::from_str(data)?;
+    assert_eq!(1, t.data.len());
+    assert_eq!(NftStandard::ERC1155, t.data[0].token.standard);
+    assert_eq!("2", t.data[0].balance);
+    assert!(t.paging.and_then(|p| p.next).is_some());
+
+    let params = NftOwnershipListBuilder::new().vault_account_ids(&[0, 1]).spam(false).page_size(50).build()?;
+    assert!(params.iter().any(|(k, v)| k == "vaultAccountIds" && v == "0,1"));
+    Ok(())
+  }
+
+  #[test]
+  fn test_transfer_args() -> color_eyre::Result<()> {
+    let mut token = NftToken { id: "NFT-1".to_string(), standard: NftStandard::ERC721, ..Default::default() };
+    let dest = || DestinationTransferPeerPath { id: "1".to_string(), ..Default::default() };
+    assert!(token.transfer_args(0, dest(), 2, None).is_err());
+    assert!(token.transfer_args(0, dest(), 0, None).is_err());
+    let args = token.transfer_args(0, dest(), 1, None)?;
+    assert_eq!("NFT-1", args.asset_id);
+    assert_eq!("1", args.amount);
+
+    token.standard = NftStandard::ERC1155;
+    let args = token.transfer_args(0, dest(), 5, Some("batch"))?;
+    assert_eq!("5", args.amount);
+    assert_eq!("batch", args.note);
+    Ok(())
+  }
+}
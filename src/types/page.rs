@@ -53,7 +53,7 @@ impl PagingAddressRequestBuilder {
   }
 
   pub fn after(&mut self, t: &str) -> &mut Self {
-    self.params.push(("before".to_owned(), String::from(t)));
+    self.params.push(("after".to_owned(), String::from(t)));
     self
   }
 }
@@ -75,7 +75,16 @@ impl PagingVaultRequestBuilder {
   }
 
   pub fn before(&mut self, t: &str) -> &mut Self {
-    self.params.push(("before".to_owned(), String::from(t)));
+    if !t.is_empty() {
+      self.params.push(("before".to_owned(), String::from(t)));
+    }
+    self
+  }
+
+  /// Order by creation time, descending (most recent first) is the Fireblocks default
+  pub fn order(&mut self, ascending: bool) -> &mut Self {
+    let order = if ascending { "ASC" } else { "DESC" };
+    self.params.push(("orderBy".to_owned(), order.to_owned()));
     self
   }
 
@@ -86,3 +95,24 @@ impl PagingVaultRequestBuilder {
     self
   }
 }
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_vault_paging() -> color_eyre::Result<()> {
+    let params = PagingVaultRequestBuilder::new().limit(10).before("MjA=").order(false).after("").build()?;
+    let expected = [("before", "MjA="), ("orderBy", "DESC"), ("limit", "10")];
+    assert_eq!(params, expected.map(|(k, v)| (k.to_owned(), v.to_owned())));
+    assert!(PagingVaultRequestBuilder::new().before("").build()?.is_empty());
+    Ok(())
+  }
+
+  #[test]
+  fn test_address_paging() -> color_eyre::Result<()> {
+    let params = PagingAddressRequestBuilder::new().after("a").before("b").build()?;
+    assert_eq!(params, [("after", "a"), ("before", "b")].map(|(k, v)| (k.to_owned(), v.to_owned())));
+    Ok(())
+  }
+}
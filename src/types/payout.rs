@@ -0,0 +1,191 @@
+use crate::types::deserialize_option_empty_object;
+use bigdecimal::BigDecimal;
+use serde_derive::{Deserialize, Serialize};
+
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, Eq)]
+pub enum PayoutAccountType {
+  #[default]
+  VAULT_ACCOUNT,
+  EXCHANGE_ACCOUNT,
+  FUND_ACCOUNT,
+  INTERNAL_WALLET,
+  EXTERNAL_WALLET,
+  NETWORK_CONNECTION,
+  FIAT_ACCOUNT,
+  BANK_ACCOUNT,
+}
+
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, Eq)]
+pub enum PayoutState {
+  #[default]
+  CREATED,
+  FINALIZED,
+  INSUFFICIENT_BALANCE,
+  REQUEST_TIMEOUT,
+  SUBMITTED,
+  TRANSACTIONS_STARTED,
+  DONE,
+  FAILED,
+  CANCELLED,
+  #[serde(other)]
+  UNKNOWN,
+}
+
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, Eq)]
+pub enum PayoutStatus {
+  #[default]
+  REGISTERED,
+  VERIFYING,
+  IN_PROGRESS,
+  DONE,
+  INSUFFICIENT_BALANCE,
+  FAILED,
+  #[serde(other)]
+  UNKNOWN,
+}
+
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, Eq)]
+pub enum PayoutInstructionState {
+  #[default]
+  NOT_STARTED,
+  TRANSACTION_SENT,
+  COMPLETED,
+  FAILED,
+  CANCELLED,
+  #[serde(other)]
+  UNKNOWN,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PayoutAccount {
+  pub id: String,
+  #[serde(rename = "type")]
+  pub account_type: PayoutAccountType,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PayoutAmount {
+  pub amount: BigDecimal,
+  pub asset_id: String,
+}
+
+/// A single payee of a payout
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PayoutInstruction {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub id: Option<String>,
+  pub payee_account: PayoutAccount,
+  pub amount: PayoutAmount,
+}
+
+/// Create a payout instruction set
+///
+/// See
+/// * [createPayout](https://developers.fireblocks.com/reference/createpayout)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePayoutRequest {
+  pub payment_account: PayoutAccount,
+  pub instruction_set: Vec<PayoutInstruction>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PayoutTransaction {
+  pub id: Option<String>,
+  pub state: String,
+  pub timestamp: Option<i64>,
+  pub instruction_id: Option<String>,
+}
+
+/// The outcome of a single payout instruction
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PayoutInstructionResponse {
+  pub id: Option<String>,
+  pub payee_account: PayoutAccount,
+  pub amount: PayoutAmount,
+  pub state: PayoutInstructionState,
+  #[serde(default)]
+  pub transactions: Vec<PayoutTransaction>,
+}
+
+/// See
+/// * [getPayout](https://developers.fireblocks.com/reference/getpayout)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Payout {
+  pub payout_id: String,
+  pub payment_account: PayoutAccount,
+  pub created_at: i64,
+  pub state: PayoutState,
+  pub status: PayoutStatus,
+  #[serde(deserialize_with = "deserialize_option_empty_object", default)]
+  pub reason_of_failure: Option<String>,
+  pub init_method: Option<String>,
+  pub instruction_set: Vec<PayoutInstructionResponse>,
+  pub report_url: Option<String>,
+}
+
+impl Payout {
+  /// Instructions which did not complete
+  pub fn failed_instructions(&self) -> impl Iterator<Item = &PayoutInstructionResponse> {
+    self.instruction_set.iter().filter(|i| i.state == PayoutInstructionState::FAILED)
+  }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PayoutExecuteResponse {
+  pub payout_id: String,
+}
+
+#[cfg(test)]
+mod test {
+  use crate::types::payout::{Payout, PayoutInstructionState, PayoutState};
+
+  #[test]
+  fn test_json_payout() -> color_eyre::Result<()> {
+    let data = r#"
+    {
+      "payoutId": "bb7e5d9b-4ae3-4e47-b14f-1e7e0ec5fd5a",
+      "paymentAccount": { "id": "0", "type": "VAULT_ACCOUNT" },
+      "createdAt": 1700000000000,
+      "state": "DONE",
+      "status": "DONE",
+      "initMethod": "API",
+      "instructionSet": [
+        {
+          "id": "1",
+          "payeeAccount": { "id": "wallet-1", "type": "EXTERNAL_WALLET" },
+          "amount": { "amount": "10.5", "assetId": "USDC" },
+          "state": "COMPLETED",
+          "transactions": [{ "id": "tx-1", "state": "COMPLETED", "timestamp": 1700000000000, "instructionId": "1" }]
+        },
+        {
+          "id": "2",
+          "payeeAccount": { "id": "wallet-2", "type": "EXTERNAL_WALLET" },
+          "amount": { "amount": "1", "assetId": "USDC" },
+          "state": "FAILED"
+        }
+      ]
+    }
+    "#;
+    let p: Payout = serde_json::from_str(data)?;
+    assert_eq!(PayoutState::DONE, p.state);
+    assert_eq!(PayoutInstructionState::COMPLETED, p.instruction_set[0].state);
+    assert_eq!(1, p.failed_instructions().count());
+    Ok(())
+  }
+}
@@ -0,0 +1,602 @@
+use crate::error::ParamError;
+use bigdecimal::{BigDecimal, Zero};
+use serde_derive::{Deserialize, Serialize};
+use std::time::Duration;
+
+const WILDCARD: &str = "*";
+
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PolicyAction {
+  #[default]
+  ALLOW,
+  BLOCK,
+  #[serde(rename = "2-TIER")]
+  TWO_TIER,
+}
+
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PolicyTransactionType {
+  #[default]
+  TRANSFER,
+  CONTRACT_CALL,
+  APPROVE,
+  MINT,
+  BURN,
+  SUPPLY,
+  REDEEM,
+  STAKE,
+  RAW,
+  TYPED_MESSAGE,
+}
+
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum PolicyAmountCurrency {
+  USD,
+  EUR,
+  NATIVE,
+}
+
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum PolicyAmountScope {
+  SINGLE_TX,
+  TIMEFRAME,
+}
+
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum PolicyDstAddressType {
+  ONE_TIME,
+  WHITELISTED,
+  #[serde(rename = "*")]
+  ANY,
+}
+
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PolicyGroupLogic {
+  #[default]
+  AND,
+  OR,
+}
+
+/// Users, user groups and API users a rule applies to. `wildcard` is `"*"` to match anyone
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyOperators {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub wildcard: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub users: Option<Vec<String>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub users_groups: Option<Vec<String>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub services: Option<Vec<String>>,
+}
+
+/// Source or destination matchers, each id being `[id, type, subType, matchFrom]` where
+/// trailing elements are optional and `"*"` matches anything
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyPeers {
+  pub ids: Vec<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorizationGroup {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub users: Option<Vec<String>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub users_groups: Option<Vec<String>>,
+  /// Number of approvals required from this group
+  pub th: u32,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorizationGroups {
+  pub logic: PolicyGroupLogic,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub allow_operator_as_authorizer: Option<bool>,
+  pub groups: Vec<AuthorizationGroup>,
+}
+
+/// A transaction authorization policy rule. Unset fields match anything
+///
+/// See
+/// * [Policy rules](https://developers.fireblocks.com/reference/policy-rules)
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyRule {
+  #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+  pub rule_type: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub transaction_type: Option<PolicyTransactionType>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub operators: Option<PolicyOperators>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub designated_signer: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub designated_signers: Option<PolicyOperators>,
+  pub action: PolicyAction,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub asset: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub src: Option<PolicyPeers>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub dst: Option<PolicyPeers>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub dst_address_type: Option<PolicyDstAddressType>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub amount_currency: Option<PolicyAmountCurrency>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub amount_scope: Option<PolicyAmountScope>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub amount: Option<BigDecimal>,
+  /// Time frame of [`PolicyAmountScope::TIMEFRAME`] rules
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub period_sec: Option<u64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub authorization_groups: Option<AuthorizationGroups>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub external_descriptor: Option<String>,
+  /// Fields not modelled above, kept so rules round-trip unchanged
+  #[serde(flatten)]
+  pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyMetadata {
+  pub edited_by: Option<String>,
+  pub edited_at: Option<String>,
+  pub publish_by: Option<String>,
+  pub published_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Policy {
+  pub metadata: PolicyMetadata,
+  pub rules: Vec<PolicyRule>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyRuleError {
+  pub error_message: String,
+  pub error_code: Option<i64>,
+  pub error_code_name: Option<String>,
+  pub error_field: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyRuleCheckResult {
+  /// Index of the rule in the submitted rule list
+  pub index: u32,
+  pub status: String,
+  #[serde(default)]
+  pub errors: Vec<PolicyRuleError>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyCheckResult {
+  pub errors: u32,
+  #[serde(default)]
+  pub results: Vec<PolicyRuleCheckResult>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyValidation {
+  pub status: String,
+  pub check_result: PolicyCheckResult,
+}
+
+impl PolicyValidation {
+  pub const fn is_valid(&self) -> bool {
+    self.check_result.errors == 0
+  }
+}
+
+/// See
+/// * [getActivePolicy](https://developers.fireblocks.com/reference/getactivepolicy)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyAndValidation {
+  pub policy: Policy,
+  pub validation: PolicyValidation,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyDraft {
+  pub status: String,
+  pub draft_id: String,
+  pub rules: Vec<PolicyRule>,
+  pub metadata: PolicyMetadata,
+}
+
+/// See
+/// * [getDraft](https://developers.fireblocks.com/reference/getdraft)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyDraftAndValidation {
+  pub draft_response: PolicyDraft,
+  pub validation: PolicyValidation,
+}
+
+/// See
+/// * [publishPolicyRules](https://developers.fireblocks.com/reference/publishpolicyrules)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyPublishResult {
+  pub status: String,
+  #[serde(default)]
+  pub rules: Vec<PolicyRule>,
+  pub check_result: PolicyCheckResult,
+  pub metadata: PolicyMetadata,
+}
+
+/// Peer types used by source and destination matchers of policy rules
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolicyPeerType {
+  VAULT,
+  EXCHANGE,
+  UNMANAGED,
+  NETWORK_CONNECTION,
+  COMPOUND,
+  FIAT_ACCOUNT,
+  ONE_TIME_ADDRESS,
+  END_USER_WALLET,
+}
+
+impl PolicyPeerType {
+  pub const fn as_str(&self) -> &'static str {
+    match self {
+      Self::VAULT => "VAULT",
+      Self::EXCHANGE => "EXCHANGE",
+      Self::UNMANAGED => "UNMANAGED",
+      Self::NETWORK_CONNECTION => "NETWORK_CONNECTION",
+      Self::COMPOUND => "COMPOUND",
+      Self::FIAT_ACCOUNT => "FIAT_ACCOUNT",
+      Self::ONE_TIME_ADDRESS => "ONE_TIME_ADDRESS",
+      Self::END_USER_WALLET => "END_USER_WALLET",
+    }
+  }
+}
+
+impl PolicyPeers {
+  pub fn any() -> Self {
+    Self { ids: vec![vec![WILDCARD.to_owned()]] }
+  }
+
+  fn is_any(peers: Option<&Self>) -> bool {
+    peers.is_none_or(|p| p.ids.iter().any(|id| id.iter().all(|s| s == WILDCARD)))
+  }
+}
+
+impl PolicyRule {
+  /// Check the rule is consistent before sending it to Fireblocks, which otherwise
+  /// only reports problems once the draft is validated
+  pub fn validate(&self) -> Result<(), ParamError> {
+    let invalid = |msg: &str| Err(ParamError::InvalidParams { msg: format!("policy rule: {msg}") });
+    let has_operators = self.operators.as_ref().is_some_and(|o| {
+      o.wildcard.is_some()
+        || [&o.users, &o.users_groups, &o.services].iter().any(|l| l.as_ref().is_some_and(|l| !l.is_empty()))
+    });
+    if !has_operators {
+      return invalid("no initiators set");
+    }
+    let has_signer = self.designated_signer.is_some() || self.designated_signers.is_some();
+    match self.action {
+      PolicyAction::BLOCK => {
+        if self.authorization_groups.is_some() || has_signer {
+          return invalid("blocking rules cannot have approvers or signers");
+        }
+      },
+      PolicyAction::ALLOW => {
+        if self.authorization_groups.is_some() {
+          return invalid("allow rules cannot have approvers, use 2-TIER");
+        }
+      },
+      PolicyAction::TWO_TIER => {
+        let Some(groups) = self.authorization_groups.as_ref().filter(|g| !g.groups.is_empty()) else {
+          return invalid("2-TIER rules need at least one approval group");
+        };
+        for g in &groups.groups {
+          let users = g.users.as_ref().map_or(0, Vec::len);
+          let has_user_groups = g.users_groups.as_ref().is_some_and(|g| !g.is_empty());
+          if g.th == 0 {
+            return invalid("approval threshold must be at least 1");
+          }
+          if !has_user_groups && usize::try_from(g.th).is_ok_and(|th| th > users) {
+            return invalid(&format!("approval threshold {} exceeds the {users} approvers", g.th));
+          }
+        }
+      },
+    }
+    if self.action != PolicyAction::BLOCK && !has_signer {
+      return invalid("a designated signer is required");
+    }
+    if self.amount.as_ref().is_some_and(|a| *a < BigDecimal::zero()) {
+      return invalid("amount cannot be negative");
+    }
+    match (self.amount_scope, self.period_sec.unwrap_or_default()) {
+      (Some(PolicyAmountScope::TIMEFRAME), 0) => return invalid("TIMEFRAME rules need a period"),
+      (Some(PolicyAmountScope::SINGLE_TX) | None, p) if p > 0 => return invalid("a period needs the TIMEFRAME scope"),
+      _ => {},
+    }
+    Ok(())
+  }
+
+  /// Matches every transaction of its type, so any later rule of the same type is unreachable
+  fn is_catch_all(&self) -> bool {
+    self.operators.as_ref().is_some_and(|o| o.wildcard.as_deref() == Some(WILDCARD))
+      && self.asset.as_deref().is_none_or(|a| a == WILDCARD)
+      && PolicyPeers::is_any(self.src.as_ref())
+      && PolicyPeers::is_any(self.dst.as_ref())
+      && self.dst_address_type.is_none_or(|t| t == PolicyDstAddressType::ANY)
+      && self.amount.as_ref().is_none_or(BigDecimal::is_zero)
+  }
+}
+
+/// Validate each rule and the rule set as a whole. Rules are evaluated in order, so a
+/// catch-all rule followed by rules of the same transaction type is rejected
+pub fn validate_policy_rules(rules: &[PolicyRule]) -> Result<(), ParamError> {
+  for (i, rule) in rules.iter().enumerate() {
+    rule.validate().map_err(|e| ParamError::InvalidParams { msg: format!("rule {i}: {e}") })?;
+    if rule.is_catch_all() {
+      if let Some(j) = rules[i + 1..].iter().position(|r| r.transaction_type == rule.transaction_type) {
+        return Err(ParamError::InvalidParams {
+          msg: format!("rule {} is unreachable, rule {i} matches all its transactions", i + 1 + j),
+        });
+      }
+    }
+  }
+  Ok(())
+}
+
+/// Build a [`PolicyRule`], validated by [`PolicyRuleBuilder::build`]
+///
+/// ```
+/// use fireblocks_sdk::types::*;
+/// use bigdecimal::BigDecimal;
+///
+/// let rule = PolicyRuleBuilder::new(PolicyAction::TWO_TIER)
+///   .any_initiator()
+///   .asset("ETH")
+///   .source(PolicyPeerType::VAULT, "0")
+///   .any_destination()
+///   .amount_usd(BigDecimal::from(10_000))
+///   .designated_signer("signer-user-id")
+///   .approvers(&["approver-1", "approver-2"], 1)
+///   .build()
+///   .expect("invalid rule");
+/// assert_eq!(Some(PolicyAmountCurrency::USD), rule.amount_currency);
+/// ```
+#[derive(Debug, Default)]
+pub struct PolicyRuleBuilder {
+  rule: PolicyRule,
+}
+
+impl PolicyRuleBuilder {
+  pub fn new(action: PolicyAction) -> Self {
+    Self {
+      rule: PolicyRule {
+        rule_type: Some("TRANSFER".to_owned()),
+        transaction_type: Some(PolicyTransactionType::TRANSFER),
+        action,
+        ..Default::default()
+      },
+    }
+  }
+
+  pub const fn transaction_type(&mut self, t: PolicyTransactionType) -> &mut Self {
+    self.rule.transaction_type = Some(t);
+    self
+  }
+
+  pub fn any_initiator(&mut self) -> &mut Self {
+    self.rule.operators = Some(PolicyOperators { wildcard: Some(WILDCARD.to_owned()), ..Default::default() });
+    self
+  }
+
+  pub fn initiators(&mut self, users: &[&str], user_groups: &[&str]) -> &mut Self {
+    self.rule.operators =
+      Some(PolicyOperators { users: to_list(users), users_groups: to_list(user_groups), ..Default::default() });
+    self
+  }
+
+  pub fn asset(&mut self, asset: &str) -> &mut Self {
+    self.rule.asset = Some(String::from(asset));
+    self
+  }
+
+  /// Add a source matcher, may be called several times
+  pub fn source(&mut self, peer_type: PolicyPeerType, id: &str) -> &mut Self {
+    self
+      .rule
+      .src
+      .get_or_insert_with(PolicyPeers::default)
+      .ids
+      .push(vec![String::from(id), peer_type.as_str().to_owned()]);
+    self
+  }
+
+  pub fn any_source(&mut self) -> &mut Self {
+    self.rule.src = Some(PolicyPeers::any());
+    self
+  }
+
+  /// Add a destination matcher, may be called several times
+  pub fn destination(&mut self, peer_type: PolicyPeerType, id: &str) -> &mut Self {
+    self
+      .rule
+      .dst
+      .get_or_insert_with(PolicyPeers::default)
+      .ids
+      .push(vec![String::from(id), peer_type.as_str().to_owned()]);
+    self
+  }
+
+  pub fn any_destination(&mut self) -> &mut Self {
+    self.rule.dst = Some(PolicyPeers::any());
+    self
+  }
+
+  pub const fn destination_address_type(&mut self, t: PolicyDstAddressType) -> &mut Self {
+    self.rule.dst_address_type = Some(t);
+    self
+  }
+
+  /// Match transactions of at least `amount` USD
+  pub fn amount_usd(&mut self, amount: BigDecimal) -> &mut Self {
+    self.amount(PolicyAmountCurrency::USD, amount)
+  }
+
+  pub fn amount(&mut self, currency: PolicyAmountCurrency, amount: BigDecimal) -> &mut Self {
+    self.rule.amount_currency = Some(currency);
+    self.rule.amount = Some(amount);
+    self.rule.amount_scope.get_or_insert(PolicyAmountScope::SINGLE_TX);
+    self
+  }
+
+  /// Apply the amount to the total transferred within `period` instead of a single transaction
+  pub const fn over_period(&mut self, period: Duration) -> &mut Self {
+    self.rule.amount_scope = Some(PolicyAmountScope::TIMEFRAME);
+    self.rule.period_sec = Some(period.as_secs());
+    self
+  }
+
+  pub fn designated_signer(&mut self, user_id: &str) -> &mut Self {
+    self.rule.designated_signer = Some(String::from(user_id));
+    self
+  }
+
+  /// Add an approval group requiring `threshold` approvals from `users`
+  pub fn approvers(&mut self, users: &[&str], threshold: u32) -> &mut Self {
+    self.approval_group(users, &[], threshold)
+  }
+
+  pub fn approval_group(&mut self, users: &[&str], user_groups: &[&str], threshold: u32) -> &mut Self {
+    let groups = self.rule.authorization_groups.get_or_insert_with(AuthorizationGroups::default);
+    groups.groups.push(AuthorizationGroup { users: to_list(users), users_groups: to_list(user_groups), th: threshold });
+    self
+  }
+
+  /// How multiple approval groups combine, defaults to [`PolicyGroupLogic::AND`]
+  pub fn approval_logic(&mut self, logic: PolicyGroupLogic) -> &mut Self {
+    self.rule.authorization_groups.get_or_insert_with(AuthorizationGroups::default).logic = logic;
+    self
+  }
+
+  pub fn description(&mut self, d: &str) -> &mut Self {
+    self.rule.external_descriptor = Some(String::from(d));
+    self
+  }
+
+  pub fn build(&self) -> Result<PolicyRule, ParamError> {
+    self.rule.validate()?;
+    Ok(self.rule.clone())
+  }
+}
+
+fn to_list(ids: &[&str]) -> Option<Vec<String>> {
+  if ids.is_empty() {
+    return None;
+  }
+  Some(ids.iter().map(|s| String::from(*s)).collect())
+}
+
+#[cfg(test)]
+mod test {
+  use crate::types::policy::{
+    validate_policy_rules, PolicyAction, PolicyAndValidation, PolicyDstAddressType, PolicyPeerType, PolicyRuleBuilder,
+  };
+  use bigdecimal::BigDecimal;
+  use std::time::Duration;
+
+  #[test]
+  fn test_json_policy() -> color_eyre::Result<()> {
+    let data = r#"
+    {
+      "policy": {
+        "metadata": {"editedBy": "u1", "editedAt": "2024-03-01T10:00:00.000Z"},
+        "rules": [
+          {
+            "type": "TRANSFER",
+            "transactionType": "TRANSFER",
+            "operators": {"wildcard": "*"},
+            "designatedSigner": "u2",
+            "action": "2-TIER",
+            "asset": "*",
+            "src": {"ids": [["*"]]},
+            "dst": {"ids": [["3", "VAULT"]]},
+            "dstAddressType": "*",
+            "amountCurrency": "USD",
+            "amountScope": "SINGLE_TX",
+            "amount": 10000,
+            "periodSec": 0,
+            "authorizationGroups": {"logic": "OR", "groups": [{"users": ["u3"], "th": 1}]},
+            "applyForApprove": true
+          }
+        ]
+      },
+      "validation": {"status": "SUCCESS", "checkResult": {"errors": 0, "results": []}}
+    }
+    "#;
+    let p: PolicyAndValidation = serde_json::from_str(data)?;
+    let rule = &p.policy.rules[0];
+    assert_eq!(PolicyAction::TWO_TIER, rule.action);
+    assert_eq!(Some(PolicyDstAddressType::ANY), rule.dst_address_type);
+    assert!(rule.extra.contains_key("applyForApprove"));
+    assert!(p.validation.is_valid());
+
+    let out = serde_json::to_value(rule)?;
+    assert_eq!(Some(&serde_json::Value::Bool(true)), out.get("applyForApprove"));
+    assert_eq!(Some(&serde_json::json!("2-TIER")), out.get("action"));
+    Ok(())
+  }
+
+  #[test]
+  fn test_rule_builder() -> color_eyre::Result<()> {
+    let block_all = PolicyRuleBuilder::new(PolicyAction::BLOCK).any_initiator().build()?;
+    let rule = PolicyRuleBuilder::new(PolicyAction::TWO_TIER)
+      .initiators(&["u1"], &[])
+      .source(PolicyPeerType::VAULT, "0")
+      .destination(PolicyPeerType::UNMANAGED, "*")
+      .amount_usd(BigDecimal::from(1_000))
+      .over_period(Duration::from_hours(24))
+      .designated_signer("u2")
+      .approvers(&["u3", "u4"], 2)
+      .build()?;
+    assert_eq!(Some(86_400), rule.period_sec);
+    validate_policy_rules(&[rule.clone(), block_all.clone()])?;
+    assert!(validate_policy_rules(&[block_all, rule]).is_err());
+
+    assert!(PolicyRuleBuilder::new(PolicyAction::ALLOW).any_initiator().build().is_err());
+    assert!(PolicyRuleBuilder::new(PolicyAction::BLOCK).build().is_err());
+    assert!(PolicyRuleBuilder::new(PolicyAction::TWO_TIER)
+      .any_initiator()
+      .designated_signer("u2")
+      .approvers(&["u3"], 2)
+      .build()
+      .is_err());
+    Ok(())
+  }
+}
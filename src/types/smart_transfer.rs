@@ -0,0 +1,295 @@
+use crate::types::page::BasePageParams;
+use crate::types::FeeLevel;
+use crate::{impl_base_query_params, Epoch, QueryParams};
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde_derive::{Deserialize, Serialize};
+
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SmartTransferStatus {
+  #[default]
+  DRAFT,
+  PENDING_APPROVAL,
+  OPEN,
+  IN_SETTLEMENT,
+  FULFILLED,
+  EXPIRED,
+  CANCELED,
+}
+
+impl SmartTransferStatus {
+  pub const fn as_str(&self) -> &'static str {
+    match self {
+      Self::DRAFT => "DRAFT",
+      Self::PENDING_APPROVAL => "PENDING_APPROVAL",
+      Self::OPEN => "OPEN",
+      Self::IN_SETTLEMENT => "IN_SETTLEMENT",
+      Self::FULFILLED => "FULFILLED",
+      Self::EXPIRED => "EXPIRED",
+      Self::CANCELED => "CANCELED",
+    }
+  }
+
+  /// No further state transitions are possible
+  pub const fn is_final(&self) -> bool {
+    matches!(self, Self::FULFILLED | Self::EXPIRED | Self::CANCELED)
+  }
+}
+
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SmartTransferTermStatus {
+  #[default]
+  CREATED,
+  FUNDING,
+  FUNDING_FAILED,
+  FUNDED,
+  REJECTED,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SmartTransferTerm {
+  pub id: String,
+  pub ticket_id: String,
+  pub asset: String,
+  pub amount: BigDecimal,
+  pub amount_usd: Option<BigDecimal>,
+  pub from_network_id: String,
+  pub from_network_id_name: Option<String>,
+  pub to_network_id: String,
+  pub to_network_id_name: Option<String>,
+  pub tx_hash: Option<String>,
+  pub fb_tx_id: Option<String>,
+  pub tx_status: Option<String>,
+  pub status: SmartTransferTermStatus,
+  pub created_at: Option<DateTime<Utc>>,
+  pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// A smart transfer (OTC settlement) ticket between network counterparties
+///
+/// See
+/// * [findTicketById](https://developers.fireblocks.com/reference/findticketbyid)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SmartTransferTicket {
+  pub id: String,
+  #[serde(rename = "type")]
+  pub ticket_type: String,
+  pub direction: Option<String>,
+  pub status: SmartTransferStatus,
+  #[serde(default)]
+  pub terms: Vec<SmartTransferTerm>,
+  pub expires_in: Option<u32>,
+  pub expires_at: Option<DateTime<Utc>>,
+  pub submitted_at: Option<DateTime<Utc>>,
+  pub created_at: Option<DateTime<Utc>>,
+  pub updated_at: Option<DateTime<Utc>>,
+  pub external_ref_id: Option<String>,
+  pub note: Option<String>,
+  pub created_by_network_id: String,
+  pub created_by_network_id_name: Option<String>,
+  #[serde(default)]
+  pub canceled_by_me: bool,
+  #[serde(default)]
+  pub created_by_me: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SmartTransferTicketResponse {
+  pub data: SmartTransferTicket,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SmartTransferTermResponse {
+  pub data: SmartTransferTerm,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SmartTransferTickets {
+  pub message: Option<String>,
+  /// Cursor of the next page
+  pub after: Option<String>,
+  pub data: Vec<SmartTransferTicket>,
+}
+
+/// A term of a ticket: `amount` of `asset` moving between two network ids
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SmartTransferTermRequest {
+  pub asset: String,
+  pub amount: String,
+  pub from_network_id: String,
+  pub to_network_id: String,
+}
+
+/// Create a ticket
+///
+/// See
+/// * [createTicket](https://developers.fireblocks.com/reference/createticket)
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSmartTransferTicket {
+  pub created_by_network_id: String,
+  #[serde(rename = "type")]
+  pub ticket_type: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub expires_in: Option<u32>,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub terms: Vec<SmartTransferTermRequest>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub external_ref_id: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub note: Option<String>,
+  pub submit: bool,
+}
+
+impl CreateSmartTransferTicket {
+  pub fn new(network_id: &str) -> Self {
+    Self {
+      created_by_network_id: String::from(network_id),
+      ticket_type: String::from("ASYNC"),
+      expires_in: None,
+      terms: vec![],
+      external_ref_id: None,
+      note: None,
+      submit: false,
+    }
+  }
+}
+
+/// Fund a term from a source account
+///
+/// See
+/// * [fundTicketTerm](https://developers.fireblocks.com/reference/fundticketterm)
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SmartTransferFundTerm {
+  pub asset: String,
+  pub amount: String,
+  pub network_connection_id: String,
+  pub src_id: String,
+  pub src_type: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub fee_level: Option<FeeLevel>,
+}
+
+/// Search smart transfer tickets
+///
+/// [searchTickets](https://developers.fireblocks.com/reference/searchtickets)
+#[derive(Debug, Default)]
+pub struct SmartTransferListBuilder {
+  params: QueryParams,
+  base: BasePageParams,
+}
+
+impl_base_query_params!(SmartTransferListBuilder);
+
+impl SmartTransferListBuilder {
+  /// Free text search
+  pub fn query(&mut self, q: &str) -> &mut Self {
+    self.params.push(("q".to_owned(), String::from(q)));
+    self
+  }
+
+  pub fn statuses(&mut self, statuses: &[SmartTransferStatus]) -> &mut Self {
+    let s: Vec<&str> = statuses.iter().map(SmartTransferStatus::as_str).collect();
+    self.params.push(("statuses".to_owned(), s.join(",")));
+    self
+  }
+
+  pub fn network_id(&mut self, id: &str) -> &mut Self {
+    self.params.push(("networkId".to_owned(), String::from(id)));
+    self
+  }
+
+  pub fn created_by_me(&mut self, mine: bool) -> &mut Self {
+    self.params.push(("createdByMe".to_owned(), mine.to_string()));
+    self
+  }
+
+  pub fn expires_after(&mut self, t: &Epoch) -> &mut Self {
+    self.params.push(("expiresAfter".to_owned(), t.to_rfc3339()));
+    self
+  }
+
+  pub fn expires_before(&mut self, t: &Epoch) -> &mut Self {
+    self.params.push(("expiresBefore".to_owned(), t.to_rfc3339()));
+    self
+  }
+
+  pub fn external_ref_id(&mut self, id: &str) -> &mut Self {
+    self.params.push(("externalRefId".to_owned(), String::from(id)));
+    self
+  }
+
+  pub fn after(&mut self, cursor: &str) -> &mut Self {
+    if !cursor.is_empty() {
+      self.params.push(("after".to_owned(), String::from(cursor)));
+    }
+    self
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::types::smart_transfer::{
+    SmartTransferListBuilder, SmartTransferStatus, SmartTransferTermStatus, SmartTransferTickets,
+  };
+
+  #[test]
+  fn test_json_tickets() -> color_eyre::Result<()> {
+    let data = r#"
+    {
+      "after": "next-page",
+      "data": [
+        {
+          "id": "ticket-1",
+          "type": "ASYNC",
+          "direction": "EXCHANGE",
+          "status": "OPEN",
+          "terms": [
+            {
+              "id": "term-1",
+              "ticketId": "ticket-1",
+              "asset": "USDC",
+              "amount": "100",
+              "fromNetworkId": "net-a",
+              "toNetworkId": "net-b",
+              "status": "FUNDED",
+              "createdAt": "2024-03-22T10:51:03.772Z",
+              "updatedAt": "2024-03-22T10:51:03.772Z"
+            }
+          ],
+          "expiresIn": 24,
+          "expiresAt": "2024-03-23T10:51:03.772Z",
+          "createdAt": "2024-03-22T10:51:03.772Z",
+          "createdByNetworkId": "net-a",
+          "createdByNetworkIdName": "Desk A",
+          "canceledByMe": false,
+          "createdByMe": true
+        }
+      ]
+    }
+    "#;
+    let t: SmartTransferTickets = serde_json::from_str(data)?;
+    assert_eq!(Some("next-page".to_string()), t.after);
+    assert_eq!(SmartTransferStatus::OPEN, t.data[0].status);
+    assert_eq!(SmartTransferTermStatus::FUNDED, t.data[0].terms[0].status);
+
+    let params = SmartTransferListBuilder::new()
+      .statuses(&[SmartTransferStatus::OPEN, SmartTransferStatus::EXPIRED])
+      .limit(10)
+      .build()?;
+    assert!(params.iter().any(|(k, v)| k == "statuses" && v == "OPEN,EXPIRED"));
+    assert!(params.iter().any(|(k, v)| k == "limit" && v == "10"));
+    Ok(())
+  }
+}
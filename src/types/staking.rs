@@ -1,10 +1,13 @@
 use crate::types::deserialize_str_u64;
+use crate::types::FeeLevel;
 use crate::Asset;
 use bigdecimal::BigDecimal;
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
+use serde_derive::Serialize;
+use std::collections::HashMap;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct StakingPosition {
   /// The unique identifier of the staking position
@@ -101,6 +104,18 @@ pub struct StakingChainInfo {
   pub additional_info: StakingAdditionalInfo,
 }
 
+/// A validator run by a provider on a chain
+#[derive(Debug, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StakingValidator {
+  /// The chain this validator stakes on
+  pub chain_descriptor: String,
+  /// The commission taken by the provider, as a percentage of rewards
+  pub fee_percent: f64,
+  #[serde(default)]
+  pub is_private: bool,
+}
+
 #[derive(Debug, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct StakingProvider {
@@ -111,4 +126,131 @@ pub struct StakingProvider {
   pub terms_of_service_url: String,
   #[serde(rename = "isTermsOfServiceApproved")]
   pub terms_of_service_approved: bool,
+  #[serde(default)]
+  pub validators: Vec<StakingValidator>,
+  #[serde(default)]
+  pub is_liquid_staking: bool,
+}
+
+impl StakingProvider {
+  /// The validator this provider runs on `chain`, if any
+  pub fn validator(&self, chain: &str) -> Option<&StakingValidator> {
+    self.validators.iter().find(|v| v.chain_descriptor.eq_ignore_ascii_case(chain))
+  }
+
+  /// The commission charged on `chain`, if the provider supports it
+  pub fn fee_percent(&self, chain: &str) -> Option<f64> {
+    self.validator(chain).map(|v| v.fee_percent)
+  }
+
+  /// Chains this provider can stake on
+  pub fn chains(&self) -> Vec<&str> {
+    self.validators.iter().map(|v| v.chain_descriptor.as_str()).collect()
+  }
+}
+
+/// Vault account id to its staking summary
+pub type StakingVaultsSummary = HashMap<String, StakingPositionsSummary>;
+
+/// Stake funds from a vault account with a provider
+///
+/// See
+/// * [stake](https://developers.fireblocks.com/reference/stake)
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StakeRequest {
+  pub vault_account_id: String,
+  pub provider_id: String,
+  pub stake_amount: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub tx_note: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub fee_level: Option<FeeLevel>,
+}
+
+impl StakeRequest {
+  pub fn new(vault: i32, provider_id: &str, amount: &BigDecimal) -> Self {
+    Self {
+      vault_account_id: vault.to_string(),
+      provider_id: String::from(provider_id),
+      stake_amount: amount.to_string(),
+      tx_note: None,
+      fee_level: None,
+    }
+  }
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct StakeResponse {
+  /// The id of the created staking position
+  pub id: String,
+}
+
+/// Unstake, withdraw or claim rewards of an existing position
+///
+/// See
+/// * [unstake](https://developers.fireblocks.com/reference/unstake)
+/// * [withdraw](https://developers.fireblocks.com/reference/withdraw)
+/// * [claimRewards](https://developers.fireblocks.com/reference/claimrewards)
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StakingPositionAction {
+  /// The id of the staking position
+  pub id: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub tx_note: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub fee_level: Option<FeeLevel>,
+}
+
+impl StakingPositionAction {
+  pub fn new(position_id: &str) -> Self {
+    Self { id: String::from(position_id), tx_note: None, fee_level: None }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::types::staking::{StakeRequest, StakingProvider, StakingVaultsSummary};
+  use crate::types::FeeLevel;
+  use bigdecimal::BigDecimal;
+
+  #[test]
+  fn test_json_staking() -> color_eyre::Result<()> {
+    let mut req = StakeRequest::new(2, "kiln", &BigDecimal::from(32));
+    req.fee_level = Some(FeeLevel::High);
+    let v = serde_json::to_value(&req)?;
+    assert_eq!("2", v["vaultAccountId"]);
+    assert_eq!("32", v["stakeAmount"]);
+    assert_eq!("HIGH", v["feeLevel"]);
+    assert!(v.get("txNote").is_none());
+
+    let data = r#"{ "0": { "active": [{ "chainDescriptor": "ETH", "amount": "32" }], "inactive": [] } }"#;
+    let summary: StakingVaultsSummary = serde_json::from_str(data)?;
+    assert_eq!(1, summary["0"].active.len());
+    Ok(())
+  }
+
+  #[test]
+  fn test_json_provider() -> color_eyre::Result<()> {
+    let data = r#"
+    {
+      "id": "kiln",
+      "providerName": "Kiln",
+      "validators": [
+        { "chainDescriptor": "ETH", "feePercent": 5 },
+        { "chainDescriptor": "SOL", "feePercent": 7.5 }
+      ],
+      "iconUrl": "https://kiln.fi/icon.png",
+      "termsOfServiceUrl": "https://kiln.fi/tos",
+      "isTermsOfServiceApproved": false
+    }
+    "#;
+    let p: StakingProvider = serde_json::from_str(data)?;
+    assert_eq!(Some(7.5), p.fee_percent("sol"));
+    assert!(p.validator("MATIC").is_none());
+    assert_eq!(vec!["ETH", "SOL"], p.chains());
+    Ok(())
+  }
 }
@@ -0,0 +1,163 @@
+//! Destination tags and memos
+//!
+//! Exchanges on XRP, Stellar, Cosmos, TON and EOS share one deposit address between their customers and
+//! credit a transfer by its tag (memo), so a transfer with a missing or mangled tag lands on nobody's
+//! account. [`Client::create_transaction`](crate::Client::create_transaction) checks the tags of one-time
+//! address destinations of these assets before submitting, unless turned off with
+//! [`ClientBuilder::with_tag_validation`](crate::ClientBuilder::with_tag_validation). A missing tag is an
+//! error. Other addresses, e.g. personal wallets, take no tag, so
+//! [`ClientBuilder::with_required_tags`](crate::ClientBuilder::with_required_tags) can turn it into a warning.
+use crate::error::ParamError;
+use crate::types::{DestinationTransferPeerPath, TransactionArguments};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagFormat {
+  /// An unsigned 32 bit integer, e.g. XRP destination tags
+  Integer,
+  /// Text of at most this many bytes
+  Text(usize),
+}
+
+/// Chains taking a tag, matched against the parts of an asset id, e.g. `XRP`, `USDC_XLM` or `ATOM_COS_TEST`
+const TAGGED: [(&str, TagFormat); 5] = [
+  ("XRP", TagFormat::Integer),
+  // memo text, numeric memo ids fit too
+  ("XLM", TagFormat::Text(28)),
+  ("ATOM", TagFormat::Text(256)),
+  // a comment in a single cell
+  ("TON", TagFormat::Text(123)),
+  ("EOS", TagFormat::Text(256)),
+];
+
+/// The tag format of an asset, `None` when its transfers take no tag
+pub fn tag_format(asset_id: &str) -> Option<TagFormat> {
+  let asset_id = asset_id.to_uppercase();
+  asset_id.split('_').find_map(|part| TAGGED.iter().find(|(chain, _)| *chain == part).map(|(_, format)| *format))
+}
+
+impl TagFormat {
+  pub fn check(self, tag: &str) -> Result<(), ParamError> {
+    let invalid = |msg: String| Err(ParamError::InvalidParams { msg });
+    if tag.trim() != tag {
+      return invalid(format!("tag {tag:?} has surrounding whitespace"));
+    }
+    if tag.chars().any(char::is_control) {
+      return invalid(format!("tag {tag:?} has control characters"));
+    }
+    match self {
+      Self::Integer if !tag.bytes().all(|b| b.is_ascii_digit()) || tag.parse::<u32>().is_err() => {
+        invalid(format!("tag {tag:?} is not a number from 0 to {}", u32::MAX))
+      },
+      Self::Text(max) if tag.len() > max => invalid(format!("tag {tag:?} is longer than {max} bytes")),
+      _ => Ok(()),
+    }
+  }
+}
+
+impl TransactionArguments {
+  /// Check the tags of one-time address destinations of assets with a [`tag_format`]. A missing tag is an
+  /// error when `required`, otherwise a warning
+  pub fn validate_tags(&self, required: bool) -> Result<(), ParamError> {
+    let Some(format) = tag_format(&self.asset_id) else {
+      return Ok(());
+    };
+    let destinations = self.destination.iter().chain(self.destinations.iter().flatten().map(|d| &d.destination));
+    for ota in destinations.filter_map(|d: &DestinationTransferPeerPath| d.one_time_address.as_ref()) {
+      let asset = &self.asset_id;
+      let address = &ota.address;
+      match ota.tag.as_deref() {
+        None | Some("") if required => {
+          return Err(ParamError::InvalidParams { msg: format!("{asset} transfer to {address} needs a tag/memo") });
+        },
+        None | Some("") => tracing::warn!("{asset} transfer to {address} has no tag/memo"),
+        Some(tag) => format
+          .check(tag)
+          .map_err(|e| ParamError::InvalidParams { msg: format!("{asset} transfer to {address}: {e}") })?,
+      }
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::mock::{self, MockTransport};
+  use crate::types::{OneTimeAddress, PeerType, TransactionOperation, TransactionRequestDestination, TransferPeerPath};
+
+  fn transfer(asset_id: &str, tag: Option<&str>) -> TransactionArguments {
+    TransactionArguments {
+      asset_id: String::from(asset_id),
+      operation: TransactionOperation::TRANSFER,
+      source: TransferPeerPath { id: Some(String::from("0")), ..Default::default() },
+      destination: Some(DestinationTransferPeerPath {
+        peer_type: PeerType::ONE_TIME_ADDRESS,
+        one_time_address: Some(OneTimeAddress { address: String::from("rExchange"), tag: tag.map(String::from) }),
+        ..Default::default()
+      }),
+      amount: String::from("1"),
+      gas_price: None,
+      gas_limit: None,
+      fee_level: None,
+      extra_parameters: None,
+      destinations: None,
+      external_tx_id: None,
+      note: String::new(),
+    }
+  }
+
+  #[test]
+  fn test_tag_format() {
+    assert_eq!(tag_format("XRP_TEST"), Some(TagFormat::Integer));
+    assert_eq!(tag_format("usdc_xlm"), Some(TagFormat::Text(28)));
+    assert_eq!(tag_format("ATOM_COS"), Some(TagFormat::Text(256)));
+    assert_eq!(tag_format("BTC"), None);
+    assert!(TagFormat::Integer.check("4294967295").is_ok());
+    assert!(TagFormat::Integer.check("4294967296").is_err());
+    assert!(TagFormat::Integer.check("+12").is_err());
+    assert!(TagFormat::Text(28).check(&"m".repeat(29)).is_err());
+    assert!(TagFormat::Text(28).check("12345 ").is_err());
+    assert!(TagFormat::Text(28).check("a\nb").is_err());
+  }
+
+  #[test]
+  fn test_validate_tags() {
+    assert!(transfer("XRP", Some("1234")).validate_tags(true).is_ok());
+    assert!(transfer("XRP", None).validate_tags(true).is_err());
+    assert!(transfer("XRP", Some("")).validate_tags(true).is_err());
+    assert!(transfer("XRP", None).validate_tags(false).is_ok());
+    assert!(transfer("XRP", Some("memo")).validate_tags(false).is_err());
+    assert!(transfer("BTC", None).validate_tags(true).is_ok());
+
+    let mut multi = transfer("XLM", Some("invoice 7"));
+    let destination = multi.destination.take().unwrap_or_default();
+    multi.destinations = Some(vec![
+      TransactionRequestDestination { amount: String::from("1"), destination: destination.clone() },
+      TransactionRequestDestination {
+        amount: String::from("1"),
+        destination: DestinationTransferPeerPath { one_time_address: None, ..destination },
+      },
+    ]);
+    assert!(multi.validate_tags(true).is_ok());
+    if let Some(d) = multi.destinations.as_mut() {
+      d[0].destination.one_time_address = Some(OneTimeAddress { address: String::from("G"), tag: None });
+    }
+    assert!(multi.validate_tags(true).is_err());
+    assert!(multi.validate_tags(false).is_ok());
+  }
+
+  #[tokio::test]
+  async fn test_missing_tag() -> color_eyre::Result<()> {
+    let sent = MockTransport::new(|_| mock::json(http::StatusCode::OK, r#"{"id": "tx1", "status": "SUBMITTED"}"#));
+    let amount = bigdecimal::BigDecimal::from(1);
+    let client = mock::client(sent.clone())?;
+    assert!(client.create_transaction_external(0, "rExchange", "XRP", amount.clone(), None).await.is_err());
+    assert!(sent.requests().is_empty());
+    client.create_transaction_external_tagged(0, "rExchange", "1234", "XRP", amount.clone(), None).await?;
+
+    let client = mock::builder(sent.clone())?.with_required_tags(false).build()?;
+    client.create_transaction_external(0, "rWallet", "XRP", amount, None).await?;
+    assert_eq!(sent.requests().len(), 2);
+    Ok(())
+  }
+}
@@ -0,0 +1,243 @@
+use crate::assets::Asset;
+use crate::QueryParams;
+use bigdecimal::BigDecimal;
+use serde_derive::{Deserialize, Serialize};
+
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, Eq)]
+pub enum TokenLinkStatus {
+  #[default]
+  PENDING,
+  COMPLETED,
+}
+
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, Eq)]
+pub enum TokenLinkType {
+  #[default]
+  FUNGIBLE_TOKEN,
+  NON_FUNGIBLE_TOKEN,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenMetadata {
+  pub asset_id: Option<Asset>,
+  pub name: Option<String>,
+  pub symbol: Option<String>,
+  pub decimals: Option<i32>,
+  pub contract_address: Option<String>,
+  pub native_asset: Option<String>,
+  pub blockchain_id: Option<String>,
+}
+
+/// A token linked to the workspace
+///
+/// See
+/// * [getLinkedToken](https://developers.fireblocks.com/reference/getlinkedtoken)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenLink {
+  pub id: String,
+  pub status: TokenLinkStatus,
+  #[serde(rename = "type")]
+  pub link_type: Option<TokenLinkType>,
+  pub ref_id: Option<String>,
+  pub display_name: Option<String>,
+  pub token_metadata: Option<TokenMetadata>,
+  /// Vault accounts holding a role on the token contract
+  #[serde(default)]
+  pub permissions: Vec<TokenLinkPermission>,
+  /// Backing of collateralized tokens
+  pub collateral: Option<TokenCollateral>,
+}
+
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub enum TokenPermission {
+  MINTER,
+  BURNER,
+  MANAGER,
+  PAUSER,
+  UPGRADER,
+  WIPER,
+  RECOVERER,
+  #[serde(other)]
+  UNKNOWN,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenLinkPermission {
+  pub permission: TokenPermission,
+  pub vault_account_id: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenCollateral {
+  /// Asset locked as collateral
+  pub asset_id: Option<String>,
+  /// Vault account holding the collateral
+  pub vault_account_id: Option<String>,
+  pub amount: Option<BigDecimal>,
+  /// Collateral per token issued
+  pub ratio: Option<BigDecimal>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ParameterWithValue {
+  pub name: String,
+  #[serde(rename = "type")]
+  pub param_type: String,
+  pub value: String,
+}
+
+/// Deploy parameters, which depend on the target blockchain
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(untagged)]
+pub enum TokenCreateParams {
+  /// EVM chains deploy a contract template with constructor parameters
+  #[serde(rename_all = "camelCase")]
+  Evm { contract_id: String, constructor_params: Vec<ParameterWithValue> },
+  /// Stellar and Ripple issue a token from an issuer address
+  #[serde(rename_all = "camelCase")]
+  Issuer { symbol: String, name: String, issuer_address: String },
+}
+
+/// Issue a new token
+///
+/// See
+/// * [issueNewToken](https://developers.fireblocks.com/reference/issuenewtoken)
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTokenRequest {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub blockchain_id: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub asset_id: Option<String>,
+  pub vault_account_id: String,
+  pub create_params: TokenCreateParams,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub display_name: Option<String>,
+  pub use_gasless: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenLinks {
+  pub data: Vec<TokenLink>,
+  pub next: Option<String>,
+}
+
+/// Search for tokens linked to the workspace
+///
+/// [getLinkedTokens](https://developers.fireblocks.com/reference/getlinkedtokens)
+#[derive(Debug, Default)]
+pub struct TokenLinkListBuilder {
+  params: QueryParams,
+}
+
+impl TokenLinkListBuilder {
+  #[allow(clippy::missing_const_for_fn)]
+  pub fn new() -> Self {
+    Self { params: Vec::new() }
+  }
+
+  pub fn page_size(&mut self, size: u16) -> &mut Self {
+    self.params.push(("pageSize".to_owned(), format!("{size}")));
+    self
+  }
+
+  pub fn page_cursor(&mut self, cursor: &str) -> &mut Self {
+    if !cursor.is_empty() {
+      self.params.push(("pageCursor".to_owned(), String::from(cursor)));
+    }
+    self
+  }
+
+  pub fn status(&mut self, status: &TokenLinkStatus) -> &mut Self {
+    let s = match status {
+      TokenLinkStatus::PENDING => "PENDING",
+      TokenLinkStatus::COMPLETED => "COMPLETED",
+    };
+    self.params.push(("status".to_owned(), String::from(s)));
+    self
+  }
+
+  #[allow(clippy::unnecessary_wraps)]
+  pub fn build(&self) -> std::result::Result<QueryParams, crate::error::ParamError> {
+    Ok(Vec::clone(&self.params))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::types::tokenization::{
+    TokenLink, TokenLinkListBuilder, TokenLinkPermission, TokenLinkStatus, TokenLinkType, TokenLinks, TokenPermission,
+  };
+  use bigdecimal::BigDecimal;
+
+  #[test]
+  fn test_json_token_link() -> color_eyre::Result<()> {
+    let data = r#"
+    {
+      "id": "a0b1c2d3-0000-4000-8000-000000000001",
+      "status": "COMPLETED",
+      "type": "FUNGIBLE_TOKEN",
+      "refId": "USDF_ETH_TEST6_1234",
+      "displayName": "Fireblocks Dollar",
+      "tokenMetadata": {
+        "assetId": "USDF_ETH_TEST6_1234",
+        "name": "Fireblocks Dollar",
+        "symbol": "USDF",
+        "decimals": 18,
+        "contractAddress": "0x9bb4d44e6963260a1850926e8f6beb8d5803836f",
+        "nativeAsset": "ETH_TEST6"
+      },
+      "permissions": [
+        {"permission": "MINTER", "vaultAccountId": "0"},
+        {"permission": "FREEZER", "vaultAccountId": "1"}
+      ],
+      "collateral": {
+        "assetId": "USDC_ETH_TEST6",
+        "vaultAccountId": "2",
+        "amount": "1000000.5",
+        "ratio": "1"
+      }
+    }
+    "#;
+    let t: TokenLink = serde_json::from_str(data)?;
+    assert_eq!(t.status, TokenLinkStatus::COMPLETED);
+    assert_eq!(t.link_type, Some(TokenLinkType::FUNGIBLE_TOKEN));
+    assert_eq!(t.token_metadata.and_then(|m| m.decimals), Some(18));
+    assert_eq!(
+      t.permissions[0],
+      TokenLinkPermission { permission: TokenPermission::MINTER, vault_account_id: String::from("0") }
+    );
+    assert_eq!(t.permissions[1].permission, TokenPermission::UNKNOWN);
+    let collateral = t.collateral.unwrap_or_default();
+    assert_eq!(collateral.asset_id.as_deref(), Some("USDC_ETH_TEST6"));
+    assert_eq!(collateral.amount, Some("1000000.5".parse::<BigDecimal>()?));
+    Ok(())
+  }
+
+  #[test]
+  fn test_json_token_links() -> color_eyre::Result<()> {
+    let data = r#"{ "data": [{ "id": "1", "status": "PENDING" }], "next": "cursor" }"#;
+    let t: TokenLinks = serde_json::from_str(data)?;
+    assert_eq!(1, t.data.len());
+    assert!(t.data[0].permissions.is_empty() && t.data[0].collateral.is_none());
+    assert_eq!(Some("cursor".to_string()), t.next);
+
+    let params =
+      TokenLinkListBuilder::new().page_size(10).page_cursor("").status(&TokenLinkStatus::COMPLETED).build()?;
+    assert_eq!(2, params.len());
+    assert!(params.iter().any(|(k, v)| k == "status" && v == "COMPLETED"));
+    Ok(())
+  }
+}
@@ -6,8 +6,9 @@ use serde::Deserialize;
 use serde_derive::Serialize;
 use std::borrow::Borrow;
 
+pub use crate::types::fee::FeeInfo;
 use crate::types::page::BasePageParams;
-use crate::types::{deserialize_epoch_time, deserialize_option_empty_object};
+use crate::types::{deserialize_epoch_time, deserialize_option_empty_object, FeeLevel};
 
 #[allow(clippy::upper_case_acronyms)]
 #[allow(non_camel_case_types)]
@@ -31,7 +32,7 @@ pub enum PeerType {
 }
 
 #[allow(non_camel_case_types)]
-#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, Eq)]
 #[allow(clippy::upper_case_acronyms)]
 #[cfg_attr(feature = "sql", derive(sqlx::Type))]
 #[cfg_attr(feature = "sql", sqlx(type_name = "transaction_operation_type", rename_all = "lowercase"))]
@@ -74,6 +75,22 @@ pub enum TransactionStatus {
   UNKNOWN,
 }
 
+impl TransactionStatus {
+  /// The status will not change anymore
+  pub const fn is_final(&self) -> bool {
+    matches!(
+      self,
+      Self::COMPLETED
+        | Self::PARTIALLY_COMPLETED
+        | Self::CANCELLED
+        | Self::REJECTED
+        | Self::FAILED
+        | Self::TIMEOUT
+        | Self::BLOCKED
+    )
+  }
+}
+
 /// Search for transactions
 ///
 /// [getTransactions](https://docs.fireblocks.com/api/swagger-ui/#/Transactions/getTransactions)
@@ -199,14 +216,6 @@ pub struct BlockInfo {
   pub block_height: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
-#[serde(rename_all = "camelCase")]
-pub struct FeeInfo {
-  pub network_fee: Option<BigDecimal>,
-  pub service_fee: Option<BigDecimal>,
-  pub gas_price: Option<BigDecimal>,
-}
-
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct RewardInfo {
@@ -243,6 +252,17 @@ pub enum SigningAlgorithm {
   MPC_EDDSA_ED25519,
 }
 
+impl std::fmt::Display for SigningAlgorithm {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let algorithm = match self {
+      Self::MPC_ECDSA_SECP256K1 => "MPC_ECDSA_SECP256K1",
+      Self::MPC_ECDSA_SECP256R1 => "MPC_ECDSA_SECP256R1",
+      Self::MPC_EDDSA_ED25519 => "MPC_EDDSA_ED25519",
+    };
+    f.write_str(algorithm)
+  }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Signature {
@@ -252,13 +272,16 @@ pub struct Signature {
 #[derive(Debug, Deserialize, Serialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct SignedMessage {
+  /// The signed content of the matching [`UnsignedMessage`]
+  #[serde(default)]
+  pub content: String,
   pub derivation_path: Vec<u64>,
   pub algorithm: SigningAlgorithm,
   pub public_key: String,
   pub signature: Signature,
 }
 
-#[derive(Debug, Deserialize, Serialize, Default)]
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct OneTimeAddress {
   pub address: String,
@@ -266,7 +289,7 @@ pub struct OneTimeAddress {
   pub tag: Option<String>,
 }
 
-#[derive(Debug, Default, Deserialize, Serialize)]
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct DestinationTransferPeerPath {
   #[serde(rename = "type")]
@@ -294,6 +317,8 @@ pub struct Transaction {
   pub id: String,
   pub asset_id: Asset,
   pub status: TransactionStatus,
+  #[serde(default)]
+  pub operation: Option<TransactionOperation>,
   pub destination: Option<TransferPeerPath>,
   pub source: Option<TransferPeerPath>,
   pub amount: Option<BigDecimal>,
@@ -301,9 +326,9 @@ pub struct Transaction {
   #[serde(rename = "amountUSD")]
   pub amount_usd: Option<BigDecimal>,
   pub net_amount: Option<BigDecimal>,
-  #[serde(deserialize_with = "deserialize_epoch_time")]
+  #[serde(deserialize_with = "deserialize_epoch_time", serialize_with = "chrono::serde::ts_milliseconds::serialize")]
   pub created_at: DateTime<Utc>,
-  #[serde(deserialize_with = "deserialize_epoch_time")]
+  #[serde(deserialize_with = "deserialize_epoch_time", serialize_with = "chrono::serde::ts_milliseconds::serialize")]
   pub last_updated: DateTime<Utc>,
   #[serde(deserialize_with = "deserialize_option_empty_object", default)]
   pub tx_hash: Option<String>,
@@ -329,7 +354,9 @@ pub struct Transaction {
   pub exchange_tx_id: Option<String>,
   pub requested_amount: Option<BigDecimal>,
   pub service_fee: Option<BigDecimal>,
-  pub fee_currency: String,
+  pub fee_currency: Asset,
+  #[serde(default)]
+  pub fee_level: Option<FeeLevel>,
 
   // amlScreeningResult?: AmlScreeningResult;
   pub customer_ref_id: Option<String>,
@@ -350,6 +377,14 @@ pub struct Transaction {
   pub extra_parameters: Option<serde_json::Value>,
 }
 
+/// One of many destinations of a transaction (UTXO based assets only)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionRequestDestination {
+  pub amount: String,
+  pub destination: DestinationTransferPeerPath,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionArguments {
@@ -366,8 +401,17 @@ pub struct TransactionArguments {
   pub gas_price: Option<String>,
   #[serde(skip_serializing_if = "Option::is_none")]
   pub gas_limit: Option<String>,
+  /// Fee level used when `gas_price` is not set, Fireblocks defaults to [`FeeLevel::Medium`]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub fee_level: Option<FeeLevel>,
   #[serde(skip_serializing_if = "Option::is_none")]
   pub extra_parameters: Option<ExtraParameters>,
+  /// Used instead of `destination` to pay many destinations in a single transaction
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub destinations: Option<Vec<TransactionRequestDestination>>,
+  /// Unique id set by the caller. Fireblocks rejects a second transaction with the same id
+  #[serde(rename = "externalTxId", skip_serializing_if = "Option::is_none")]
+  pub external_tx_id: Option<String>,
   pub note: String,
 }
 
@@ -394,9 +438,13 @@ pub struct RawMessageData {
 #[serde(rename_all = "camelCase")]
 pub struct UnsignedMessage {
   pub content: String,
-  #[serde(rename = "type")]
+  /// Empty for raw messages
+  #[serde(rename = "type", default, skip_serializing_if = "String::is_empty")]
   pub message_type: String,
   pub index: i32,
+  /// Sign with the key at this path instead of the source vault account's, e.g. `[44, 0, 0, 0, 3]`
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub derivation_path: Option<Vec<u32>>,
 }
 
 #[derive(Debug, Deserialize, Default)]
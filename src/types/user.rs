@@ -0,0 +1,287 @@
+use serde_derive::{Deserialize, Serialize};
+
+/// Workspace role of a console or API user
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, Eq, Hash)]
+pub enum UserRole {
+  OWNER,
+  ADMIN,
+  NON_SIGNING_ADMIN,
+  SIGNER,
+  COLLATERALS_SIGNER,
+  APPROVER,
+  EDITOR,
+  #[default]
+  VIEWER,
+  AUDITOR,
+  NCW_ADMIN,
+  NCW_SIGNER,
+  #[serde(other)]
+  UNKNOWN,
+}
+
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, Eq)]
+pub enum UserStatus {
+  #[default]
+  PENDING_ACTIVATION,
+  ACTIVE,
+  PENDING_DEVICE_SETUP,
+  LOCKED,
+  #[serde(other)]
+  UNKNOWN,
+}
+
+/// A user of the Fireblocks console
+///
+/// See
+/// * [getConsoleUsers](https://developers.fireblocks.com/reference/getconsoleusers)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsoleUser {
+  pub id: String,
+  pub first_name: String,
+  pub last_name: String,
+  pub email: String,
+  pub role: UserRole,
+  pub enabled: bool,
+  #[serde(default)]
+  pub status: Option<UserStatus>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsoleUsers {
+  pub users: Vec<ConsoleUser>,
+}
+
+/// See
+/// * [createConsoleUser](https://developers.fireblocks.com/reference/createconsoleuser)
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateConsoleUser {
+  pub first_name: String,
+  pub last_name: String,
+  pub email: String,
+  pub role: UserRole,
+}
+
+/// A user authenticating with an API key, e.g. a service account
+///
+/// See
+/// * [getApiUsers](https://developers.fireblocks.com/reference/getapiusers)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiUser {
+  pub id: String,
+  pub name: String,
+  pub role: UserRole,
+  pub enabled: bool,
+  #[serde(default)]
+  pub status: Option<UserStatus>,
+}
+
+impl ApiUser {
+  /// The user is awaiting workspace approval or co-signer pairing
+  pub fn is_pending(&self) -> bool {
+    self.status.as_ref().is_some_and(|s| *s != UserStatus::ACTIVE)
+  }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiUsers {
+  pub users: Vec<ApiUser>,
+}
+
+/// Where the co-signer of a new API user runs
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, Eq)]
+pub enum CoSignerSetup {
+  #[default]
+  SGX_MACHINE,
+  CLOUD_COSIGNER,
+}
+
+/// See
+/// * [createApiUser](https://developers.fireblocks.com/reference/createapiuser)
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateApiUser {
+  pub name: String,
+  pub role: UserRole,
+  /// CSR of the API key, the private key never leaves the caller
+  pub csr_pem: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub co_signer_setup: Option<CoSignerSetup>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub co_signer_setup_is_first_user: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct WhitelistedIps {
+  pub user_id: String,
+  #[serde(default, alias = "whitelistedIps")]
+  pub whitelist_ips: Vec<String>,
+}
+
+/// See
+/// * [getCosigners](https://developers.fireblocks.com/reference/getcosigners)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Cosigner {
+  pub id: String,
+  pub name: Option<String>,
+  #[serde(default)]
+  pub archived: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Cosigners {
+  pub data: Vec<Cosigner>,
+  pub next: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CallbackHandler {
+  pub url: String,
+  pub public_key: Option<String>,
+}
+
+/// An API key paired (or pending pairing) with a co-signer
+///
+/// See
+/// * [getApiKey](https://developers.fireblocks.com/reference/getapikey)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CosignerApiKey {
+  pub id: String,
+  /// Unset until the co-signer has completed pairing
+  pub last_seen: Option<String>,
+  pub callback_handler: Option<CallbackHandler>,
+}
+
+impl CosignerApiKey {
+  pub const fn is_paired(&self) -> bool {
+    self.last_seen.is_some()
+  }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CosignerApiKeys {
+  pub data: Vec<CosignerApiKey>,
+  pub next: Option<String>,
+}
+
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, Eq)]
+pub enum UserGroupStatus {
+  #[default]
+  PENDING_APPROVAL,
+  ACTIVE,
+  PENDING_DELETE,
+  #[serde(other)]
+  UNKNOWN,
+}
+
+/// A group of console users, usable as approvers in the policy
+///
+/// See
+/// * [getUserGroup](https://developers.fireblocks.com/reference/getusergroup)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct UserGroup {
+  pub id: String,
+  pub name: String,
+  #[serde(default)]
+  pub members_ids: Vec<String>,
+  #[serde(default)]
+  pub status: Option<UserGroupStatus>,
+}
+
+/// Request body of both group creation and update. An update replaces the name and the whole member list
+///
+/// See
+/// * [createUserGroup](https://developers.fireblocks.com/reference/createusergroup)
+/// * [updateUserGroup](https://developers.fireblocks.com/reference/updateusergroup)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct UserGroupRequest {
+  pub group_name: String,
+  pub member_ids: Vec<String>,
+}
+
+#[cfg(test)]
+mod test {
+  use crate::types::user::{ApiUsers, ConsoleUsers, CosignerApiKeys, UserGroup, UserGroupStatus, UserRole, UserStatus};
+
+  #[test]
+  fn test_json_console_users() -> color_eyre::Result<()> {
+    let data = r#"
+    {
+      "users": [
+        {
+          "id": "5d9f6a8e-1f0b-4a1e-8c2d-7e3b4a5c6d7e",
+          "firstName": "Jane",
+          "lastName": "Doe",
+          "email": "jane@example.com",
+          "role": "SIGNER",
+          "enabled": true,
+          "status": "ACTIVE"
+        },
+        {
+          "id": "6e0a7b9f-2a1c-4b2f-9d3e-8f4c5b6d7e8f",
+          "firstName": "John",
+          "lastName": "Doe",
+          "email": "john@example.com",
+          "role": "SOME_FUTURE_ROLE",
+          "enabled": false
+        }
+      ]
+    }
+    "#;
+    let users: ConsoleUsers = serde_json::from_str(data)?;
+    assert_eq!(UserRole::SIGNER, users.users[0].role);
+    assert_eq!(Some(UserStatus::ACTIVE), users.users[0].status);
+    assert_eq!(UserRole::UNKNOWN, users.users[1].role);
+    assert!(users.users[1].status.is_none());
+    Ok(())
+  }
+
+  #[test]
+  fn test_json_api_users() -> color_eyre::Result<()> {
+    let data = r#"
+    {
+      "users": [
+        {"id": "b1c2d3e4-f5a6-4b7c-8d9e-0f1a2b3c4d5e", "name": "treasury-bot", "role": "SIGNER", "enabled": true, "status": "PENDING_DEVICE_SETUP"},
+        {"id": "c2d3e4f5-a6b7-4c8d-9e0f-1a2b3c4d5e6f", "name": "reporting", "role": "VIEWER", "enabled": true}
+      ]
+    }
+    "#;
+    let users: ApiUsers = serde_json::from_str(data)?;
+    assert!(users.users[0].is_pending());
+    assert!(!users.users[1].is_pending());
+
+    let data = r#"{"data": [{"id": "b1c2d3e4-f5a6-4b7c-8d9e-0f1a2b3c4d5e", "callbackHandler": {"url": "https://cb.example.com"}}], "next": null}"#;
+    let keys: CosignerApiKeys = serde_json::from_str(data)?;
+    assert!(!keys.data[0].is_paired());
+    Ok(())
+  }
+
+  #[test]
+  fn test_json_user_group() -> color_eyre::Result<()> {
+    let data = r#"{"id": "7f8e9d0c-1b2a-4c3d-8e4f-5a6b7c8d9e0f", "name": "approvers", "membersIds": ["u1", "u2"], "status": "ACTIVE"}"#;
+    let group: UserGroup = serde_json::from_str(data)?;
+    assert_eq!(2, group.members_ids.len());
+    assert_eq!(Some(UserGroupStatus::ACTIVE), group.status);
+    Ok(())
+  }
+}
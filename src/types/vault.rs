@@ -1,6 +1,6 @@
 use serde_derive::{Deserialize, Serialize};
 
-use crate::types::{asset::AccountAsset, deserialize_str_i32, Paging};
+use crate::types::{asset::AccountAsset, deserialize_str_i32, Paging, SigningAlgorithm};
 
 #[derive(Debug, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -41,3 +41,13 @@ pub struct VaultRenameResponse {
   #[serde(deserialize_with = "deserialize_str_i32")]
   pub id: i32,
 }
+
+/// A public key of the workspace and the path it is derived at
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicKeyInfo {
+  pub algorithm: SigningAlgorithm,
+  pub derivation_path: Vec<u32>,
+  /// Hex encoded
+  pub public_key: String,
+}
@@ -0,0 +1,11 @@
+use serde_derive::{Deserialize, Serialize};
+
+/// Whether transfers to one-time (non whitelisted) addresses are allowed
+///
+/// See
+/// * [getOtaStatus](https://developers.fireblocks.com/reference/getotastatus)
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct OtaStatus {
+  pub enabled: bool,
+}
@@ -0,0 +1,103 @@
+//! Verified webhook extraction for [actix-web](https://docs.rs/actix-web)
+//!
+//! ```
+//! use actix_web::{web, App};
+//! use fireblocks_sdk::webhook::{actix::FireblocksWebhook, WebhookEvent, WebhookVerifier};
+//!
+//! async fn hook(FireblocksWebhook(msg): FireblocksWebhook) -> &'static str {
+//!   if let WebhookEvent::TRANSACTION_STATUS_UPDATED(tx) = msg.event {
+//!     println!("{} is {:?}", tx.id, tx.status);
+//!   }
+//!   "ok"
+//! }
+//!
+//! fn configure(verifier: WebhookVerifier, cfg: &mut web::ServiceConfig) {
+//!   cfg.app_data(web::Data::new(verifier)).route("/fireblocks", web::post().to(hook));
+//! }
+//! ```
+use crate::error::WebhookError;
+use crate::webhook::{WebhookMessage, WebhookVerifier, SIGNATURE_HEADER};
+use actix_web::dev::Payload;
+use actix_web::http::StatusCode;
+use actix_web::web::{Bytes, Data};
+use actix_web::{FromRequest, HttpRequest, ResponseError};
+use futures::future::LocalBoxFuture;
+use thiserror::Error;
+
+/// Extracts a [`WebhookMessage`] whose signature was checked with the
+/// `web::Data<WebhookVerifier>` registered as app data
+#[derive(Debug, Clone)]
+pub struct FireblocksWebhook(pub WebhookMessage);
+
+#[derive(Debug, Error)]
+pub enum WebhookRejection {
+  #[error("no WebhookVerifier registered as app data")]
+  NoVerifier,
+
+  #[error(transparent)]
+  Body(actix_web::Error),
+
+  #[error(transparent)]
+  Webhook(#[from] WebhookError),
+}
+
+impl ResponseError for WebhookRejection {
+  fn status_code(&self) -> StatusCode {
+    match self {
+      Self::Body(e) => e.as_response_error().status_code(),
+      Self::Webhook(WebhookError::MissingSignature | WebhookError::InvalidSignature) => StatusCode::UNAUTHORIZED,
+      Self::Webhook(WebhookError::Payload(_)) => StatusCode::BAD_REQUEST,
+      Self::NoVerifier | Self::Webhook(WebhookError::Key(_)) => StatusCode::INTERNAL_SERVER_ERROR,
+      #[cfg(feature = "reqwest")]
+      Self::Webhook(WebhookError::Http(_)) => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+  }
+}
+
+impl FromRequest for FireblocksWebhook {
+  type Error = WebhookRejection;
+  type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+  fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+    let verifier = req.app_data::<Data<WebhookVerifier>>().cloned();
+    let signature = req.headers().get(SIGNATURE_HEADER).and_then(|v| v.to_str().ok()).map(str::to_owned);
+    let body = Bytes::from_request(req, payload);
+    Box::pin(async move {
+      let verifier = verifier.ok_or(WebhookRejection::NoVerifier)?;
+      let body = body.await.map_err(WebhookRejection::Body)?;
+      Ok(Self(verifier.parse(&body, signature.as_deref())?))
+    })
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::FireblocksWebhook;
+  use crate::webhook::verify::test::{sign, BODY, PUBLIC_KEY};
+  use crate::webhook::{WebhookVerifier, SIGNATURE_HEADER};
+  use actix_web::http::StatusCode;
+  use actix_web::test::TestRequest;
+  use actix_web::web::Data;
+  use actix_web::{FromRequest, ResponseError};
+
+  #[tokio::test]
+  async fn test_extract() -> color_eyre::Result<()> {
+    let verifier = Data::new(WebhookVerifier::new(PUBLIC_KEY)?);
+    let (req, mut payload) = TestRequest::post()
+      .app_data(verifier.clone())
+      .insert_header((SIGNATURE_HEADER, sign(BODY)?))
+      .set_payload(BODY)
+      .to_http_parts();
+    FireblocksWebhook::from_request(&req, &mut payload).await.map_err(|e| color_eyre::eyre::format_err!("{e}"))?;
+
+    let (req, mut payload) = TestRequest::post().app_data(verifier).set_payload(BODY).to_http_parts();
+    let rejection = FireblocksWebhook::from_request(&req, &mut payload).await.err();
+    assert_eq!(Some(StatusCode::UNAUTHORIZED), rejection.map(|r| r.status_code()));
+
+    let (req, mut payload) =
+      TestRequest::post().insert_header((SIGNATURE_HEADER, sign(BODY)?)).set_payload(BODY).to_http_parts();
+    let rejection = FireblocksWebhook::from_request(&req, &mut payload).await.err();
+    assert_eq!(Some(StatusCode::INTERNAL_SERVER_ERROR), rejection.map(|r| r.status_code()));
+    Ok(())
+  }
+}
@@ -0,0 +1,95 @@
+//! Verified webhook extraction for [axum](https://docs.rs/axum)
+//!
+//! ```
+//! use axum::{routing::post, Router};
+//! use fireblocks_sdk::webhook::{axum::FireblocksWebhook, WebhookEvent, WebhookVerifier};
+//!
+//! async fn hook(FireblocksWebhook(msg): FireblocksWebhook) {
+//!   if let WebhookEvent::TRANSACTION_STATUS_UPDATED(tx) = msg.event {
+//!     println!("{} is {:?}", tx.id, tx.status);
+//!   }
+//! }
+//!
+//! fn router(verifier: WebhookVerifier) -> Router {
+//!   Router::new().route("/fireblocks", post(hook)).with_state(verifier)
+//! }
+//! ```
+use crate::error::WebhookError;
+use crate::webhook::{WebhookMessage, WebhookVerifier, SIGNATURE_HEADER};
+use axum::body::Bytes;
+use axum::extract::rejection::BytesRejection;
+use axum::extract::{FromRef, FromRequest, Request};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+
+/// Extracts a [`WebhookMessage`] whose signature was checked with the [`WebhookVerifier`]
+/// of the router state
+#[derive(Debug, Clone)]
+pub struct FireblocksWebhook(pub WebhookMessage);
+
+#[derive(Debug)]
+pub enum WebhookRejection {
+  Body(BytesRejection),
+  Webhook(WebhookError),
+}
+
+impl IntoResponse for WebhookRejection {
+  fn into_response(self) -> Response {
+    match self {
+      Self::Body(e) => e.into_response(),
+      Self::Webhook(e) => {
+        let status = match e {
+          WebhookError::MissingSignature | WebhookError::InvalidSignature => StatusCode::UNAUTHORIZED,
+          WebhookError::Payload(_) => StatusCode::BAD_REQUEST,
+          WebhookError::Key(_) => StatusCode::INTERNAL_SERVER_ERROR,
+          #[cfg(feature = "reqwest")]
+          WebhookError::Http(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        (status, e.to_string()).into_response()
+      },
+    }
+  }
+}
+
+impl<S> FromRequest<S> for FireblocksWebhook
+where
+  S: Send + Sync,
+  WebhookVerifier: FromRef<S>,
+{
+  type Rejection = WebhookRejection;
+
+  async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+    let verifier = WebhookVerifier::from_ref(state);
+    let signature = req.headers().get(SIGNATURE_HEADER).and_then(|v| v.to_str().ok()).map(str::to_owned);
+    let body = Bytes::from_request(req, state).await.map_err(WebhookRejection::Body)?;
+    verifier.parse(&body, signature.as_deref()).map(Self).map_err(WebhookRejection::Webhook)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::FireblocksWebhook;
+  use crate::webhook::verify::test::{sign, BODY, PUBLIC_KEY};
+  use crate::webhook::{WebhookVerifier, SIGNATURE_HEADER};
+  use axum::body::Body;
+  use axum::extract::{FromRequest, Request};
+  use axum::http::StatusCode;
+  use axum::response::IntoResponse;
+
+  #[tokio::test]
+  async fn test_extract() -> color_eyre::Result<()> {
+    let verifier = WebhookVerifier::new(PUBLIC_KEY)?;
+    let req = Request::builder().header(SIGNATURE_HEADER, sign(BODY)?).body(Body::from(BODY))?;
+    FireblocksWebhook::from_request(req, &verifier).await.map_err(|e| color_eyre::eyre::format_err!("{e:?}"))?;
+
+    let req = Request::builder().body(Body::from(BODY))?;
+    let rejection = FireblocksWebhook::from_request(req, &verifier).await.err();
+    assert_eq!(Some(StatusCode::UNAUTHORIZED), rejection.map(|r| r.into_response().status()));
+
+    let body = b"{}";
+    let req = Request::builder().header(SIGNATURE_HEADER, sign(body)?).body(Body::from(&body[..]))?;
+    let rejection = FireblocksWebhook::from_request(req, &verifier).await.err();
+    assert_eq!(Some(StatusCode::BAD_REQUEST), rejection.map(|r| r.into_response().status()));
+    Ok(())
+  }
+}
@@ -0,0 +1,260 @@
+use crate::error::StoreError;
+use crate::webhook::{WebhookEvent, WebhookMessage};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Remembers which notifications were already handled
+pub trait DedupStore: Send + Sync {
+  fn contains(&self, key: &str) -> impl Future<Output = Result<bool, StoreError>> + Send;
+  /// Record `key`, returning `false` if it was already present
+  fn insert(&self, key: &str) -> impl Future<Output = Result<bool, StoreError>> + Send;
+  fn remove(&self, key: &str) -> impl Future<Output = Result<(), StoreError>> + Send;
+}
+
+/// In-memory [`DedupStore`] keeping at most `capacity` keys for at most `ttl`, evicting the
+/// least recently used keys first
+#[derive(Debug)]
+pub struct MemoryDedupStore {
+  capacity: usize,
+  ttl: Duration,
+  inner: Mutex<Lru>,
+}
+
+#[derive(Debug, Default)]
+struct Lru {
+  seq: u64,
+  /// key -> (inserted at, last use)
+  keys: HashMap<String, (Instant, u64)>,
+  by_use: BTreeMap<u64, String>,
+}
+
+impl Lru {
+  fn touch(&mut self, key: &str) {
+    self.seq += 1;
+    if let Some((_, used)) = self.keys.get_mut(key) {
+      self.by_use.remove(used);
+      *used = self.seq;
+      self.by_use.insert(self.seq, key.to_owned());
+    }
+  }
+
+  fn remove(&mut self, key: &str) {
+    if let Some((_, used)) = self.keys.remove(key) {
+      self.by_use.remove(&used);
+    }
+  }
+}
+
+impl MemoryDedupStore {
+  pub fn new(capacity: usize, ttl: Duration) -> Self {
+    Self { capacity: capacity.max(1), ttl, inner: Mutex::new(Lru::default()) }
+  }
+
+  fn live(&self, lru: &mut Lru, key: &str) -> bool {
+    match lru.keys.get(key) {
+      Some((at, _)) if at.elapsed() < self.ttl => {
+        lru.touch(key);
+        true
+      },
+      Some(_) => {
+        lru.remove(key);
+        false
+      },
+      None => false,
+    }
+  }
+}
+
+impl MemoryDedupStore {
+  fn insert_key(&self, lru: &mut Lru, key: &str) -> bool {
+    if self.live(lru, key) {
+      return false;
+    }
+    while lru.keys.len() >= self.capacity {
+      let Some((_, oldest)) = lru.by_use.pop_first() else { break };
+      lru.keys.remove(&oldest);
+    }
+    lru.seq += 1;
+    lru.keys.insert(key.to_owned(), (Instant::now(), lru.seq));
+    lru.by_use.insert(lru.seq, key.to_owned());
+    true
+  }
+}
+
+impl Default for MemoryDedupStore {
+  /// 100k keys for a day, longer than Fireblocks retries a notification
+  fn default() -> Self {
+    Self::new(100_000, Duration::from_hours(24))
+  }
+}
+
+impl DedupStore for MemoryDedupStore {
+  async fn contains(&self, key: &str) -> Result<bool, StoreError> {
+    let mut lru = self.inner.lock().map_err(|e| StoreError(e.to_string()))?;
+    Ok(self.live(&mut lru, key))
+  }
+
+  async fn insert(&self, key: &str) -> Result<bool, StoreError> {
+    let mut lru = self.inner.lock().map_err(|e| StoreError(e.to_string()))?;
+    Ok(self.insert_key(&mut lru, key))
+  }
+
+  async fn remove(&self, key: &str) -> Result<(), StoreError> {
+    self.inner.lock().map_err(|e| StoreError(e.to_string()))?.remove(key);
+    Ok(())
+  }
+}
+
+/// Drops webhook notifications that were already handled. Fireblocks delivers at least once
+/// and may resend or reorder transaction updates, so
+/// * a notification is identified by its type and resource, plus the status for transactions
+/// * once a transaction reached a final status, older status updates for it are dropped
+///
+/// [`WebhookDeduplicator::handle`] only keeps a notification as handled when its handler succeeds, so a
+/// failed one is handled again when Fireblocks resends it.
+#[derive(Debug, Default)]
+pub struct WebhookDeduplicator<S = MemoryDedupStore> {
+  store: S,
+}
+
+impl<S: DedupStore> WebhookDeduplicator<S> {
+  pub const fn new(store: S) -> Self {
+    Self { store }
+  }
+
+  /// Run `handler` if `msg` is new, returning `false` for duplicates. When the handler fails the notification
+  /// is forgotten and its error returned
+  pub async fn handle<F, Fut, E>(&self, msg: WebhookMessage, handler: F) -> Result<bool, E>
+  where
+    F: FnOnce(WebhookMessage) -> Fut,
+    Fut: Future<Output = Result<(), E>>,
+    E: From<StoreError>,
+  {
+    if !self.check(&msg).await? {
+      return Ok(false);
+    }
+    let forget = msg.clone();
+    if let Err(e) = handler(msg).await {
+      if let Err(store) = self.forget(&forget).await {
+        tracing::warn!("failed to forget webhook {}: {store}", Self::key(&forget));
+      }
+      return Err(e);
+    }
+    Ok(true)
+  }
+
+  /// Returns `true` if `msg` is new and should be handled. It is recorded as handled right away, so concurrent
+  /// deliveries are not handled twice: [`WebhookDeduplicator::forget`] it if handling fails
+  pub async fn check(&self, msg: &WebhookMessage) -> Result<bool, StoreError> {
+    if let WebhookEvent::TRANSACTION_STATUS_UPDATED(tx) = &msg.event {
+      let final_key = format!("final:{}", tx.id);
+      if !tx.status.is_final() && self.store.contains(&final_key).await? {
+        return Ok(false);
+      }
+      let new = self.store.insert(&Self::key(msg)).await?;
+      if new && tx.status.is_final() {
+        self.store.insert(&final_key).await?;
+      }
+      return Ok(new);
+    }
+    self.store.insert(&Self::key(msg)).await
+  }
+
+  /// Undo [`WebhookDeduplicator::check`] returning `true` for `msg`
+  pub async fn forget(&self, msg: &WebhookMessage) -> Result<(), StoreError> {
+    if let WebhookEvent::TRANSACTION_STATUS_UPDATED(tx) = &msg.event {
+      if tx.status.is_final() {
+        self.store.remove(&format!("final:{}", tx.id)).await?;
+      }
+    }
+    self.store.remove(&Self::key(msg)).await
+  }
+
+  /// Identity of a notification, see [`WebhookDeduplicator`]
+  pub fn key(msg: &WebhookMessage) -> String {
+    let event_type = msg.event.event_type();
+    match &msg.event {
+      WebhookEvent::TRANSACTION_CREATED(tx) => format!("{event_type}:{}", tx.id),
+      WebhookEvent::TRANSACTION_STATUS_UPDATED(tx) | WebhookEvent::TRANSACTION_APPROVAL_STATUS_UPDATED(tx) => {
+        format!("{event_type}:{}:{:?}:{}", tx.id, tx.status, tx.sub_status.as_deref().unwrap_or_default())
+      },
+      // unknown events cannot be serialized, their data can
+      WebhookEvent::UNKNOWN(_, data) => format!("{event_type}:{}:{}", msg.timestamp.unwrap_or_default(), digest(data)),
+      event => format!("{event_type}:{}:{}", msg.timestamp.unwrap_or_default(), digest(event)),
+    }
+  }
+}
+
+#[allow(clippy::format_collect)]
+fn digest<T: Serialize>(value: &T) -> String {
+  let body = serde_json::to_vec(value).unwrap_or_default();
+  Sha256::digest(body).iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod test {
+  use super::{DedupStore, MemoryDedupStore, WebhookDeduplicator};
+  use crate::error::StoreError;
+  use crate::types::{Transaction, TransactionStatus};
+  use crate::webhook::{WebhookEvent, WebhookMessage};
+  use std::time::Duration;
+
+  fn update(status: TransactionStatus) -> WebhookMessage {
+    let tx = Transaction { id: "tx1".to_owned(), status, ..Default::default() };
+    WebhookMessage { tenant_id: None, timestamp: None, event: WebhookEvent::TRANSACTION_STATUS_UPDATED(tx) }
+  }
+
+  #[tokio::test]
+  async fn test_dedup() -> color_eyre::Result<()> {
+    let dedup = WebhookDeduplicator::new(MemoryDedupStore::default());
+    assert!(dedup.check(&update(TransactionStatus::BROADCASTING)).await?);
+    assert!(!dedup.check(&update(TransactionStatus::BROADCASTING)).await?);
+    assert!(dedup.check(&update(TransactionStatus::COMPLETED)).await?);
+    assert!(!dedup.check(&update(TransactionStatus::CONFIRMING)).await?);
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn test_handler_failure() -> color_eyre::Result<()> {
+    let dedup = WebhookDeduplicator::new(MemoryDedupStore::default());
+    let failed = dedup.handle(update(TransactionStatus::COMPLETED), |_| async { Err(StoreError("down".into())) }).await;
+    assert!(failed.is_err());
+    assert!(dedup.check(&update(TransactionStatus::CONFIRMING)).await?);
+    let ok = |_| async { Ok::<_, StoreError>(()) };
+    assert!(dedup.handle(update(TransactionStatus::COMPLETED), ok).await?);
+    assert!(!dedup.handle(update(TransactionStatus::COMPLETED), ok).await?);
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn test_lru() -> color_eyre::Result<()> {
+    let store = MemoryDedupStore::new(2, Duration::from_mins(1));
+    assert!(store.insert("a").await?);
+    assert!(store.insert("b").await?);
+    assert!(store.contains("a").await?);
+    assert!(store.insert("c").await?);
+    assert!(!store.contains("b").await?);
+    assert!(store.contains("a").await?);
+
+    let store = MemoryDedupStore::new(2, Duration::ZERO);
+    assert!(store.insert("a").await?);
+    assert!(store.insert("a").await?);
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn test_unknown_events() -> color_eyre::Result<()> {
+    let unknown = |id: &str| -> color_eyre::Result<WebhookMessage> {
+      Ok(serde_json::from_str(&format!(r#"{{"type": "SOMETHING_NEW", "data": {{"id": "{id}"}}}}"#))?)
+    };
+    let dedup = WebhookDeduplicator::new(MemoryDedupStore::default());
+    assert!(dedup.check(&unknown("1")?).await?);
+    assert!(dedup.check(&unknown("2")?).await?);
+    assert!(!dedup.check(&unknown("1")?).await?);
+    Ok(())
+  }
+}
@@ -0,0 +1,206 @@
+use crate::types::Transaction;
+use crate::webhook::{VaultBalanceUpdate, WebhookEvent, WebhookMessage};
+use futures::future::{join_all, BoxFuture};
+use futures::FutureExt;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+/// Error returned by a webhook handler
+pub type HandlerError = Box<dyn std::error::Error + Send + Sync>;
+
+type Handler = Arc<dyn Fn(WebhookMessage) -> BoxFuture<'static, Result<(), HandlerError>> + Send + Sync>;
+type ErrorHandler = Arc<dyn Fn(&str, &HandlerError) + Send + Sync>;
+
+/// Routes webhook notifications to async handlers registered per event type
+///
+/// ```
+/// use fireblocks_sdk::webhook::{HandlerError, WebhookDispatcher, WebhookMessage};
+///
+/// # async fn run(msg: WebhookMessage) {
+/// let dispatcher = WebhookDispatcher::new(8)
+///   .on_transaction_status(|tx| async move {
+///     println!("{} is {:?}", tx.id, tx.status);
+///     Ok::<_, HandlerError>(())
+///   })
+///   .on_error(|event_type, e| eprintln!("{event_type} handler failed: {e}"));
+/// dispatcher.dispatch(msg).await;
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct WebhookDispatcher {
+  /// Handlers by event type, `None` matches every event
+  handlers: Vec<(Option<String>, Handler)>,
+  permits: Arc<Semaphore>,
+  on_error: Option<ErrorHandler>,
+}
+
+impl std::fmt::Debug for WebhookDispatcher {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("WebhookDispatcher")
+      .field("handlers", &self.handlers.iter().map(|(t, _)| t).collect::<Vec<_>>())
+      .field("available_permits", &self.permits.available_permits())
+      .finish_non_exhaustive()
+  }
+}
+
+impl WebhookDispatcher {
+  /// At most `max_concurrent` handlers run at once, across all dispatched notifications
+  pub fn new(max_concurrent: usize) -> Self {
+    Self { handlers: Vec::new(), permits: Arc::new(Semaphore::new(max_concurrent.max(1))), on_error: None }
+  }
+
+  /// Handle notifications of `event_type`, e.g. `"EXTERNAL_WALLET_ASSET_ADDED"`
+  pub fn on<F, Fut>(mut self, event_type: &str, handler: F) -> Self
+  where
+    F: Fn(WebhookMessage) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), HandlerError>> + Send + 'static,
+  {
+    self.handlers.push((Some(String::from(event_type)), Arc::new(move |msg| handler(msg).boxed())));
+    self
+  }
+
+  /// Handle every notification, including unknown event types
+  pub fn on_any<F, Fut>(mut self, handler: F) -> Self
+  where
+    F: Fn(WebhookMessage) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), HandlerError>> + Send + 'static,
+  {
+    self.handlers.push((None, Arc::new(move |msg| handler(msg).boxed())));
+    self
+  }
+
+  pub fn on_transaction_created<F, Fut>(self, handler: F) -> Self
+  where
+    F: Fn(Transaction) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), HandlerError>> + Send + 'static,
+  {
+    self.on("TRANSACTION_CREATED", move |msg| {
+      let fut = match msg.event {
+        WebhookEvent::TRANSACTION_CREATED(tx) => Some(handler(tx)),
+        _ => None,
+      };
+      run_some(fut)
+    })
+  }
+
+  pub fn on_transaction_status<F, Fut>(self, handler: F) -> Self
+  where
+    F: Fn(Transaction) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), HandlerError>> + Send + 'static,
+  {
+    self.on("TRANSACTION_STATUS_UPDATED", move |msg| {
+      let fut = match msg.event {
+        WebhookEvent::TRANSACTION_STATUS_UPDATED(tx) => Some(handler(tx)),
+        _ => None,
+      };
+      run_some(fut)
+    })
+  }
+
+  pub fn on_vault_balance<F, Fut>(self, handler: F) -> Self
+  where
+    F: Fn(VaultBalanceUpdate) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), HandlerError>> + Send + 'static,
+  {
+    self.on("VAULT_BALANCE_UPDATE", move |msg| {
+      let fut = match msg.event {
+        WebhookEvent::VAULT_BALANCE_UPDATE(b) => Some(handler(b)),
+        _ => None,
+      };
+      run_some(fut)
+    })
+  }
+
+  /// Called with the event type and error of each failed handler. Without it failures are
+  /// logged with `tracing`
+  pub fn on_error<F>(mut self, f: F) -> Self
+  where
+    F: Fn(&str, &HandlerError) + Send + Sync + 'static,
+  {
+    self.on_error = Some(Arc::new(f));
+    self
+  }
+
+  /// Run the handlers of the notification concurrently, returning the errors of those that failed
+  pub async fn dispatch(&self, msg: WebhookMessage) -> Vec<HandlerError> {
+    let event_type = msg.event.event_type().to_owned();
+    let runs = self.handlers.iter().filter(|(t, _)| t.as_ref().is_none_or(|t| *t == event_type)).map(|(_, h)| {
+      let fut = h(msg.clone());
+      let permits = Arc::clone(&self.permits);
+      async move {
+        // the semaphore is never closed
+        let _permit = permits.acquire().await.ok();
+        fut.await
+      }
+    });
+    let errors: Vec<HandlerError> = join_all(runs).await.into_iter().filter_map(Result::err).collect();
+    for e in &errors {
+      if let Some(f) = &self.on_error {
+        f(&event_type, e);
+      } else {
+        tracing::warn!("webhook handler for {event_type} failed: {e}");
+      }
+    }
+    errors
+  }
+}
+
+async fn run_some<Fut>(fut: Option<Fut>) -> Result<(), HandlerError>
+where
+  Fut: Future<Output = Result<(), HandlerError>>,
+{
+  match fut {
+    Some(fut) => fut.await,
+    None => Ok(()),
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::{HandlerError, WebhookDispatcher};
+  use crate::webhook::WebhookMessage;
+  use std::sync::atomic::{AtomicUsize, Ordering};
+  use std::sync::Arc;
+
+  #[tokio::test]
+  async fn test_dispatch() -> color_eyre::Result<()> {
+    let balances = Arc::new(AtomicUsize::new(0));
+    let all = Arc::new(AtomicUsize::new(0));
+    let reported = Arc::new(AtomicUsize::new(0));
+    let (b, a, r) = (Arc::clone(&balances), Arc::clone(&all), Arc::clone(&reported));
+    let dispatcher = WebhookDispatcher::new(2)
+      .on_vault_balance(move |update| {
+        let b = Arc::clone(&b);
+        async move {
+          assert_eq!(3, update.vault_account_id);
+          b.fetch_add(1, Ordering::SeqCst);
+          Ok(())
+        }
+      })
+      .on_any(move |_| {
+        let a = Arc::clone(&a);
+        async move {
+          a.fetch_add(1, Ordering::SeqCst);
+          Ok(())
+        }
+      })
+      .on("EXTERNAL_WALLET_ASSET_ADDED", |_| async { Err::<(), HandlerError>("boom".into()) })
+      .on_error(move |t, _| {
+        assert_eq!("EXTERNAL_WALLET_ASSET_ADDED", t);
+        r.fetch_add(1, Ordering::SeqCst);
+      });
+
+    let msg: WebhookMessage =
+      serde_json::from_str(r#"{"type": "VAULT_BALANCE_UPDATE", "data": {"accountId": "3", "assetId": "BTC"}}"#)?;
+    assert!(dispatcher.dispatch(msg).await.is_empty());
+    let msg: WebhookMessage =
+      serde_json::from_str(r#"{"type": "EXTERNAL_WALLET_ASSET_ADDED", "data": {"walletId": "w", "assetId": "BTC"}}"#)?;
+    assert_eq!(1, dispatcher.dispatch(msg).await.len());
+
+    assert_eq!(1, balances.load(Ordering::SeqCst));
+    assert_eq!(2, all.load(Ordering::SeqCst));
+    assert_eq!(1, reported.load(Ordering::SeqCst));
+    Ok(())
+  }
+}
@@ -0,0 +1,223 @@
+use crate::error::WebhookError;
+use crate::types::{Transaction, TransactionStatus};
+use crate::webhook::{
+  AccountAdded, EmbeddedWalletEvent, NetworkConnectionAdded, VaultAccountAdded, VaultAssetAdded, VaultBalanceUpdate,
+  WalletAssetEvent, WebhookEvent, WebhookMessage, SIGNATURE_HEADER,
+};
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use base64::Engine;
+use jsonwebtoken::{Algorithm, EncodingKey};
+use reqwest::StatusCode;
+use serde_json::Value;
+use url::Url;
+
+/// Public half of [`TEST_PRIVATE_KEY`], for a [`crate::webhook::WebhookVerifier`] under test
+pub const TEST_PUBLIC_KEY: &[u8] = include_bytes!("testdata/webhook_public.pem");
+/// RSA key for signing synthetic notifications. Never use outside of tests
+pub const TEST_PRIVATE_KEY: &[u8] = include_bytes!("testdata/webhook_private.pem");
+
+/// How the [`SIGNATURE_HEADER`] of an emitted notification is set
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Signature {
+  Valid,
+  /// A well-formed signature of a different body
+  Invalid,
+  Missing,
+}
+
+/// Signs and posts synthetic webhook notifications, to integration test webhook handlers without a
+/// workspace
+///
+/// ```
+/// use fireblocks_sdk::webhook::{Signature, WebhookEmitter};
+///
+/// async fn emit() -> color_eyre::Result<()> {
+///   let emitter = WebhookEmitter::with_test_key("http://localhost:3000/webhook".parse()?)?;
+///   for (event_type, status) in emitter.emit_all(Signature::Valid).await? {
+///     assert!(status.is_success(), "{event_type} rejected");
+///   }
+///   Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct WebhookEmitter {
+  client: reqwest::Client,
+  url: Url,
+  key: EncodingKey,
+}
+
+impl std::fmt::Debug for WebhookEmitter {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("WebhookEmitter").field("url", &self.url).finish_non_exhaustive()
+  }
+}
+
+impl WebhookEmitter {
+  pub fn new(url: Url, private_key_pem: &[u8]) -> Result<Self, WebhookError> {
+    Ok(Self { client: reqwest::Client::new(), url, key: EncodingKey::from_rsa_pem(private_key_pem)? })
+  }
+
+  /// Sign with [`TEST_PRIVATE_KEY`]
+  pub fn with_test_key(url: Url) -> Result<Self, WebhookError> {
+    Self::new(url, TEST_PRIVATE_KEY)
+  }
+
+  /// The [`SIGNATURE_HEADER`] value for `body`
+  pub fn sign(&self, body: &[u8]) -> Result<String, WebhookError> {
+    let sig = jsonwebtoken::crypto::sign(body, &self.key, Algorithm::RS512)?;
+    let raw = URL_SAFE_NO_PAD.decode(sig).map_err(|_| WebhookError::InvalidSignature)?;
+    Ok(STANDARD.encode(raw))
+  }
+
+  /// Post a raw body, returning the status code of the endpoint
+  pub async fn emit_raw(&self, body: Vec<u8>, signature: Signature) -> Result<StatusCode, WebhookError> {
+    let mut req = self.client.post(self.url.clone()).header(reqwest::header::CONTENT_TYPE, "application/json");
+    match signature {
+      Signature::Valid => req = req.header(SIGNATURE_HEADER, self.sign(&body)?),
+      Signature::Invalid => req = req.header(SIGNATURE_HEADER, self.sign(b"{}")?),
+      Signature::Missing => {},
+    }
+    Ok(req.body(body).send().await?.status())
+  }
+
+  pub async fn emit(&self, msg: &WebhookMessage, signature: Signature) -> Result<StatusCode, WebhookError> {
+    self.emit_raw(to_body(msg)?, signature).await
+  }
+
+  /// Post one [`sample_events`] notification of every event type
+  pub async fn emit_all(&self, signature: Signature) -> Result<Vec<(String, StatusCode)>, WebhookError> {
+    let mut results = vec![];
+    for msg in sample_events() {
+      let status = self.emit(&msg, signature).await?;
+      results.push((msg.event.event_type().to_string(), status));
+    }
+    Ok(results)
+  }
+}
+
+/// Serialize like Fireblocks, which omits absent fields rather than sending `null`
+fn to_body(msg: &WebhookMessage) -> Result<Vec<u8>, WebhookError> {
+  fn strip_nulls(value: &mut Value) {
+    match value {
+      Value::Object(map) => {
+        map.retain(|_, v| !v.is_null());
+        map.values_mut().for_each(strip_nulls);
+      },
+      Value::Array(values) => values.iter_mut().for_each(strip_nulls),
+      _ => {},
+    }
+  }
+  let mut value = serde_json::to_value(msg)?;
+  strip_nulls(&mut value);
+  Ok(serde_json::to_vec(&value)?)
+}
+
+/// A notification of every known [`WebhookEvent`] type with placeholder payloads
+pub fn sample_events() -> Vec<WebhookMessage> {
+  let tx = Transaction {
+    id: "00000000-0000-0000-0000-000000000001".to_string(),
+    status: TransactionStatus::SUBMITTED,
+    ..Default::default()
+  };
+  let completed = Transaction { status: TransactionStatus::COMPLETED, ..tx.clone() };
+  let wallet = WalletAssetEvent::default;
+  let account = AccountAdded::default;
+  let embedded = EmbeddedWalletEvent::default;
+  vec![
+    WebhookEvent::TRANSACTION_CREATED(tx.clone()),
+    WebhookEvent::TRANSACTION_STATUS_UPDATED(completed.clone()),
+    WebhookEvent::TRANSACTION_APPROVAL_STATUS_UPDATED(tx),
+    WebhookEvent::TRANSACTION_NETWORK_RECORDS_PROCESSING_COMPLETED(completed),
+    WebhookEvent::VAULT_ACCOUNT_ADDED(VaultAccountAdded { name: "test".to_string(), ..Default::default() }),
+    WebhookEvent::VAULT_ACCOUNT_ASSET_ADDED(VaultAssetAdded::default()),
+    WebhookEvent::VAULT_BALANCE_UPDATE(VaultBalanceUpdate::default()),
+    WebhookEvent::INTERNAL_WALLET_ASSET_ADDED(wallet()),
+    WebhookEvent::INTERNAL_WALLET_ASSET_REMOVED(wallet()),
+    WebhookEvent::EXTERNAL_WALLET_ASSET_ADDED(wallet()),
+    WebhookEvent::EXTERNAL_WALLET_ASSET_REMOVED(wallet()),
+    WebhookEvent::CONTRACT_WALLET_ASSET_ADDED(wallet()),
+    WebhookEvent::CONTRACT_WALLET_ASSET_REMOVED(wallet()),
+    WebhookEvent::EXCHANGE_ACCOUNT_ADDED(account()),
+    WebhookEvent::FIAT_ACCOUNT_ADDED(account()),
+    WebhookEvent::NETWORK_CONNECTION_ADDED(NetworkConnectionAdded::default()),
+    WebhookEvent::EMBEDDED_WALLET_CREATED(embedded()),
+    WebhookEvent::EMBEDDED_WALLET_STATUS_UPDATED(embedded()),
+    WebhookEvent::EMBEDDED_WALLET_ACCOUNT_CREATED(embedded()),
+    WebhookEvent::EMBEDDED_WALLET_ASSET_ADDED(embedded()),
+    WebhookEvent::EMBEDDED_WALLET_ASSET_BALANCE_UPDATED(embedded()),
+  ]
+  .into_iter()
+  .map(|event| WebhookMessage {
+    tenant_id: Some("00000000-0000-0000-0000-000000000000".to_string()),
+    timestamp: Some(chrono::Utc::now().timestamp_millis()),
+    event,
+  })
+  .collect()
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::webhook::WebhookVerifier;
+  use tokio::io::{AsyncReadExt, AsyncWriteExt};
+  use tokio::net::TcpListener;
+
+  /// Accept one request and verify it like a webhook handler would
+  async fn receive(listener: &TcpListener, verifier: &WebhookVerifier) -> color_eyre::Result<bool> {
+    let (mut socket, _) = listener.accept().await?;
+    let mut buf = vec![];
+    let (headers, body) = loop {
+      let mut chunk = [0u8; 4096];
+      let n = socket.read(&mut chunk).await?;
+      buf.extend_from_slice(&chunk[..n]);
+      let text = String::from_utf8_lossy(&buf).to_string();
+      if let Some(end) = text.find("\r\n\r\n") {
+        let headers: Vec<(String, String)> = text[..end]
+          .lines()
+          .filter_map(|l| l.split_once(':'))
+          .map(|(k, v)| (k.to_lowercase(), v.trim().to_string()))
+          .collect();
+        let len: usize = headers.iter().find(|(k, _)| k == "content-length").map_or(Ok(0), |(_, v)| v.parse())?;
+        if buf.len() >= end + 4 + len {
+          break (headers, buf[end + 4..end + 4 + len].to_vec());
+        }
+      }
+    };
+    let signature = headers.into_iter().find(|(k, _)| k == SIGNATURE_HEADER).map(|(_, v)| v);
+    let ok = verifier.parse(&body, signature.as_deref()).is_ok();
+    let status = if ok { "200 OK" } else { "401 Unauthorized" };
+    socket.write_all(format!("HTTP/1.1 {status}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n").as_bytes()).await?;
+    Ok(ok)
+  }
+
+  #[test]
+  fn test_sample_events() -> color_eyre::Result<()> {
+    let emitter = WebhookEmitter::with_test_key("http://localhost".parse()?)?;
+    let verifier = WebhookVerifier::new(TEST_PUBLIC_KEY)?;
+    for msg in sample_events() {
+      let body = to_body(&msg)?;
+      let parsed = verifier.parse(&body, Some(&emitter.sign(&body)?))?;
+      assert_eq!(parsed.event.event_type(), msg.event.event_type());
+    }
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn test_emit() -> color_eyre::Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("http://{}/webhook", listener.local_addr()?).parse()?;
+    let emitter = WebhookEmitter::with_test_key(url)?;
+    let verifier = WebhookVerifier::new(TEST_PUBLIC_KEY)?;
+    let msg = &sample_events()[0];
+    for (signature, expected) in [
+      (Signature::Valid, StatusCode::OK),
+      (Signature::Invalid, StatusCode::UNAUTHORIZED),
+      (Signature::Missing, StatusCode::UNAUTHORIZED),
+    ] {
+      let (status, accepted) = tokio::join!(emitter.emit(msg, signature), receive(&listener, &verifier));
+      assert_eq!(status?, expected);
+      assert_eq!(accepted?, signature == Signature::Valid);
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,287 @@
+use crate::types::{deserialize_str_i32, Transaction};
+use bigdecimal::BigDecimal;
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A webhook notification as posted by Fireblocks
+///
+/// ```
+/// use fireblocks_sdk::webhook::{WebhookEvent, WebhookMessage};
+///
+/// let body = r#"{"type": "VAULT_ACCOUNT_ASSET_ADDED", "tenantId": "t", "timestamp": 1, "data": {"accountId": "1", "tenantId": "t", "accountName": "ops", "assetId": "ETH"}}"#;
+/// let msg: WebhookMessage = serde_json::from_str(body).expect("invalid webhook");
+/// assert!(matches!(msg.event, WebhookEvent::VAULT_ACCOUNT_ASSET_ADDED(_)));
+/// ```
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase", try_from = "RawWebhookMessage")]
+pub struct WebhookMessage {
+  pub tenant_id: Option<String>,
+  /// Milliseconds since epoch
+  pub timestamp: Option<i64>,
+  #[serde(flatten)]
+  pub event: WebhookEvent,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawWebhookMessage {
+  tenant_id: Option<String>,
+  timestamp: Option<i64>,
+  #[serde(rename = "type")]
+  event_type: String,
+  #[serde(default)]
+  data: Value,
+}
+
+impl TryFrom<RawWebhookMessage> for WebhookMessage {
+  type Error = serde_json::Error;
+
+  fn try_from(raw: RawWebhookMessage) -> Result<Self, Self::Error> {
+    let event_type = serde_json::from_value::<WebhookEventType>(Value::String(raw.event_type.clone()))?;
+    let known = event_type != WebhookEventType::UNKNOWN;
+    let event = if known {
+      serde_json::from_value(serde_json::json!({ "type": raw.event_type, "data": raw.data }))?
+    } else {
+      WebhookEvent::UNKNOWN(raw.event_type, raw.data)
+    };
+    Ok(Self { tenant_id: raw.tenant_id, timestamp: raw.timestamp, event })
+  }
+}
+
+/// Defines [`WebhookEvent`], [`WebhookEventType`] and [`WebhookEvent::event_type`] from one list of types and
+/// payloads
+macro_rules! webhook_events {
+  ($($(#[$attr:meta])* $event:ident($payload:ty)),+ $(,)?) => {
+    /// Webhook payloads keyed by their `type`. When received as a [`WebhookMessage`], types this
+    /// crate does not know yet become [`WebhookEvent::UNKNOWN`] rather than failing
+    #[allow(non_camel_case_types)]
+    #[allow(clippy::upper_case_acronyms)]
+    #[allow(clippy::large_enum_variant)]
+    #[derive(Debug, Deserialize, Serialize, Clone)]
+    #[serde(tag = "type", content = "data")]
+    pub enum WebhookEvent {
+      $($(#[$attr])* $event($payload),)+
+      /// The unknown `type` and its `data`. Cannot be serialized
+      #[serde(skip)]
+      UNKNOWN(String, Value),
+    }
+
+    impl WebhookEvent {
+      /// The `type` of the notification
+      pub fn event_type(&self) -> &str {
+        match self {
+          $(Self::$event(_) => stringify!($event),)+
+          Self::UNKNOWN(t, _) => t,
+        }
+      }
+    }
+
+    /// Event types a webhook can subscribe to, the `type` of a [`WebhookEvent`]
+    #[allow(non_camel_case_types)]
+    #[allow(clippy::upper_case_acronyms)]
+    #[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum WebhookEventType {
+      $($(#[$attr])* $event,)+
+      #[serde(other)]
+      UNKNOWN,
+    }
+  };
+}
+
+webhook_events!(
+  TRANSACTION_CREATED(Transaction),
+  TRANSACTION_STATUS_UPDATED(Transaction),
+  TRANSACTION_APPROVAL_STATUS_UPDATED(Transaction),
+  TRANSACTION_NETWORK_RECORDS_PROCESSING_COMPLETED(Transaction),
+  #[serde(alias = "VAULT_ACCOUNT_CREATED")]
+  VAULT_ACCOUNT_ADDED(VaultAccountAdded),
+  VAULT_ACCOUNT_ASSET_ADDED(VaultAssetAdded),
+  VAULT_BALANCE_UPDATE(VaultBalanceUpdate),
+  INTERNAL_WALLET_ASSET_ADDED(WalletAssetEvent),
+  INTERNAL_WALLET_ASSET_REMOVED(WalletAssetEvent),
+  EXTERNAL_WALLET_ASSET_ADDED(WalletAssetEvent),
+  EXTERNAL_WALLET_ASSET_REMOVED(WalletAssetEvent),
+  CONTRACT_WALLET_ASSET_ADDED(WalletAssetEvent),
+  CONTRACT_WALLET_ASSET_REMOVED(WalletAssetEvent),
+  EXCHANGE_ACCOUNT_ADDED(AccountAdded),
+  FIAT_ACCOUNT_ADDED(AccountAdded),
+  NETWORK_CONNECTION_ADDED(NetworkConnectionAdded),
+  EMBEDDED_WALLET_CREATED(EmbeddedWalletEvent),
+  EMBEDDED_WALLET_STATUS_UPDATED(EmbeddedWalletEvent),
+  EMBEDDED_WALLET_ACCOUNT_CREATED(EmbeddedWalletEvent),
+  EMBEDDED_WALLET_ASSET_ADDED(EmbeddedWalletEvent),
+  EMBEDDED_WALLET_ASSET_BALANCE_UPDATED(EmbeddedWalletEvent),
+);
+
+impl WebhookEvent {
+  /// The transaction of `TRANSACTION_*` events
+  pub const fn transaction(&self) -> Option<&Transaction> {
+    match self {
+      Self::TRANSACTION_CREATED(tx)
+      | Self::TRANSACTION_STATUS_UPDATED(tx)
+      | Self::TRANSACTION_APPROVAL_STATUS_UPDATED(tx)
+      | Self::TRANSACTION_NETWORK_RECORDS_PROCESSING_COMPLETED(tx) => Some(tx),
+      _ => None,
+    }
+  }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultAccountAdded {
+  #[serde(deserialize_with = "deserialize_str_i32")]
+  pub id: i32,
+  pub name: String,
+  #[serde(rename = "hiddenOnUI", default)]
+  pub hidden_on_ui: bool,
+  pub customer_ref_id: Option<String>,
+  #[serde(default)]
+  pub auto_fuel: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultAssetAdded {
+  #[serde(deserialize_with = "deserialize_str_i32")]
+  pub account_id: i32,
+  pub tenant_id: Option<String>,
+  pub account_name: Option<String>,
+  pub asset_id: String,
+}
+
+/// Balances are only set when the notification carries them, otherwise fetch the vault asset
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultBalanceUpdate {
+  #[serde(alias = "accountId", deserialize_with = "deserialize_str_i32")]
+  pub vault_account_id: i32,
+  pub tenant_id: Option<String>,
+  pub asset_id: String,
+  pub total: Option<BigDecimal>,
+  pub available: Option<BigDecimal>,
+  pub pending: Option<BigDecimal>,
+  pub frozen: Option<BigDecimal>,
+  pub locked_amount: Option<BigDecimal>,
+}
+
+/// An asset added to or removed from an internal, external or contract wallet
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletAssetEvent {
+  pub wallet_id: String,
+  pub tenant_id: Option<String>,
+  pub asset_id: String,
+  pub address: Option<String>,
+  pub tag: Option<String>,
+}
+
+/// An exchange or fiat account connected to the workspace
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountAdded {
+  pub id: String,
+  pub tenant_id: Option<String>,
+  pub name: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkId {
+  pub id: String,
+  pub name: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkConnectionAdded {
+  pub id: String,
+  pub local_network_id: Option<NetworkId>,
+  pub remote_network_id: Option<NetworkId>,
+}
+
+/// Notification about an embedded (NCW) wallet. Which fields are set depends on the event
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddedWalletEvent {
+  pub wallet_id: String,
+  pub account_id: Option<String>,
+  pub asset_id: Option<String>,
+  pub enabled: Option<bool>,
+  pub total: Option<BigDecimal>,
+  pub available: Option<BigDecimal>,
+}
+
+#[cfg(test)]
+mod test {
+  use super::{WebhookEvent, WebhookEventType, WebhookMessage};
+  use crate::types::TransactionStatus;
+
+  #[test]
+  fn test_json_webhook_events() -> color_eyre::Result<()> {
+    let data = r#"
+    {
+      "type": "TRANSACTION_STATUS_UPDATED",
+      "tenantId": "6d1ef2f2-7b2c-4f3d-9a37-1c8c7b8f2e11",
+      "timestamp": 1700000000000,
+      "data": {
+        "id": "e01b1c68-2d26-45dc-bb02-4cc9152295e1",
+        "assetId": "ETH_TEST5",
+        "source": {"id": "0", "type": "VAULT_ACCOUNT", "name": "Default"},
+        "destination": {"id": "1", "type": "VAULT_ACCOUNT", "name": "Ops"},
+        "status": "COMPLETED",
+        "subStatus": "CONFIRMED",
+        "txHash": "0xabc",
+        "createdAt": 1700000000000,
+        "lastUpdated": 1700000000000,
+        "destinationAddress": "0x0",
+        "sourceAddress": "",
+        "destinationAddressDescription": "",
+        "destinationTag": "",
+        "signedBy": [],
+        "createdBy": "u",
+        "rejectedBy": "",
+        "addressType": "",
+        "note": "",
+        "exchangeTxId": "",
+        "feeCurrency": "ETH_TEST5",
+        "operation": "TRANSFER",
+        "amountInfo": {},
+        "feeInfo": {},
+        "signedMessages": [],
+        "numOfConfirmations": 3,
+        "networkRecords": []
+      }
+    }
+    "#;
+    // an unknown value inside a known event is invalid, not an unknown event
+    assert!(serde_json::from_str::<WebhookMessage>(&data.replace(r#""TRANSFER""#, r#""TELEPORT""#)).is_err());
+    let msg: WebhookMessage = serde_json::from_str(data)?;
+    let WebhookEvent::TRANSACTION_STATUS_UPDATED(tx) = msg.event else {
+      return Err(color_eyre::eyre::format_err!("wrong event {:?}", msg.event));
+    };
+    assert_eq!(TransactionStatus::COMPLETED, tx.status);
+
+    let data = r#"{"type": "VAULT_BALANCE_UPDATE", "data": {"accountId": "3", "assetId": "BTC", "total": "1.5"}}"#;
+    let msg: WebhookMessage = serde_json::from_str(data)?;
+    assert!(matches!(msg.event, WebhookEvent::VAULT_BALANCE_UPDATE(ref b) if b.vault_account_id == 3));
+    assert_eq!("VAULT_BALANCE_UPDATE", msg.event.event_type());
+
+    let data = r#"{"type": "SOMETHING_NEW", "data": {"id": "1"}}"#;
+    let msg: WebhookMessage = serde_json::from_str(data)?;
+    assert!(matches!(msg.event, WebhookEvent::UNKNOWN(ref t, ref data) if t == "SOMETHING_NEW" && data["id"] == "1"));
+
+    let data = r#"{"type": "VAULT_BALANCE_UPDATE", "data": {"assetId": "BTC"}}"#;
+    assert!(serde_json::from_str::<WebhookMessage>(data).is_err());
+    Ok(())
+  }
+
+  #[test]
+  fn test_event_types() -> color_eyre::Result<()> {
+    let parse = |t: &str| serde_json::from_str::<WebhookEventType>(&format!("\"{t}\""));
+    assert_eq!(parse("VAULT_BALANCE_UPDATE")?, WebhookEventType::VAULT_BALANCE_UPDATE);
+    assert_eq!(parse("VAULT_ACCOUNT_CREATED")?, WebhookEventType::VAULT_ACCOUNT_ADDED);
+    assert_eq!(parse("SOMETHING_NEW")?, WebhookEventType::UNKNOWN);
+    assert_eq!(serde_json::to_string(&WebhookEventType::EMBEDDED_WALLET_CREATED)?, "\"EMBEDDED_WALLET_CREATED\"");
+    Ok(())
+  }
+}
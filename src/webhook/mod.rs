@@ -0,0 +1,23 @@
+//! Types and helpers for consuming Fireblocks webhook notifications
+//!
+//! See
+//! * [Webhooks](https://developers.fireblocks.com/docs/webhooks-notifications)
+#[cfg(feature = "actix-web")]
+pub mod actix;
+#[cfg(feature = "axum")]
+pub mod axum;
+mod dedup;
+mod dispatcher;
+#[cfg(feature = "test-utils")]
+mod emitter;
+mod event;
+mod stream;
+mod verify;
+
+pub use dedup::*;
+pub use dispatcher::*;
+#[cfg(feature = "test-utils")]
+pub use emitter::*;
+pub use event::*;
+pub use stream::*;
+pub use verify::*;
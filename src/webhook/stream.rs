@@ -0,0 +1,96 @@
+use crate::webhook::WebhookEvent;
+use futures::Stream;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::sync::mpsc;
+
+/// Create a bounded bridge between webhook handlers and a [`WebhookStream`], so push
+/// notifications can be consumed with the same combinators as the paged streams
+///
+/// ```
+/// use fireblocks_sdk::webhook::{webhook_channel, WebhookMessage};
+/// use futures::StreamExt;
+///
+/// # async fn run(msg: WebhookMessage) {
+/// let (sender, stream) = webhook_channel(1024);
+/// // in the webhook handler
+/// sender.send(msg.event).await.ok();
+/// // elsewhere
+/// let mut txs = stream.filter_map(|e| async move { e.transaction().cloned() }).boxed();
+/// while let Some(tx) = txs.next().await {
+///   println!("{}", tx.id);
+/// }
+/// # }
+/// ```
+pub fn webhook_channel(buffer: usize) -> (WebhookSender, WebhookStream) {
+  let (tx, rx) = mpsc::channel(buffer.max(1));
+  (WebhookSender { tx }, WebhookStream { rx })
+}
+
+#[derive(Debug, Clone)]
+pub struct WebhookSender {
+  tx: mpsc::Sender<WebhookEvent>,
+}
+
+impl WebhookSender {
+  /// Wait for room in the channel. Gives the event back if the stream was dropped
+  pub async fn send(&self, event: WebhookEvent) -> Result<(), Box<WebhookEvent>> {
+    self.tx.send(event).await.map_err(|e| Box::new(e.0))
+  }
+
+  /// Send without waiting, e.g. from a webhook request that must be answered promptly.
+  /// Gives the event back if the channel is full or the stream was dropped
+  pub fn try_send(&self, event: WebhookEvent) -> Result<(), Box<WebhookEvent>> {
+    self.tx.try_send(event).map_err(|e| match e {
+      mpsc::error::TrySendError::Full(e) | mpsc::error::TrySendError::Closed(e) => Box::new(e),
+    })
+  }
+}
+
+/// Webhook events received by the paired [`WebhookSender`]s. Ends once all senders are dropped
+#[derive(Debug)]
+pub struct WebhookStream {
+  rx: mpsc::Receiver<WebhookEvent>,
+}
+
+impl Stream for WebhookStream {
+  type Item = WebhookEvent;
+
+  fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+    self.rx.poll_recv(cx)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::webhook_channel;
+  use crate::types::Transaction;
+  use crate::webhook::WebhookEvent;
+  use futures::StreamExt;
+  use serde_json::Value;
+
+  #[tokio::test]
+  async fn test_stream() {
+    let (sender, stream) = webhook_channel(1);
+    let producer = sender.clone();
+    tokio::spawn(async move {
+      for id in ["a", "b"] {
+        let tx = Transaction { id: id.to_owned(), ..Default::default() };
+        producer.send(WebhookEvent::TRANSACTION_CREATED(tx)).await.ok();
+      }
+    });
+    drop(sender);
+    let ids: Vec<String> = stream.filter_map(|e| async move { e.transaction().map(|t| t.id.clone()) }).collect().await;
+    assert_eq!(vec!["a".to_owned(), "b".to_owned()], ids);
+  }
+
+  #[tokio::test]
+  async fn test_try_send() {
+    let (sender, mut stream) = webhook_channel(1);
+    assert!(sender.try_send(WebhookEvent::UNKNOWN("A".to_owned(), Value::Null)).is_ok());
+    assert!(sender.try_send(WebhookEvent::UNKNOWN("B".to_owned(), Value::Null)).is_err());
+    assert!(matches!(stream.next().await, Some(WebhookEvent::UNKNOWN(t, _)) if t == "A"));
+    drop(stream);
+    assert!(sender.send(WebhookEvent::UNKNOWN("C".to_owned(), Value::Null)).await.is_err());
+  }
+}
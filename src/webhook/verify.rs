@@ -0,0 +1,76 @@
+use crate::error::WebhookError;
+use crate::webhook::WebhookMessage;
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use base64::Engine;
+use jsonwebtoken::{Algorithm, DecodingKey};
+
+/// Header carrying the base64 RSA-SHA512 signature of the request body
+pub const SIGNATURE_HEADER: &str = "fireblocks-signature";
+
+/// Authenticates webhook notifications with the Fireblocks webhook public key
+///
+/// The public keys of the production and sandbox environments are published in the
+/// [webhook docs](https://developers.fireblocks.com/docs/webhooks-notifications#validation)
+#[derive(Clone)]
+pub struct WebhookVerifier {
+  key: DecodingKey,
+}
+
+impl std::fmt::Debug for WebhookVerifier {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("WebhookVerifier").finish_non_exhaustive()
+  }
+}
+
+impl WebhookVerifier {
+  pub fn new(public_key_pem: &[u8]) -> Result<Self, WebhookError> {
+    Ok(Self { key: DecodingKey::from_rsa_pem(public_key_pem)? })
+  }
+
+  /// Check `signature`, the value of the [`SIGNATURE_HEADER`] header, against the raw body
+  pub fn verify(&self, body: &[u8], signature: &str) -> Result<(), WebhookError> {
+    let raw = STANDARD.decode(signature.trim()).map_err(|_| WebhookError::InvalidSignature)?;
+    match jsonwebtoken::crypto::verify(&URL_SAFE_NO_PAD.encode(raw), body, &self.key, Algorithm::RS512) {
+      Ok(true) => Ok(()),
+      _ => Err(WebhookError::InvalidSignature),
+    }
+  }
+
+  /// Verify and deserialize a notification
+  pub fn parse(&self, body: &[u8], signature: Option<&str>) -> Result<WebhookMessage, WebhookError> {
+    self.verify(body, signature.ok_or(WebhookError::MissingSignature)?)?;
+    Ok(serde_json::from_slice(body)?)
+  }
+}
+
+#[cfg(test)]
+pub mod test {
+  use super::WebhookVerifier;
+  use crate::error::WebhookError;
+  use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+  use base64::Engine;
+  use jsonwebtoken::{Algorithm, EncodingKey};
+
+  pub const PUBLIC_KEY: &[u8] = include_bytes!("testdata/webhook_public.pem");
+  const PRIVATE_KEY: &[u8] = include_bytes!("testdata/webhook_private.pem");
+  pub const BODY: &[u8] =
+    br#"{"type": "VAULT_ACCOUNT_ASSET_ADDED", "tenantId": "t", "timestamp": 1, "data": {"accountId": "1", "assetId": "ETH"}}"#;
+
+  /// Sign like Fireblocks does, for tests of the webhook integrations
+  pub fn sign(body: &[u8]) -> color_eyre::Result<String> {
+    let sig = jsonwebtoken::crypto::sign(body, &EncodingKey::from_rsa_pem(PRIVATE_KEY)?, Algorithm::RS512)?;
+    Ok(STANDARD.encode(URL_SAFE_NO_PAD.decode(sig)?))
+  }
+
+  #[test]
+  fn test_verify() -> color_eyre::Result<()> {
+    let v = WebhookVerifier::new(PUBLIC_KEY)?;
+    let sig = sign(BODY)?;
+    v.parse(BODY, Some(&sig))?;
+    assert!(matches!(v.parse(BODY, None), Err(WebhookError::MissingSignature)));
+    let tampered = String::from_utf8_lossy(BODY).replace("ETH", "BTC");
+    assert!(matches!(v.verify(tampered.as_bytes(), &sig), Err(WebhookError::InvalidSignature)));
+    assert!(matches!(v.verify(BODY, "not base64!"), Err(WebhookError::InvalidSignature)));
+    Ok(())
+  }
+}
@@ -0,0 +1,173 @@
+//! Clients for several Fireblocks workspaces, routed by workspace id
+//!
+//! A TOML file for [`WorkspaceManager::from_file`] has one table of [`crate::config`] keys per workspace
+//!
+//! ```toml
+//! [treasury]
+//! api_key = "00000000-0000-0000-0000-000000000000"
+//! secret_path = "/run/secrets/treasury.key"
+//!
+//! [trading]
+//! api_key = "11111111-1111-1111-1111-111111111111"
+//! secret_path = "/run/secrets/trading.key"
+//! environment = "sandbox"
+//! ```
+//!
+//! ```
+//! use fireblocks_sdk::workspace::WorkspaceManager;
+//! use futures::StreamExt;
+//!
+//! async fn accounts(workspaces: WorkspaceManager) -> color_eyre::Result<()> {
+//!   let (account, _) = workspaces.call("treasury", |c| c.vault(0)).await?;
+//!   println!("treasury {}", account.name);
+//!   let mut pages = workspaces.vaults(100);
+//!   while let Some((workspace, page)) = pages.next().await {
+//!     println!("{workspace} {} accounts", page?.0.items.len());
+//!   }
+//!   Ok(())
+//! }
+//! ```
+use crate::config::ClientConfig;
+use crate::error::ClientError;
+use crate::types::Account;
+use crate::{Client, FireblocksError, Page, PagedClient};
+use futures::stream::{self, SelectAll};
+use futures::{Stream, StreamExt};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::Path;
+use std::sync::Arc;
+
+#[derive(Clone, Default)]
+pub struct WorkspaceManager {
+  workspaces: BTreeMap<String, PagedClient>,
+}
+
+impl WorkspaceManager {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Build a client for each workspace
+  pub fn from_configs<'a>(configs: impl IntoIterator<Item = (&'a str, &'a ClientConfig)>) -> Result<Self, ClientError> {
+    let mut manager = Self::new();
+    for (id, config) in configs {
+      manager.insert(id, config.builder()?.build()?);
+    }
+    Ok(manager)
+  }
+
+  /// See the module documentation for the layout
+  pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ClientError> {
+    let configs: BTreeMap<String, ClientConfig> = toml::from_str(&std::fs::read_to_string(path)?)?;
+    Self::from_configs(configs.iter().map(|(id, config)| (id.as_str(), config)))
+  }
+
+  #[allow(clippy::return_self_not_must_use)]
+  pub fn with_workspace(mut self, id: &str, client: Client) -> Self {
+    self.insert(id, client);
+    self
+  }
+
+  /// Add or replace the client of a workspace, returning the replaced one
+  pub fn insert(&mut self, id: &str, client: Client) -> Option<Arc<Client>> {
+    self.workspaces.insert(String::from(id), PagedClient::new(Arc::new(client))).map(|pc| pc.client)
+  }
+
+  pub fn remove(&mut self, id: &str) -> Option<Arc<Client>> {
+    self.workspaces.remove(id).map(|pc| pc.client)
+  }
+
+  /// Workspace ids in order
+  pub fn ids(&self) -> impl Iterator<Item = &str> {
+    self.workspaces.keys().map(String::as_str)
+  }
+
+  pub fn client(&self, id: &str) -> Result<&Arc<Client>, FireblocksError> {
+    self.paged(id).map(|pc| &pc.client)
+  }
+
+  pub fn paged(&self, id: &str) -> Result<&PagedClient, FireblocksError> {
+    self.workspaces.get(id).ok_or_else(|| FireblocksError::UnknownWorkspace(String::from(id)))
+  }
+
+  /// Make a call with the client of workspace `id`
+  pub async fn call<'a, T, F, Fut>(&'a self, id: &str, call: F) -> crate::Result<T>
+  where
+    F: FnOnce(&'a Client) -> Fut,
+    Fut: Future<Output = crate::Result<T>>,
+  {
+    call(self.client(id)?).await
+  }
+
+  /// Make a call with every client concurrently, results are in workspace id order
+  pub async fn call_all<'a, T, F, Fut>(&'a self, call: F) -> Vec<(String, crate::Result<T>)>
+  where
+    F: Fn(&'a Client) -> Fut,
+    Fut: Future<Output = crate::Result<T>>,
+  {
+    let calls = self.workspaces.iter().map(|(id, pc)| {
+      let fut = call(&pc.client);
+      async move { (id.clone(), fut.await) }
+    });
+    futures::future::join_all(calls).await
+  }
+
+  /// Merge a paged stream of every workspace, items are tagged with their workspace id and arrive as
+  /// soon as any workspace yields them
+  pub fn streams<S, F>(&self, stream: F) -> SelectAll<stream::BoxStream<'static, (String, S::Item)>>
+  where
+    S: Stream + Send + 'static,
+    F: Fn(&PagedClient) -> S,
+  {
+    stream::select_all(self.workspaces.iter().map(|(id, pc)| {
+      let id = id.clone();
+      stream(pc).map(move |item| (id.clone(), item)).boxed()
+    }))
+  }
+
+  /// Vault accounts of every workspace, see [`PagedClient::vaults`]
+  pub fn vaults(&self, batch_size: u16) -> impl Stream<Item = (String, crate::Result<Page<Account>>)> {
+    self.streams(|pc| pc.vaults(batch_size))
+  }
+}
+
+#[cfg(all(test, feature = "reqwest"))]
+mod test {
+  use super::*;
+  use crate::mock::{self, MockTransport, SECRET_PATH};
+  use http::StatusCode;
+
+  fn client() -> color_eyre::Result<Client> {
+    mock::client(MockTransport::new(|_| mock::json(StatusCode::OK, "{}")))
+  }
+
+  #[tokio::test]
+  async fn test_routing() -> color_eyre::Result<()> {
+    let mut workspaces = WorkspaceManager::new().with_workspace("b", client()?).with_workspace("a", client()?);
+    assert_eq!(workspaces.ids().collect::<Vec<_>>(), ["a", "b"]);
+    assert!(workspaces.client("a").is_ok());
+    let missing = workspaces.call("c", |c| c.vault(0)).await;
+    assert!(matches!(missing, Err(FireblocksError::UnknownWorkspace(id)) if id == "c"));
+    assert!(workspaces.insert("a", client()?).is_some());
+    assert!(workspaces.remove("b").is_some());
+    assert_eq!(workspaces.ids().count(), 1);
+
+    let tagged = workspaces.streams(|_| stream::iter([1, 2])).collect::<Vec<_>>().await;
+    assert_eq!(tagged, [(String::from("a"), 1), (String::from("a"), 2)]);
+    Ok(())
+  }
+
+  #[test]
+  fn test_from_file() -> color_eyre::Result<()> {
+    let path = std::env::temp_dir().join(format!("fireblocks-workspaces-{}.toml", std::process::id()));
+    let toml = format!(
+      "[treasury]\napi_key = \"a\"\nsecret_path = \"{SECRET_PATH}\"\n\n[trading]\napi_key = \"b\"\nsecret_path = \"{SECRET_PATH}\"\nenvironment = \"sandbox\"\n"
+    );
+    std::fs::write(&path, toml)?;
+    let workspaces = WorkspaceManager::from_file(&path);
+    std::fs::remove_file(&path)?;
+    assert_eq!(workspaces?.ids().collect::<Vec<_>>(), ["trading", "treasury"]);
+    Ok(())
+  }
+}